@@ -0,0 +1,127 @@
+//! Parsing of `#[neon(...)]` attributes.
+
+use syn::{Attribute, Error, Lit, Meta, NestedMeta, Result};
+use syn::spanned::Spanned;
+
+use case::Case;
+
+/// Attributes on a struct or enum.
+#[derive(Default)]
+pub struct Container {
+    /// `rename_all = "..."`: renames the fields of a struct or the variants of an enum.
+    pub rename_all: Option<Case>,
+    /// `tag = "..."`: the property holding the variant name of a tagged enum.
+    pub tag: Option<String>,
+    /// `content = "..."`: the property holding the value of a tuple variant.
+    pub content: Option<String>
+}
+
+/// Attributes on an enum variant.
+#[derive(Default)]
+pub struct Variant {
+    /// `rename = "..."`: the name of the variant in JavaScript.
+    pub rename: Option<String>,
+    /// `rename_all = "..."`: renames the fields of a struct variant.
+    pub rename_all: Option<Case>
+}
+
+/// Attributes on a struct field.
+#[derive(Default)]
+pub struct Field {
+    /// `rename = "..."`: the name of the property in JavaScript.
+    pub rename: Option<String>,
+    /// `default`: a missing or `undefined` property produces `Default::default()`.
+    pub default: bool
+}
+
+impl Container {
+    pub fn parse(attrs: &[Attribute]) -> Result<Container> {
+        let mut result = Container::default();
+        for meta in neon_metas(attrs)? {
+            if meta.path().is_ident("rename_all") {
+                result.rename_all = Some(parse_case(&meta)?);
+            } else if meta.path().is_ident("tag") {
+                result.tag = Some(parse_string(&meta)?);
+            } else if meta.path().is_ident("content") {
+                result.content = Some(parse_string(&meta)?);
+            } else {
+                return Err(Error::new(meta.span(), "unknown neon container attribute"));
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Variant {
+    pub fn parse(attrs: &[Attribute]) -> Result<Variant> {
+        let mut result = Variant::default();
+        for meta in neon_metas(attrs)? {
+            if meta.path().is_ident("rename") {
+                result.rename = Some(parse_string(&meta)?);
+            } else if meta.path().is_ident("rename_all") {
+                result.rename_all = Some(parse_case(&meta)?);
+            } else {
+                return Err(Error::new(meta.span(), "unknown neon variant attribute"));
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Field {
+    pub fn parse(attrs: &[Attribute]) -> Result<Field> {
+        let mut result = Field::default();
+        for meta in neon_metas(attrs)? {
+            if meta.path().is_ident("rename") {
+                result.rename = Some(parse_string(&meta)?);
+            } else if meta.path().is_ident("default") {
+                match meta {
+                    Meta::Path(_) => result.default = true,
+                    _ => return Err(Error::new(meta.span(), "expected `default` without a value"))
+                }
+            } else {
+                return Err(Error::new(meta.span(), "unknown neon field attribute"));
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Collects the items of every `#[neon(...)]` attribute.
+fn neon_metas(attrs: &[Attribute]) -> Result<Vec<Meta>> {
+    let mut metas = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("neon") {
+            continue;
+        }
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(meta) => metas.push(meta),
+                        NestedMeta::Lit(lit) => return Err(Error::new(lit.span(), "expected a neon attribute"))
+                    }
+                }
+            }
+            meta => return Err(Error::new(meta.span(), "expected `#[neon(...)]`"))
+        }
+    }
+    Ok(metas)
+}
+
+fn parse_string(meta: &Meta) -> Result<String> {
+    match *meta {
+        Meta::NameValue(ref nv) => match nv.lit {
+            Lit::Str(ref s) => Ok(s.value()),
+            ref lit => Err(Error::new(lit.span(), "expected a string"))
+        },
+        _ => Err(Error::new(meta.span(), "expected `name = \"value\"`"))
+    }
+}
+
+fn parse_case(meta: &Meta) -> Result<Case> {
+    let value = parse_string(meta)?;
+    Case::from_str(&value).ok_or_else(|| Error::new(meta.span(), format!(
+        "unknown case `{}`, expected one of `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase`, \
+         `snake_case`, `SCREAMING_SNAKE_CASE` or `kebab-case`", value)))
+}
@@ -0,0 +1,87 @@
+//! Renaming of fields and variants for `#[neon(rename_all = "...")]`.
+
+/// A naming convention that Rust identifiers can be converted to.
+#[derive(Clone, Copy)]
+pub enum Case {
+    Lower,
+    Upper,
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+    Kebab
+}
+
+impl Case {
+    pub fn from_str(s: &str) -> Option<Case> {
+        Some(match s {
+            "lowercase" => Case::Lower,
+            "UPPERCASE" => Case::Upper,
+            "camelCase" => Case::Camel,
+            "PascalCase" => Case::Pascal,
+            "snake_case" => Case::Snake,
+            "SCREAMING_SNAKE_CASE" => Case::ScreamingSnake,
+            "kebab-case" => Case::Kebab,
+            _ => return None
+        })
+    }
+
+    /// Converts a `snake_case` field name or a `PascalCase` variant name.
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            Case::Lower => words.concat().to_lowercase(),
+            Case::Upper => words.concat().to_uppercase(),
+            Case::Camel => {
+                let mut result = String::new();
+                for (i, word) in words.iter().enumerate() {
+                    if i == 0 {
+                        result.push_str(&word.to_lowercase());
+                    } else {
+                        result.push_str(&capitalize(word));
+                    }
+                }
+                result
+            }
+            Case::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Case::Snake => words.join("_").to_lowercase(),
+            Case::ScreamingSnake => words.join("_").to_uppercase(),
+            Case::Kebab => words.join("-").to_lowercase()
+        }
+    }
+}
+
+/// Splits an identifier into words at underscores and at lowercase-to-uppercase transitions.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            words.push(word);
+            word = String::new();
+        }
+        prev_lower = c.is_lowercase() || c.is_numeric();
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect(),
+        None => String::new()
+    }
+}
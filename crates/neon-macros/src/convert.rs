@@ -0,0 +1,321 @@
+//! `#[derive(FromJs)]` and `#[derive(IntoJs)]`.
+//!
+//! Structs with named fields convert to and from plain objects, newtype structs convert like
+//! the type they wrap, and other tuple structs convert to and from arrays. Enums whose variants
+//! all have no fields convert to and from strings; any other enum, or an enum with a `tag`
+//! attribute, converts to and from objects whose `tag` property names the variant.
+
+use proc_macro2::{Span, TokenStream};
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, GenericParam, Generics, Ident, Result, Type};
+use syn::ext::IdentExt;
+
+use attr;
+use case::Case;
+
+const DEFAULT_TAG: &str = "type";
+const DEFAULT_CONTENT: &str = "value";
+
+pub fn derive_from_js(input: DeriveInput) -> Result<TokenStream> {
+    let container = attr::Container::parse(&input.attrs)?;
+    let name = &input.ident;
+    let body = match input.data {
+        Data::Struct(ref data) => from_fields(&quote!(#name), &data.fields, container.rename_all, name.span())?,
+        Data::Enum(ref data) => from_enum(name, data, &container)?,
+        Data::Union(_) => return Err(Error::new_spanned(&input, "FromJs cannot be derived for unions"))
+    };
+    let generics = add_bounds(&input.generics, quote!(::neon::convert::FromJs));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::neon::convert::FromJs for #name #ty_generics #where_clause {
+            fn from_js<'__neon_a, __NeonC: ::neon::context::Context<'__neon_a>>(
+                cx: &mut __NeonC,
+                value: ::neon::handle::Handle<'__neon_a, ::neon::types::JsValue>
+            ) -> ::neon::convert::ConversionResult<Self> {
+                #body
+            }
+        }
+    })
+}
+
+pub fn derive_into_js(input: DeriveInput) -> Result<TokenStream> {
+    let container = attr::Container::parse(&input.attrs)?;
+    let name = &input.ident;
+    let body = match input.data {
+        Data::Struct(ref data) => into_struct(name, &data.fields, container.rename_all)?,
+        Data::Enum(ref data) => into_enum(name, data, &container)?,
+        Data::Union(_) => return Err(Error::new_spanned(&input, "IntoJs cannot be derived for unions"))
+    };
+    let generics = add_bounds(&input.generics, quote!(::neon::convert::IntoJs));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::neon::convert::IntoJs for #name #ty_generics #where_clause {
+            fn into_js<'__neon_a, __NeonC: ::neon::context::Context<'__neon_a>>(
+                self,
+                cx: &mut __NeonC
+            ) -> ::neon::result::JsResult<'__neon_a, ::neon::types::JsValue> {
+                #body
+            }
+        }
+    })
+}
+
+/// Requires every type parameter to implement the derived trait.
+fn add_bounds(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<Ident> = generics.params.iter().filter_map(|param| match *param {
+        GenericParam::Type(ref ty) => Some(ty.ident.clone()),
+        _ => None
+    }).collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+/// A field of a struct or struct variant, along with its JavaScript property name.
+struct NamedField<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    key: String,
+    attrs: attr::Field
+}
+
+fn named_fields<'a>(fields: &'a Fields, rename_all: Option<Case>) -> Result<Vec<NamedField<'a>>> {
+    let mut result = Vec::new();
+    for field in fields.iter() {
+        let ident = field.ident.as_ref().expect("named field");
+        let attrs = attr::Field::parse(&field.attrs)?;
+        let name = ident.unraw().to_string();
+        let key = match (&attrs.rename, rename_all) {
+            (&Some(ref rename), _) => rename.clone(),
+            (&None, Some(case)) => case.apply(&name),
+            (&None, None) => name
+        };
+        result.push(NamedField { ident, ty: &field.ty, key, attrs });
+    }
+    Ok(result)
+}
+
+/// Whether a field's type is spelled `Option<...>`. Such fields are left out of the object
+/// when they are `None`.
+fn is_option(ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref path) => path.qself.is_none() && path.path.segments.last()
+            .map_or(false, |segment| segment.ident == "Option"),
+        _ => false
+    }
+}
+
+/// Builds the expression that converts `value` into the struct or struct variant at `path`.
+fn from_fields(path: &TokenStream, fields: &Fields, rename_all: Option<Case>, span: Span) -> Result<TokenStream> {
+    match *fields {
+        Fields::Named(_) => {
+            let fields = named_fields(fields, rename_all)?;
+            let inits = fields.iter().map(|field| {
+                let ident = field.ident;
+                let key = &field.key;
+                if field.attrs.default {
+                    quote!(#ident: ::neon::macro_internal::convert::get_or_default(cx, object, #key)?)
+                } else {
+                    quote!(#ident: ::neon::macro_internal::convert::get(cx, object, #key)?)
+                }
+            });
+            Ok(quote! {{
+                let object = ::neon::macro_internal::convert::object(value)?;
+                Ok(#path { #(#inits),* })
+            }})
+        }
+        Fields::Unnamed(ref unnamed) if unnamed.unnamed.len() == 1 => {
+            Ok(quote!(Ok(#path(::neon::convert::FromJs::from_js(cx, value)?))))
+        }
+        Fields::Unnamed(ref unnamed) => {
+            let tys = unnamed.unnamed.iter().map(|field| &field.ty);
+            let bindings = bindings(unnamed.unnamed.len());
+            Ok(quote! {{
+                let (#(#bindings,)*): (#(#tys,)*) = ::neon::convert::FromJs::from_js(cx, value)?;
+                Ok(#path(#(#bindings),*))
+            }})
+        }
+        Fields::Unit => Err(Error::new(span, "FromJs cannot be derived for unit structs"))
+    }
+}
+
+fn into_struct(name: &Ident, fields: &Fields, rename_all: Option<Case>) -> Result<TokenStream> {
+    match *fields {
+        Fields::Named(_) => {
+            let fields = named_fields(fields, rename_all)?;
+            let idents: Vec<&Ident> = fields.iter().map(|field| field.ident).collect();
+            let bindings = bindings(fields.len());
+            let sets = set_fields(&fields, &bindings);
+            Ok(quote! {
+                let #name { #(#idents: #bindings),* } = self;
+                let object = ::neon::types::JsObject::new(cx);
+                #(#sets)*
+                Ok(object.upcast())
+            })
+        }
+        Fields::Unnamed(ref unnamed) if unnamed.unnamed.len() == 1 => {
+            Ok(quote!(::neon::convert::IntoJs::into_js(self.0, cx)))
+        }
+        Fields::Unnamed(ref unnamed) => {
+            let bindings = bindings(unnamed.unnamed.len());
+            Ok(quote! {
+                let #name(#(#bindings),*) = self;
+                ::neon::convert::IntoJs::into_js((#(#bindings,)*), cx)
+            })
+        }
+        Fields::Unit => Err(Error::new(name.span(), "IntoJs cannot be derived for unit structs"))
+    }
+}
+
+/// Statements that set each named field, bound to `bindings`, as a property of `object`.
+fn set_fields(fields: &[NamedField], bindings: &[Ident]) -> Vec<TokenStream> {
+    fields.iter().zip(bindings).map(|(field, binding)| {
+        let key = &field.key;
+        if is_option(field.ty) {
+            quote!(::neon::macro_internal::convert::set_optional(cx, object, #key, #binding)?;)
+        } else {
+            quote!(::neon::macro_internal::convert::set(cx, object, #key, #binding)?;)
+        }
+    }).collect()
+}
+
+fn bindings(len: usize) -> Vec<Ident> {
+    (0..len).map(|i| Ident::new(&format!("__field{}", i), Span::call_site())).collect()
+}
+
+/// The JavaScript names of an enum's variants.
+fn variant_names(data: &DataEnum, container: &attr::Container) -> Result<Vec<(String, attr::Variant)>> {
+    data.variants.iter().map(|variant| {
+        let attrs = attr::Variant::parse(&variant.attrs)?;
+        let name = variant.ident.unraw().to_string();
+        let name = match (&attrs.rename, container.rename_all) {
+            (&Some(ref rename), _) => rename.clone(),
+            (&None, Some(case)) => case.apply(&name),
+            (&None, None) => name
+        };
+        Ok((name, attrs))
+    }).collect()
+}
+
+/// Whether an enum converts to and from strings rather than tagged objects.
+fn is_string_union(data: &DataEnum, container: &attr::Container) -> bool {
+    container.tag.is_none() && data.variants.iter().all(|variant| match variant.fields {
+        Fields::Unit => true,
+        _ => false
+    })
+}
+
+fn from_enum(name: &Ident, data: &DataEnum, container: &attr::Container) -> Result<TokenStream> {
+    let names = variant_names(data, container)?;
+    let expected: Vec<&String> = names.iter().map(|&(ref name, _)| name).collect();
+
+    if is_string_union(data, container) {
+        let arms = data.variants.iter().zip(&names).map(|(variant, &(ref key, _))| {
+            let ident = &variant.ident;
+            quote!(#key => Ok(#name::#ident))
+        });
+        return Ok(quote! {
+            let variant: ::std::string::String = ::neon::convert::FromJs::from_js(cx, value)?;
+            match variant.as_str() {
+                #(#arms,)*
+                _ => Err(::neon::macro_internal::convert::unknown_variant(&variant, &[#(#expected),*]))
+            }
+        });
+    }
+
+    let tag = container.tag.as_ref().map_or(DEFAULT_TAG, |tag| tag.as_str());
+    let content = container.content.as_ref().map_or(DEFAULT_CONTENT, |content| content.as_str());
+    let mut arms = Vec::new();
+    for (variant, &(ref key, ref attrs)) in data.variants.iter().zip(&names) {
+        let ident = &variant.ident;
+        let path = quote!(#name::#ident);
+        let arm = match variant.fields {
+            Fields::Named(_) => from_fields(&path, &variant.fields, attrs.rename_all, ident.span())?,
+            Fields::Unnamed(ref unnamed) if unnamed.unnamed.len() == 1 => {
+                quote!(Ok(#path(::neon::macro_internal::convert::get(cx, object, #content)?)))
+            }
+            Fields::Unnamed(ref unnamed) => {
+                let tys = unnamed.unnamed.iter().map(|field| &field.ty);
+                let bindings = bindings(unnamed.unnamed.len());
+                quote! {{
+                    let (#(#bindings,)*): (#(#tys,)*) = ::neon::macro_internal::convert::get(cx, object, #content)?;
+                    Ok(#path(#(#bindings),*))
+                }}
+            }
+            Fields::Unit => quote!(Ok(#path))
+        };
+        arms.push(quote!(#key => #arm));
+    }
+    Ok(quote! {
+        let object = ::neon::macro_internal::convert::object(value)?;
+        let variant: ::std::string::String = ::neon::macro_internal::convert::get(cx, object, #tag)?;
+        match variant.as_str() {
+            #(#arms,)*
+            _ => Err(::neon::macro_internal::convert::unknown_variant(&variant, &[#(#expected),*])
+                .context(format!("property {:?}", #tag)))
+        }
+    })
+}
+
+fn into_enum(name: &Ident, data: &DataEnum, container: &attr::Container) -> Result<TokenStream> {
+    let names = variant_names(data, container)?;
+
+    if is_string_union(data, container) {
+        let arms = data.variants.iter().zip(&names).map(|(variant, &(ref key, _))| {
+            let ident = &variant.ident;
+            quote!(#name::#ident => #key)
+        });
+        return Ok(quote! {
+            let variant: &'static str = match self {
+                #(#arms,)*
+            };
+            ::neon::convert::IntoJs::into_js(variant, cx)
+        });
+    }
+
+    let tag = container.tag.as_ref().map_or(DEFAULT_TAG, |tag| tag.as_str());
+    let content = container.content.as_ref().map_or(DEFAULT_CONTENT, |content| content.as_str());
+    let mut arms = Vec::new();
+    for (variant, &(ref key, ref attrs)) in data.variants.iter().zip(&names) {
+        let ident = &variant.ident;
+        let set_tag = quote!(::neon::macro_internal::convert::set(cx, object, #tag, #key)?;);
+        let arm = match variant.fields {
+            Fields::Named(_) => {
+                let fields = named_fields(&variant.fields, attrs.rename_all)?;
+                let idents: Vec<&Ident> = fields.iter().map(|field| field.ident).collect();
+                let bindings = bindings(fields.len());
+                let sets = set_fields(&fields, &bindings);
+                quote!(#name::#ident { #(#idents: #bindings),* } => { #set_tag #(#sets)* })
+            }
+            Fields::Unnamed(ref unnamed) => {
+                let bindings = bindings(unnamed.unnamed.len());
+                let value = if bindings.len() == 1 {
+                    let binding = &bindings[0];
+                    quote!(#binding)
+                } else {
+                    quote!((#(#bindings,)*))
+                };
+                let bindings = &bindings;
+                quote! {
+                    #name::#ident(#(#bindings),*) => {
+                        #set_tag
+                        ::neon::macro_internal::convert::set(cx, object, #content, #value)?;
+                    }
+                }
+            }
+            Fields::Unit => quote!(#name::#ident => { #set_tag })
+        };
+        arms.push(arm);
+    }
+    Ok(quote! {
+        let object = ::neon::types::JsObject::new(cx);
+        match self {
+            #(#arms)*
+        }
+        Ok(object.upcast())
+    })
+}
@@ -0,0 +1,196 @@
+//! `#[neon::main]` and `#[neon::export]`.
+
+use proc_macro2::{Span, TokenStream};
+use syn::{AttributeArgs, Error, FnArg, GenericParam, Ident, ItemFn, Lit, Meta, NestedMeta, Result, ReturnType, Type};
+use syn::spanned::Spanned;
+
+/// Registers the function as the module's initialization logic, like `register_module!`.
+pub fn main(args: AttributeArgs, item: ItemFn) -> Result<TokenStream> {
+    if let Some(arg) = args.first() {
+        return Err(Error::new(arg.span(), "neon::main does not take any arguments"));
+    }
+    check_signature(&item, "neon::main")?;
+    let ident = &item.sig.ident;
+
+    Ok(quote! {
+        #item
+
+        ::neon::register_module!(cx, { #ident(cx) });
+    })
+}
+
+/// Registers the function to be exported from the module when it is loaded.
+pub fn export(args: AttributeArgs, item: ItemFn) -> Result<TokenStream> {
+    check_signature(&item, "neon::export")?;
+    let ident = &item.sig.ident;
+    let name = match export_name(&args)? {
+        Some(name) => name,
+        None => ident.to_string().trim_start_matches("r#").to_string()
+    };
+    let register = if takes_function_context(&item) {
+        // The function already has the signature of a Neon function.
+        if returns(&item, &["JsResult"]) {
+            quote!(cx.export_function(#name, #ident))
+        } else {
+            quote!(cx.export_typed_function(#name, #ident))
+        }
+    } else {
+        let wrapper = wrapper(&item)?;
+        quote! {
+            #wrapper
+            cx.export_function(#name, __neon_wrapper)
+        }
+    };
+    let upper = ident.to_string().trim_start_matches("r#").to_uppercase();
+    let registration = Ident::new(&format!("__NEON_EXPORT_{}", upper), Span::call_site());
+    let constructor = Ident::new(&format!("__NEON_REGISTER_{}", upper), Span::call_site());
+
+    Ok(quote! {
+        #item
+
+        #[doc(hidden)]
+        #[::neon::macro_internal::linkme::distributed_slice(::neon::macro_internal::EXPORTS)]
+        #[linkme(crate = ::neon::macro_internal::linkme)]
+        static #registration: ::neon::macro_internal::Export = {
+            fn __neon_export(cx: &mut ::neon::context::ModuleContext) -> ::neon::result::NeonResult<()> {
+                #register
+            }
+            __neon_export
+        };
+
+        // A global constructor that registers the module, in case the crate doesn't have a
+        // `#[neon::main]` or `register_module!` to do it. During tests, Node isn't available.
+        #[doc(hidden)]
+        #[cfg(not(test))]
+        #[cfg_attr(target_os = "linux", link_section = ".ctors")]
+        #[cfg_attr(target_os = "macos", link_section = "__DATA,__mod_init_func")]
+        #[cfg_attr(target_os = "windows", link_section = ".CRT$XCU")]
+        #[used]
+        static #constructor: extern "C" fn() = {
+            extern "C" fn __neon_register() {
+                ::neon::macro_internal::register_default_module();
+            }
+            __neon_register
+        };
+    })
+}
+
+fn check_signature(item: &ItemFn, attr: &str) -> Result<()> {
+    let sig = &item.sig;
+    if let Some(ref asyncness) = sig.asyncness {
+        return Err(Error::new(asyncness.span(), format!("{} cannot be used on async functions", attr)));
+    }
+    for param in &sig.generics.params {
+        if let GenericParam::Lifetime(_) = *param {
+            continue;
+        }
+        return Err(Error::new(param.span(), format!("{} cannot be used on generic functions", attr)));
+    }
+    if let Some(ref variadic) = sig.variadic {
+        return Err(Error::new(variadic.span(), format!("{} cannot be used on variadic functions", attr)));
+    }
+    if let Some(FnArg::Receiver(ref receiver)) = sig.inputs.first() {
+        return Err(Error::new(receiver.span(), format!("{} cannot be used on methods", attr)));
+    }
+    Ok(())
+}
+
+/// Parses `name = "..."`, which overrides the name of an export.
+fn export_name(args: &AttributeArgs) -> Result<Option<String>> {
+    let mut name = None;
+    for arg in args {
+        match *arg {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("name") => match nv.lit {
+                Lit::Str(ref s) => name = Some(s.value()),
+                ref lit => return Err(Error::new(lit.span(), "expected a string"))
+            },
+            _ => return Err(Error::new(arg.span(), "expected `name = \"...\"`"))
+        }
+    }
+    Ok(name)
+}
+
+/// Whether the last path segment of a type is `name`, such as `FunctionContext` in
+/// `neon::context::FunctionContext<'a>`.
+fn is_named(ty: &Type, name: &str) -> bool {
+    match *ty {
+        Type::Path(ref path) => path.path.segments.last().map_or(false, |segment| segment.ident == name),
+        Type::Paren(ref paren) => is_named(&paren.elem, name),
+        Type::Group(ref group) => is_named(&group.elem, name),
+        _ => false
+    }
+}
+
+fn returns(item: &ItemFn, names: &[&str]) -> bool {
+    match item.sig.output {
+        ReturnType::Default => false,
+        ReturnType::Type(_, ref ty) => names.iter().any(|name| is_named(ty, name))
+    }
+}
+
+/// Whether the function takes exactly one argument, a `FunctionContext`.
+fn takes_function_context(item: &ItemFn) -> bool {
+    match (item.sig.inputs.len(), item.sig.inputs.first()) {
+        (1, Some(&FnArg::Typed(ref arg))) => is_named(&arg.ty, "FunctionContext"),
+        _ => false
+    }
+}
+
+/// Generates a Neon function that converts the JavaScript arguments with `FromJs`, calls the
+/// exported function, and converts its result with `IntoJs`. The exported function may take
+/// a `&mut FunctionContext` as its first argument. A `NeonResult` or `JsResult` return value
+/// propagates exceptions, and the error of any other `Result` is thrown as an `Error`.
+fn wrapper(item: &ItemFn) -> Result<TokenStream> {
+    let ident = &item.sig.ident;
+    let mut inputs = item.sig.inputs.iter().peekable();
+    let mut call_args = Vec::new();
+
+    if let Some(&&FnArg::Typed(ref arg)) = inputs.peek() {
+        if let Type::Reference(ref reference) = *arg.ty {
+            if is_named(&reference.elem, "FunctionContext") {
+                if reference.mutability.is_none() {
+                    return Err(Error::new(arg.ty.span(), "expected `&mut FunctionContext`"));
+                }
+                call_args.push(quote!(&mut cx));
+                inputs.next();
+            }
+        }
+    }
+
+    let mut tys = Vec::new();
+    let mut bindings = Vec::new();
+    for (i, input) in inputs.enumerate() {
+        let arg = match *input {
+            FnArg::Typed(ref arg) => arg,
+            FnArg::Receiver(ref receiver) => return Err(Error::new(receiver.span(), "unexpected `self`"))
+        };
+        let binding = Ident::new(&format!("__arg{}", i), Span::call_site());
+        tys.push(&arg.ty);
+        call_args.push(quote!(#binding));
+        bindings.push(binding);
+    }
+    if tys.len() > 8 {
+        return Err(Error::new(item.sig.inputs.span(), "neon::export supports at most 8 arguments"));
+    }
+
+    let result = if returns(item, &["NeonResult", "JsResult"]) {
+        quote!(#ident(#(#call_args),*)?)
+    } else if returns(item, &["Result"]) {
+        quote! {
+            match #ident(#(#call_args),*) {
+                Ok(result) => result,
+                Err(err) => return ::neon::context::Context::throw_error(&mut cx, err.to_string())
+            }
+        }
+    } else {
+        quote!(#ident(#(#call_args),*))
+    };
+
+    Ok(quote! {
+        fn __neon_wrapper(mut cx: ::neon::context::FunctionContext) -> ::neon::result::JsResult<::neon::types::JsValue> {
+            let (#(#bindings,)*) = cx.args::<(#(#tys,)*)>()?;
+            let result = #result;
+            ::neon::convert::IntoJs::into_js(result, &mut cx)
+        }
+    })
+}
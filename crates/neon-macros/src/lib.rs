@@ -0,0 +1,92 @@
+//! Procedural macros supporting [Neon](https://www.neon-bindings.com/). These are re-exported
+//! by the `neon` crate and should be used through it.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+mod attr;
+mod case;
+mod convert;
+mod export;
+
+use proc_macro::TokenStream;
+use syn::{AttributeArgs, DeriveInput, ItemFn};
+
+/// Marks a function as the module's initialization logic, as an alternative to
+/// `register_module!`. The function takes a `ModuleContext` and returns a `NeonResult<()>`,
+/// and runs after the functions marked with `#[neon::export]` have been exported.
+///
+/// ```rust,ignore
+/// #[neon::main]
+/// fn main(mut cx: ModuleContext) -> NeonResult<()> {
+///     let version = cx.string("1.0.0");
+///     cx.export_value("version", version)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn main(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let item = parse_macro_input!(item as ItemFn);
+    export::main(args, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Exports a function from the module. Exported functions are collected at link time and
+/// added to the module's exports when it loads, before the `#[neon::main]` function or the
+/// `register_module!` block runs. A crate that has neither is registered as a module that
+/// only exports these functions.
+///
+/// A function that takes a `FunctionContext` is exported as it is. Any other function has
+/// its arguments converted with `FromJs` and its return value converted with `IntoJs`. It
+/// may take a `&mut FunctionContext` as its first argument, and it may return a
+/// `NeonResult`, or a `Result` whose error is thrown as an `Error`.
+///
+/// The export has the name of the function, unless it is overridden with `name`:
+///
+/// ```rust,ignore
+/// #[neon::export]
+/// fn add(a: f64, b: f64) -> f64 {
+///     a + b
+/// }
+///
+/// #[neon::export(name = "parseConfig")]
+/// fn parse_config(source: String) -> Result<Vec<String>, String> {
+///     if source.is_empty() {
+///         return Err("empty configuration".to_string());
+///     }
+///     Ok(source.lines().map(String::from).collect())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn export(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as AttributeArgs);
+    let item = parse_macro_input!(item as ItemFn);
+    export::export(args, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `neon::convert::FromJs`. See the `neon::convert` module for the supported
+/// attributes.
+#[proc_macro_derive(FromJs, attributes(neon))]
+pub fn derive_from_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    convert::derive_from_js(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `neon::convert::IntoJs`. See the `neon::convert` module for the supported
+/// attributes.
+#[proc_macro_derive(IntoJs, attributes(neon))]
+pub fn derive_into_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    convert::derive_into_js(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -6,3 +6,9 @@ pub use neon_sys::Neon_ArrayBuffer_New as new;
 
 /// Mutates the `base_out` and `size_out` arguments to access the data of a `v8::ArrayBuffer` object.
 pub use neon_sys::Neon_ArrayBuffer_Data as data;
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::ArrayBuffer` object
+/// backed by `length` bytes of external memory at `base`. Once the buffer is garbage collected,
+/// `finalize` is called with `data`, possibly on another thread. Returns `false` if the value
+/// couldn't be created.
+pub use neon_sys::Neon_ArrayBuffer_External as new_external;
@@ -0,0 +1,26 @@
+//! Facilities for working with `v8::BigInt`s.
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::BigInt` with the value
+/// of an `i64`. Returns `false` if the value couldn't be created.
+pub use neon_sys::Neon_BigInt_NewI64 as new_i64;
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::BigInt` with the value
+/// of a `u64`. Returns `false` if the value couldn't be created.
+pub use neon_sys::Neon_BigInt_NewU64 as new_u64;
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::BigInt` with the sign
+/// and the little-endian 64-bit words of its magnitude. Returns `false` if the value couldn't
+/// be created.
+pub use neon_sys::Neon_BigInt_NewWords as new_words;
+
+/// Converts a `v8::BigInt` to an `i64` in `out`, returning `false` if the conversion truncated
+/// the value.
+pub use neon_sys::Neon_BigInt_ToI64 as to_i64;
+
+/// Converts a `v8::BigInt` to a `u64` in `out`, returning `false` if the conversion truncated
+/// the value.
+pub use neon_sys::Neon_BigInt_ToU64 as to_u64;
+
+/// Copies the sign and up to `capacity` little-endian 64-bit words of the magnitude of a
+/// `v8::BigInt`, returning the number of words the whole magnitude takes.
+pub use neon_sys::Neon_BigInt_ToWords as to_words;
@@ -10,3 +10,9 @@ pub use neon_sys::Neon_Buffer_Uninitialized as uninitialized;
 
 /// Mutates the `base_out` and `size_out` arguments to access the data of a `node::Buffer` object.
 pub use neon_sys::Neon_Buffer_Data as data;
+
+/// Mutates the `out` argument provided to refer to a newly created `node::Buffer` object backed
+/// by `length` bytes of external memory at `base`. Once the buffer is garbage collected,
+/// `finalize` is called with `data`, possibly on another thread. Returns `false` if the value
+/// couldn't be created.
+pub use neon_sys::Neon_Buffer_External as new_external;
@@ -0,0 +1,11 @@
+//! Facilities for working with `v8::DataView`s.
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::DataView` of
+/// `byte_length` bytes of a `v8::ArrayBuffer` starting at `byte_offset`. The caller is
+/// responsible for checking that the view fits in the buffer. Returns `false` if the value
+/// couldn't be created.
+pub use neon_sys::Neon_DataView_New as new;
+
+/// Gets the underlying `v8::ArrayBuffer`, the byte offset and the length in bytes of a
+/// `v8::DataView`.
+pub use neon_sys::Neon_DataView_Info as info;
@@ -0,0 +1,8 @@
+//! Facilities for working with `v8::Date`s.
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::Date` with the given
+/// number of milliseconds since the epoch. Returns `false` if the value couldn't be created.
+pub use neon_sys::Neon_Date_New as new;
+
+/// Gets the number of milliseconds since the epoch of a `v8::Date`.
+pub use neon_sys::Neon_Date_Value as value;
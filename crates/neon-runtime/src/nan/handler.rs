@@ -0,0 +1,19 @@
+//! Facilities for calling a JavaScript function from any thread.
+
+/// Creates a handler that calls `callback` with `this` as its receiver.
+pub use neon_sys::Neon_EventHandler_New as new;
+
+/// Queues a Rust callback to be run on the JavaScript thread of the handler. This can be
+/// called from any thread.
+pub use neon_sys::Neon_EventHandler_Schedule as schedule;
+
+/// Keeps the event loop alive while the handler is alive, which is the default. This can only
+/// be called on the JavaScript thread of the handler.
+pub use neon_sys::Neon_EventHandler_Ref as reference;
+
+/// Lets the event loop exit while the handler is alive. This can only be called on the
+/// JavaScript thread of the handler.
+pub use neon_sys::Neon_EventHandler_Unref as unreference;
+
+/// Releases a handler. It is freed once the callbacks that are already queued have run.
+pub use neon_sys::Neon_EventHandler_Delete as delete;
@@ -1,4 +1,23 @@
-//! A helper function for comparing `v8::Local` handles.
+//! Helper functions for working with `v8::Local` and `v8::Persistent` handles.
 
 /// Indicates if two `v8::Local` handles are the same.
 pub use neon_sys::Neon_Mem_SameHandle as same_handle;
+
+/// Creates a persistent handle, which keeps `value` alive until it is dropped.
+pub use neon_sys::Neon_Mem_NewPersistent as new_persistent;
+
+/// Produces a `v8::Local` handle to the value of a persistent handle.
+pub use neon_sys::Neon_Mem_GetPersistent as get_persistent;
+
+/// Drops a persistent handle, allowing its value to be garbage collected.
+pub use neon_sys::Neon_Mem_DropPersistent as drop_persistent;
+
+/// Creates a weak handle, which produces `value` for as long as it hasn't been garbage collected.
+pub use neon_sys::Neon_Mem_NewWeak as new_weak;
+
+/// Produces a `v8::Local` handle to the value of a weak handle, returning `false` if the value
+/// has been garbage collected. Weak handles are dropped with `drop_persistent`.
+pub use neon_sys::Neon_Mem_GetWeak as get_weak;
+
+/// Registers a callback that is passed `data` once the object has been garbage collected.
+pub use neon_sys::Neon_Mem_AddFinalizer as add_finalizer;
@@ -8,6 +8,8 @@ pub mod primitive;
 pub mod error;
 pub mod arraybuffer;
 pub mod buffer;
+pub mod typedarray;
+pub mod dataview;
 pub mod tag;
 pub mod module;
 pub mod mem;
@@ -15,3 +17,8 @@ pub mod fun;
 pub mod convert;
 pub mod class;
 pub mod task;
+pub mod promise;
+pub mod handler;
+pub mod bigint;
+pub mod date;
+pub mod symbol;
@@ -0,0 +1,18 @@
+//! Facilities for creating and settling promises.
+
+/// Creates a new promise, producing it in `out` and returning its pending resolution.
+pub use neon_sys::Neon_Promise_New as new;
+
+/// Resolves the promise of a `Deferred` with a value. The `Deferred` is consumed.
+pub use neon_sys::Neon_Promise_Resolve as resolve;
+
+/// Rejects the promise of a `Deferred` with a value. The `Deferred` is consumed.
+pub use neon_sys::Neon_Promise_Reject as reject;
+
+/// Calls `complete` to produce the value that resolves the promise of a `Deferred`, rejecting
+/// the promise instead if `complete` throws an exception. The `Deferred` is consumed.
+pub use neon_sys::Neon_Promise_Settle as settle;
+
+/// Gets the state of a promise: 0 if it is pending, 1 if it has been fulfilled and 2 if it
+/// has been rejected. Once the promise has settled, its result is produced in `out`.
+pub use neon_sys::Neon_Promise_State as state;
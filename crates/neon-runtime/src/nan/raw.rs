@@ -1,3 +1,12 @@
 //! Fundamental definitions for mapping to the V8 memory space.
 
-pub use neon_sys::{Local, FunctionCallbackInfo, Isolate, HandleScope, EscapableHandleScope, InheritedHandleScope};
+pub use neon_sys::{Local, FunctionCallbackInfo, Isolate, HandleScope, EscapableHandleScope, InheritedHandleScope, TypedArrayType};
+
+/// The environment that handles belong to, which for V8 is the isolate.
+pub type Env = *mut Isolate;
+
+/// The pending resolution of a promise, which for V8 is a persistent `Promise::Resolver`.
+pub type Deferred = *mut ::neon_sys::Deferred;
+
+/// A handle that keeps a value alive, which for V8 is a `Persistent`.
+pub type Persistent = *mut ::neon_sys::Persistent;
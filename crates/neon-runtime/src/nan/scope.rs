@@ -1,48 +1,48 @@
 //! Facilities for working with `v8::HandleScope`s and `v8::EscapableHandleScope`s.
 
-use raw::{HandleScope, EscapableHandleScope, InheritedHandleScope, Isolate};
+use raw::{Env, HandleScope, EscapableHandleScope, InheritedHandleScope};
 
 pub trait Root {
     unsafe fn allocate() -> Self;
-    unsafe fn enter(&mut self, *mut Isolate);
-    unsafe fn exit(&mut self);
+    unsafe fn enter(&mut self, Env);
+    unsafe fn exit(&mut self, Env);
 }
 
 impl Root for HandleScope {
     unsafe fn allocate() -> Self { HandleScope::new() }
-    unsafe fn enter(&mut self, isolate: *mut Isolate) {
-        enter(self, isolate)
+    unsafe fn enter(&mut self, env: Env) {
+        enter(self, env)
     }
-    unsafe fn exit(&mut self) {
-        exit(self)
+    unsafe fn exit(&mut self, env: Env) {
+        exit(self, env)
     }
 }
 
 impl Root for EscapableHandleScope {
     unsafe fn allocate() -> Self { EscapableHandleScope::new() }
-    unsafe fn enter(&mut self, isolate: *mut Isolate) {
-        enter_escapable(self, isolate)
+    unsafe fn enter(&mut self, env: Env) {
+        enter_escapable(self, env)
     }
-    unsafe fn exit(&mut self) {
-        exit_escapable(self)
+    unsafe fn exit(&mut self, env: Env) {
+        exit_escapable(self, env)
     }
 }
 
 impl Root for InheritedHandleScope {
     unsafe fn allocate() -> Self { InheritedHandleScope }
-    unsafe fn enter(&mut self, _: *mut Isolate) { }
-    unsafe fn exit(&mut self) { }
+    unsafe fn enter(&mut self, _: Env) { }
+    unsafe fn exit(&mut self, _: Env) { }
 }
 
 /// Mutates the `out` argument provided to refer to the newly escaped `v8::Local` value.
 pub use neon_sys::Neon_Scope_Escape as escape;
 
-/// Creates a `v8::EscapableHandleScope` and calls the `callback` provided with the argument
-/// signature `(out, parent_scope, &v8_scope, closure)`.
+/// Creates a `v8::EscapableHandleScope` in the isolate of the parent scope and calls the
+/// `callback` provided with the argument signature `(out, parent_scope, &v8_scope, closure)`.
 pub use neon_sys::Neon_Scope_Chained as chained;
 
-/// Creates a `v8::HandleScope` and calls the `callback` provided with the argument signature
-/// `(out, realm, closure)`.
+/// Creates a `v8::HandleScope` in the isolate of the parent scope and calls the `callback`
+/// provided with the argument signature `(out, realm, closure)`.
 pub use neon_sys::Neon_Scope_Nested as nested;
 
 /// Instantiates a new `v8::HandleScope`.
@@ -0,0 +1,9 @@
+//! Facilities for working with `v8::Symbol`s.
+
+/// Mutates the `out` argument provided to refer to a newly created `v8::Symbol`. The
+/// `description` is either a `v8::String` or an empty handle for a symbol with no description.
+pub use neon_sys::Neon_Symbol_New as new;
+
+/// Mutates the `out` argument provided to refer to the description of a `v8::Symbol`, which is
+/// `undefined` if the symbol has none.
+pub use neon_sys::Neon_Symbol_Description as description;
@@ -32,3 +32,21 @@ pub use neon_sys::Neon_Tag_IsBuffer as is_buffer;
 
 /// Indicates if the value type is `ArrayBuffer`.
 pub use neon_sys::Neon_Tag_IsArrayBuffer as is_arraybuffer;
+
+/// Indicates if the value type is `Promise`.
+pub use neon_sys::Neon_Tag_IsPromise as is_promise;
+
+/// Indicates if the value type is `BigInt`.
+pub use neon_sys::Neon_Tag_IsBigInt as is_bigint;
+
+/// Indicates if the value type is `Date`.
+pub use neon_sys::Neon_Tag_IsDate as is_date;
+
+/// Indicates if the value type is `Symbol`.
+pub use neon_sys::Neon_Tag_IsSymbol as is_symbol;
+
+/// Indicates if the value type is a typed array, such as `Uint8Array`.
+pub use neon_sys::Neon_Tag_IsTypedArray as is_typedarray;
+
+/// Indicates if the value type is `DataView`.
+pub use neon_sys::Neon_Tag_IsDataView as is_dataview;
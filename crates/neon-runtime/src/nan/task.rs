@@ -2,3 +2,6 @@
 
 /// Schedules a background task.
 pub use neon_sys::Neon_Task_Schedule as schedule;
+
+/// Schedules a background task whose result settles a promise.
+pub use neon_sys::Neon_Task_SchedulePromise as schedule_promise;
@@ -0,0 +1,11 @@
+//! Facilities for working with `v8::TypedArray`s.
+
+/// Mutates the `out` argument provided to refer to a newly created typed array of the given
+/// element type, viewing `length` elements of a `v8::ArrayBuffer` starting at `byte_offset`.
+/// The caller is responsible for checking that the view fits in the buffer. Returns `false` if
+/// the value couldn't be created.
+pub use neon_sys::Neon_TypedArray_New as new;
+
+/// Gets the element type, the underlying `v8::ArrayBuffer`, the byte offset and the length in
+/// elements of a `v8::TypedArray`.
+pub use neon_sys::Neon_TypedArray_Info as info;
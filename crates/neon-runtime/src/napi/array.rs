@@ -1,5 +1,19 @@
-use raw::{Local, Isolate};
+//! Facilities for working with array `napi_value`s.
 
-pub unsafe extern "C" fn new(_out: &mut Local, _isolate: *mut Isolate, _length: u32) { unimplemented!() }
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn len(_array: Local) -> u32 { unimplemented!() }
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created array.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, length: u32) {
+    let status = napi::napi_create_array_with_length(env, length as usize, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Gets the length of an array.
+pub unsafe extern "C" fn len(env: Env, array: Local) -> u32 {
+    let mut len = 0u32;
+    let status = napi::napi_get_array_length(env, array, &mut len as *mut u32);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    len
+}
@@ -1,6 +1,86 @@
-use raw::Local;
-use std::os::raw::c_void;
+//! Facilities for working with `ArrayBuffer`s.
 
-pub unsafe extern "C" fn new(_out: &mut Local, _isolate: *mut c_void, _size: u32) -> bool { unimplemented!() }
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn data<'a, 'b>(_base_out: &'a mut *mut c_void, _obj: Local) -> usize { unimplemented!() }
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created and zero-filled
+/// `ArrayBuffer` object. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, size: u32) -> bool {
+    let mut data: *mut c_void = ptr::null_mut();
+    let status = napi::napi_create_arraybuffer(env, size as usize, &mut data as *mut *mut c_void, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
+
+/// Mutates the `base_out` argument to point to the data of an `ArrayBuffer` object and returns
+/// its length in bytes.
+pub unsafe extern "C" fn data<'a, 'b>(env: Env, base_out: &'a mut *mut c_void, obj: Local) -> usize {
+    let mut size = 0usize;
+    let status = napi::napi_get_arraybuffer_info(env, obj, base_out as *mut *mut c_void, &mut size as *mut usize);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    size
+}
+
+/// The Rust value that owns the memory of an external `ArrayBuffer` or `Buffer`.
+pub(crate) struct External {
+    data: *mut c_void,
+    finalize: extern "C" fn(*mut c_void)
+}
+
+impl External {
+    fn new(data: *mut c_void, finalize: extern "C" fn(*mut c_void)) -> *mut External {
+        Box::into_raw(Box::new(External { data, finalize }))
+    }
+
+    unsafe extern "C" fn finalize(_env: Env, _base: *mut c_void, hint: *mut c_void) {
+        let external = Box::from_raw(hint as *mut External);
+        (external.finalize)(external.data);
+    }
+
+    /// Creates an external buffer with `create`, which is passed the finalizer of the buffer and
+    /// its hint. If the buffer can't be created, the failure is reported as an exception, and
+    /// the memory is released unless it's already been handed over to the finalizer.
+    pub(crate) unsafe fn create<F>(env: Env, data: *mut c_void, finalize: extern "C" fn(*mut c_void), create: F) -> bool
+        where F: FnOnce(napi::napi_finalize, *mut c_void) -> napi::napi_status
+    {
+        // N-API won't create anything while an exception is pending.
+        if External::is_exception_pending(env) {
+            finalize(data);
+            return false;
+        }
+
+        let external = External::new(data, finalize);
+        match create(Some(External::finalize), external as *mut c_void) {
+            napi::napi_status::napi_ok => return true,
+            // Arguments are checked before the finalizer is attached, so the memory is still
+            // ours to release. After that, Node may have called the finalizer itself, as
+            // `Buffer::New` does for a buffer that is too large, so the memory is left to it.
+            napi::napi_status::napi_invalid_arg => External::finalize(env, ptr::null_mut(), external as *mut c_void),
+            _ => {}
+        }
+
+        if !External::is_exception_pending(env) {
+            let message = b"failed to create an external buffer\0";
+            napi::napi_throw_error(env, ptr::null(), message.as_ptr() as *const c_char);
+        }
+
+        false
+    }
+
+    unsafe fn is_exception_pending(env: Env) -> bool {
+        let mut pending = false;
+        napi::napi_is_exception_pending(env, &mut pending as *mut bool);
+        pending
+    }
+}
+
+/// Mutates the `out` argument provided to refer to a newly created `ArrayBuffer` object backed
+/// by `length` bytes of external memory at `base`. Once the buffer is garbage collected,
+/// `finalize` is called with `data`. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new_external(out: &mut Local, env: Env, base: *mut c_void, length: usize, data: *mut c_void, finalize: extern "C" fn(*mut c_void)) -> bool {
+    External::create(env, data, finalize, |finalize_cb, hint| {
+        napi::napi_create_external_arraybuffer(env, base, length, finalize_cb, hint, out as *mut Local)
+    })
+}
@@ -0,0 +1,62 @@
+//! Facilities for working with BigInt `napi_value`s.
+
+use std::os::raw::c_int;
+use std::ptr::null_mut;
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created BigInt with the value of an
+/// `i64`. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new_i64(out: &mut Local, env: Env, value: i64) -> bool {
+    napi::napi_create_bigint_int64(env, value, out as *mut Local) == napi::napi_status::napi_ok
+}
+
+/// Mutates the `out` argument provided to refer to a newly created BigInt with the value of a
+/// `u64`. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new_u64(out: &mut Local, env: Env, value: u64) -> bool {
+    napi::napi_create_bigint_uint64(env, value, out as *mut Local) == napi::napi_status::napi_ok
+}
+
+/// Mutates the `out` argument provided to refer to a newly created BigInt with the sign and
+/// the little-endian 64-bit words of its magnitude. Returns `false` if the value couldn't be
+/// created.
+pub unsafe extern "C" fn new_words(out: &mut Local, env: Env, negative: bool, count: usize, words: *const u64) -> bool {
+    let sign_bit = if negative { 1 } else { 0 };
+    napi::napi_create_bigint_words(env, sign_bit, count, words, out as *mut Local) == napi::napi_status::napi_ok
+}
+
+/// Converts a BigInt to an `i64` in `out`, returning `false` if the conversion truncated the
+/// value.
+pub unsafe extern "C" fn to_i64(env: Env, out: &mut i64, bigint: Local) -> bool {
+    let mut lossless = false;
+    let status = napi::napi_get_value_bigint_int64(env, bigint, out as *mut i64, &mut lossless as *mut bool);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    lossless
+}
+
+/// Converts a BigInt to a `u64` in `out`, returning `false` if the conversion truncated the
+/// value.
+pub unsafe extern "C" fn to_u64(env: Env, out: &mut u64, bigint: Local) -> bool {
+    let mut lossless = false;
+    let status = napi::napi_get_value_bigint_uint64(env, bigint, out as *mut u64, &mut lossless as *mut bool);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    lossless
+}
+
+/// Copies the sign and up to `capacity` little-endian 64-bit words of the magnitude of a
+/// BigInt, returning the number of words the whole magnitude takes.
+pub unsafe extern "C" fn to_words(env: Env, negative: &mut bool, words: *mut u64, capacity: usize, bigint: Local) -> usize {
+    // N-API only reports the number of words that are needed when it isn't asked for them.
+    let mut needed = 0;
+    let status = napi::napi_get_value_bigint_words(env, bigint, null_mut(), &mut needed as *mut usize, null_mut());
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    let mut sign_bit: c_int = 0;
+    let mut count = capacity;
+    let status = napi::napi_get_value_bigint_words(env, bigint, &mut sign_bit as *mut c_int, &mut count as *mut usize, words);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    *negative = sign_bit != 0;
+    needed
+}
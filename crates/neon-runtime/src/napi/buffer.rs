@@ -1,8 +1,49 @@
-use raw::Local;
+//! Facilities for working with Node `Buffer`s.
+
 use std::os::raw::c_void;
+use std::ptr;
+use arraybuffer::External;
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created and zero-filled `Buffer`
+/// object. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, size: u32) -> bool {
+    let mut data: *mut c_void = ptr::null_mut();
+    if napi::napi_create_buffer(env, size as usize, &mut data as *mut *mut c_void, out as *mut Local) != napi::napi_status::napi_ok {
+        return false;
+    }
+
+    if size > 0 {
+        ptr::write_bytes(data as *mut u8, 0, size as usize);
+    }
+
+    true
+}
 
-pub unsafe extern "C" fn new(_out: &mut Local, _size: u32) -> bool { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created `Buffer` object, whose
+/// contents are left uninitialized. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn uninitialized(out: &mut Local, env: Env, size: u32) -> bool {
+    let mut data: *mut c_void = ptr::null_mut();
+    let status = napi::napi_create_buffer(env, size as usize, &mut data as *mut *mut c_void, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
 
-pub unsafe extern "C" fn uninitialized(_out: &mut Local, _size: u32) -> bool { unimplemented!() }
+/// Mutates the `base_out` argument to point to the data of a `Buffer` object and returns its
+/// length in bytes.
+pub unsafe extern "C" fn data<'a, 'b>(env: Env, base_out: &'a mut *mut c_void, obj: Local) -> usize {
+    let mut size = 0usize;
+    let status = napi::napi_get_buffer_info(env, obj, base_out as *mut *mut c_void, &mut size as *mut usize);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    size
+}
 
-pub unsafe extern "C" fn data<'a, 'b>(_base_out: &'a mut *mut c_void, _obj: Local) -> usize { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created `Buffer` object backed by
+/// `length` bytes of external memory at `base`. Once the buffer is garbage collected,
+/// `finalize` is called with `data`. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new_external(out: &mut Local, env: Env, base: *mut c_void, length: usize, data: *mut c_void, finalize: extern "C" fn(*mut c_void)) -> bool {
+    External::create(env, data, finalize, |finalize_cb, hint| {
+        napi::napi_create_external_buffer(env, length, base, finalize_cb, hint, out as *mut Local)
+    })
+}
@@ -1,6 +1,12 @@
+//! Facilities for working with `napi_callback_info` and getting the current `napi_env`.
+
+use std::cell::Cell;
+use std::mem;
 use std::os::raw::c_void;
 use std::ptr::null_mut;
-use raw::{FunctionCallbackInfo, Isolate, Local};
+use raw::{FunctionCallbackInfo, Env, Local};
+
+use nodejs_sys as napi;
 
 #[repr(C)]
 pub struct CCallback {
@@ -17,18 +23,101 @@ impl Default for CCallback {
     }
 }
 
-pub unsafe extern "C" fn set_return(_info: &FunctionCallbackInfo, _value: Local) { unimplemented!() }
+thread_local! {
+    // N-API hands a `napi_env` to every native entry point instead of exposing a
+    // global isolate. Each entry point records it here so that code without access
+    // to a context, such as the completion callback of a task, can still reach it.
+    static CURRENT_ENV: Cell<Env> = Cell::new(null_mut());
+}
+
+/// Records `env` as the environment of the current thread, returning the one it replaces.
+pub unsafe fn set_current_env(env: Env) -> Env {
+    CURRENT_ENV.with(|current| current.replace(env))
+}
+
+/// Gets the environment most recently recorded for the current thread.
+pub fn current_env() -> Env {
+    CURRENT_ENV.with(|current| current.get())
+}
+
+/// Invokes the static half of a `CCallback` for a function call received from N-API, returning
+/// its result along with the value it set as the return value of the call, if any.
+///
+/// The environment of the call is recorded as the current one for the duration of the
+/// callback, so that nested calls back into Rust can't clobber it.
+pub unsafe fn invoke<T>(env: Env, info: napi::napi_callback_info, callback: &CCallback) -> (T, Local) {
+    let info = FunctionCallbackInfo::new(env, info, callback.dynamic_callback);
+    let static_callback: extern "C" fn(&FunctionCallbackInfo) -> T = mem::transmute(callback.static_callback);
+    let previous = set_current_env(env);
+    let result = static_callback(&info);
+    set_current_env(previous);
+    (result, info.return_value.get())
+}
+
+/// Gets the number of arguments passed to the function call, and its receiver.
+unsafe fn arguments(info: &FunctionCallbackInfo, argv: &mut [Local]) -> (usize, Local) {
+    let mut argc = argv.len();
+    let mut this: Local = null_mut();
+    let status = napi::napi_get_cb_info(
+        info.env,
+        info.info,
+        &mut argc as *mut usize,
+        argv.as_mut_ptr(),
+        &mut this as *mut Local,
+        null_mut()
+    );
+    assert_eq!(status, napi::napi_status::napi_ok);
+    (argc, this)
+}
 
-pub unsafe extern "C" fn get_isolate(_info: &FunctionCallbackInfo) -> *mut Isolate { unimplemented!() }
+/// Sets the return value of the function call.
+pub unsafe extern "C" fn set_return(info: &FunctionCallbackInfo, value: Local) {
+    info.return_value.set(value);
+}
 
-pub unsafe extern "C" fn current_isolate() -> *mut Isolate { unimplemented!() }
+/// Gets the environment of the function call.
+pub unsafe extern "C" fn get_isolate(info: &FunctionCallbackInfo) -> Env {
+    info.env
+}
+
+/// Gets the current environment.
+pub unsafe extern "C" fn current_isolate() -> Env {
+    current_env()
+}
 
-pub unsafe extern "C" fn is_construct(_info: &FunctionCallbackInfo) -> bool { unimplemented!() }
+/// Indicates if the function call was invoked as a constructor.
+pub unsafe extern "C" fn is_construct(info: &FunctionCallbackInfo) -> bool {
+    let mut target: Local = null_mut();
+    let status = napi::napi_get_new_target(info.env, info.info, &mut target as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    !target.is_null()
+}
 
-pub unsafe extern "C" fn this(_info: &FunctionCallbackInfo, _out: &mut Local) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the `napi_value` of the object the
+/// function is bound to.
+pub unsafe extern "C" fn this(info: &FunctionCallbackInfo, out: &mut Local) {
+    let (_, this) = arguments(info, &mut []);
+    *out = this;
+}
 
-pub unsafe extern "C" fn data(_info: &FunctionCallbackInfo, _out: &mut Local) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the data of the function call.
+///
+/// N-API has no equivalent of a `v8::External`, so this is the raw dynamic callback pointer
+/// itself, which is only ever meant to be handed back to `fun::get_dynamic_callback`.
+pub unsafe extern "C" fn data(info: &FunctionCallbackInfo, out: &mut Local) {
+    *out = info.data as Local;
+}
 
-pub unsafe extern "C" fn len(_info: &FunctionCallbackInfo) -> i32 { unimplemented!() }
+/// Gets the number of arguments passed to the function.
+pub unsafe extern "C" fn len(info: &FunctionCallbackInfo) -> i32 {
+    let (argc, _) = arguments(info, &mut []);
+    argc as i32
+}
 
-pub unsafe extern "C" fn get(_info: &FunctionCallbackInfo, _i: i32, _out: &mut Local) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the `napi_value` of the `i`th argument
+/// passed to the function.
+pub unsafe extern "C" fn get(info: &FunctionCallbackInfo, i: i32, out: &mut Local) {
+    let mut argv: Vec<Local> = vec![null_mut(); (i + 1) as usize];
+    arguments(info, &mut argv);
+    *out = argv[i as usize];
+}
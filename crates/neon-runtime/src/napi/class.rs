@@ -1,39 +1,361 @@
-use std::os::raw::c_void;
-use call::CCallback;
-use raw::{Isolate, Local};
+//! Facilities for defining JS classes backed by Rust data.
 
-pub unsafe extern "C" fn get_class_map(_isolate: *mut Isolate) -> *mut c_void { unimplemented!() }
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::mem;
+use std::os::raw::{c_char, c_void};
+use std::ptr::{null, null_mut};
+use call::{self, CCallback};
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn set_class_map(_isolate: *mut Isolate, _map: *mut c_void, _free_map: *mut c_void) { unimplemented!() }
+use nodejs_sys as napi;
 
-pub unsafe extern "C" fn create_base(_isolate: *mut Isolate,
-                                     _allocate: CCallback,
-                                     _construct: CCallback,
-                                     _call: CCallback,
-                                     _drop: extern "C" fn(*mut c_void)) -> *mut c_void { unimplemented!() }
+/// The runtime's half of a class. It is owned by the environment that created the class and
+/// freed when that environment is torn down.
+struct ClassMetadata {
+    env: Env,
+    allocate: CCallback,
+    construct: CCallback,
+    call: CCallback,
+    drop_internals: extern "C" fn(*mut c_void),
+    name: Vec<u8>,
+    this_error: CString,
+    call_error: CString,
+    // N-API classes can't be extended once defined, so the class is only defined the first
+    // time its constructor is needed, and no methods can be added after that. Methods are held
+    // on to until the class has been defined successfully.
+    methods: Vec<(CString, napi::napi_ref)>,
+    constructor: napi::napi_ref,
+    // Objects wrapped by other addons can't be told apart from class instances by
+    // `napi_unwrap`, so every instance is added to a `WeakSet` when it's constructed and only
+    // members of the set are unwrapped. The set's `add` and `has` methods are held on to as
+    // well, so that patching `WeakSet.prototype` can't forge an instance.
+    instances: napi::napi_ref,
+    add_instance: napi::napi_ref,
+    has_instance: napi::napi_ref
+}
 
-pub unsafe extern "C" fn get_name<'a>(_base_out: &'a mut *mut u8, _isolate: *mut Isolate, _metadata: *const c_void) -> usize { unimplemented!() }
+/// The native object wrapped by every class instance.
+struct ClassInstance {
+    internals: *mut c_void,
+    drop_internals: extern "C" fn(*mut c_void)
+}
 
-pub unsafe extern "C" fn set_name(_isolate: *mut Isolate, _metadata: *mut c_void, _name: *const u8, _byte_length: u32) -> bool { unimplemented!() }
+thread_local! {
+    // Every environment gets its own class map, along with the function that frees it.
+    static CLASS_MAPS: RefCell<HashMap<Env, (*mut c_void, *mut c_void)>> = RefCell::new(HashMap::new());
+}
 
-pub unsafe extern "C" fn throw_call_error(_isolate: *mut Isolate, _metadata: *mut c_void) { unimplemented!() }
+unsafe extern "C" fn drop_class_map(env: *mut c_void) {
+    let entry = CLASS_MAPS.with(|maps| maps.borrow_mut().remove(&(env as Env)));
+    if let Some((map, free_map)) = entry {
+        let free_map: extern "C" fn(*mut c_void) = mem::transmute(free_map);
+        free_map(map);
+    }
+}
 
-pub unsafe extern "C" fn throw_this_error(_isolate: *mut Isolate, _metadata: *mut c_void) { unimplemented!() }
+unsafe extern "C" fn drop_metadata(metadata: *mut c_void) {
+    let metadata = Box::from_raw(metadata as *mut ClassMetadata);
+    let env = metadata.env;
+    for &(_, method) in &metadata.methods {
+        napi::napi_delete_reference(env, method);
+    }
+    for &r in &[metadata.constructor, metadata.instances, metadata.add_instance, metadata.has_instance] {
+        if !r.is_null() {
+            napi::napi_delete_reference(env, r);
+        }
+    }
+}
 
-pub unsafe extern "C" fn add_method(_isolate: *mut Isolate, _metadata: *mut c_void, _name: *const u8, _byte_length: u32, _method: Local) -> bool { unimplemented!() }
+unsafe extern "C" fn drop_instance(_env: Env, instance: *mut c_void, _hint: *mut c_void) {
+    let instance = Box::from_raw(instance as *mut ClassInstance);
+    (instance.drop_internals)(instance.internals);
+}
 
-pub unsafe extern "C" fn metadata_to_constructor(_out: &mut Local, _isolate: *mut Isolate, _metadata: *mut c_void) -> bool { unimplemented!() }
+/// The N-API callback of every class constructor. The class metadata is provided as its data.
+unsafe extern "C" fn construct_base(env: Env, info: napi::napi_callback_info) -> Local {
+    let mut this: Local = null_mut();
+    let mut data: *mut c_void = null_mut();
+    let status = napi::napi_get_cb_info(env, info, null_mut(), null_mut(), &mut this as *mut Local, &mut data as *mut *mut c_void);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    let metadata = &*(data as *const ClassMetadata);
+
+    let mut new_target: Local = null_mut();
+    let status = napi::napi_get_new_target(env, info, &mut new_target as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    if new_target.is_null() {
+        let ((), result) = call::invoke(env, info, &metadata.call);
+        return result;
+    }
+
+    let (internals, _): (*mut c_void, _) = call::invoke(env, info, &metadata.allocate);
+    if internals.is_null() {
+        return null_mut();
+    }
+
+    let instance = Box::into_raw(Box::new(ClassInstance {
+        internals,
+        drop_internals: metadata.drop_internals
+    })) as *mut c_void;
+
+    if napi::napi_wrap(env, this, instance, Some(drop_instance), null_mut(), null_mut()) != napi::napi_status::napi_ok {
+        drop_instance(env, instance, null_mut());
+        return null_mut();
+    }
+
+    if call_instances(env, metadata, metadata.add_instance, this).is_none() {
+        return null_mut();
+    }
+
+    if metadata.construct.static_callback.is_null() {
+        return this;
+    }
+
+    let (_, result): (bool, _) = call::invoke(env, info, &metadata.construct);
+    if result.is_null() { this } else { result }
+}
+
+/// Creates the `WeakSet` that brands the instances of a class, returning references to it and
+/// to its `add` and `has` methods.
+unsafe fn new_instances(env: Env) -> Option<(napi::napi_ref, napi::napi_ref, napi::napi_ref)> {
+    let mut global: Local = null_mut();
+    let mut weak_set: Local = null_mut();
+    let mut instances: Local = null_mut();
+    let mut add: Local = null_mut();
+    let mut has: Local = null_mut();
+    let ok = napi::napi_get_global(env, &mut global as *mut Local) == napi::napi_status::napi_ok
+          && napi::napi_get_named_property(env, global, b"WeakSet\0".as_ptr() as *const c_char, &mut weak_set as *mut Local) == napi::napi_status::napi_ok
+          && napi::napi_new_instance(env, weak_set, 0, null(), &mut instances as *mut Local) == napi::napi_status::napi_ok
+          && napi::napi_get_named_property(env, instances, b"add\0".as_ptr() as *const c_char, &mut add as *mut Local) == napi::napi_status::napi_ok
+          && napi::napi_get_named_property(env, instances, b"has\0".as_ptr() as *const c_char, &mut has as *mut Local) == napi::napi_status::napi_ok;
+    if !ok {
+        return None;
+    }
+
+    let mut refs: [napi::napi_ref; 3] = [null_mut(); 3];
+    for i in 0..refs.len() {
+        let value = [instances, add, has][i];
+        if napi::napi_create_reference(env, value, 1, &mut refs[i] as *mut napi::napi_ref) != napi::napi_status::napi_ok {
+            for &r in &refs[..i] {
+                napi::napi_delete_reference(env, r);
+            }
+            return None;
+        }
+    }
+    Some((refs[0], refs[1], refs[2]))
+}
+
+/// Calls `method`, one of the methods of the class's `WeakSet`, with `v` as its argument.
+unsafe fn call_instances(env: Env, metadata: &ClassMetadata, method: napi::napi_ref, v: Local) -> Option<Local> {
+    let mut instances: Local = null_mut();
+    let mut method_value: Local = null_mut();
+    let mut result: Local = null_mut();
+    let ok = napi::napi_get_reference_value(env, metadata.instances, &mut instances as *mut Local) == napi::napi_status::napi_ok
+          && napi::napi_get_reference_value(env, method, &mut method_value as *mut Local) == napi::napi_status::napi_ok
+          && napi::napi_call_function(env, instances, method_value, 1, &v as *const Local, &mut result as *mut Local) == napi::napi_status::napi_ok;
+    if ok { Some(result) } else { None }
+}
+
+/// Gets the constructor of a class, defining the class first if this is the first time.
+unsafe fn constructor_of(env: Env, metadata: &mut ClassMetadata) -> Option<Local> {
+    let mut constructor: Local = null_mut();
+
+    if !metadata.constructor.is_null() {
+        let status = napi::napi_get_reference_value(env, metadata.constructor, &mut constructor as *mut Local);
+        return if status == napi::napi_status::napi_ok { Some(constructor) } else { None };
+    }
+
+    let status = napi::napi_define_class(
+        env,
+        metadata.name.as_ptr() as *const c_char,
+        metadata.name.len(),
+        Some(construct_base),
+        metadata as *mut ClassMetadata as *mut c_void,
+        0,
+        null(),
+        &mut constructor as *mut Local
+    );
+    if status != napi::napi_status::napi_ok {
+        return None;
+    }
+
+    let mut prototype: Local = null_mut();
+    let status = napi::napi_get_named_property(env, constructor, b"prototype\0".as_ptr() as *const c_char, &mut prototype as *mut Local);
+    if status != napi::napi_status::napi_ok {
+        return None;
+    }
+
+    // The methods are only released once the class is complete, so a failed definition leaves
+    // them in place for the next attempt.
+    for &(ref name, method) in &metadata.methods {
+        let mut value: Local = null_mut();
+        let added = napi::napi_get_reference_value(env, method, &mut value as *mut Local) == napi::napi_status::napi_ok
+                 && napi::napi_set_named_property(env, prototype, name.as_ptr(), value) == napi::napi_status::napi_ok;
+        if !added {
+            return None;
+        }
+    }
+
+    let status = napi::napi_create_reference(env, constructor, 1, &mut metadata.constructor as *mut napi::napi_ref);
+    if status != napi::napi_status::napi_ok {
+        metadata.constructor = null_mut();
+        return None;
+    }
+
+    for (_, method) in metadata.methods.drain(..) {
+        napi::napi_delete_reference(env, method);
+    }
+
+    Some(constructor)
+}
+
+pub unsafe extern "C" fn get_class_map(env: Env) -> *mut c_void {
+    CLASS_MAPS.with(|maps| {
+        maps.borrow()
+            .get(&env)
+            .map(|&(map, _)| map)
+            .unwrap_or(null_mut())
+    })
+}
+
+pub unsafe extern "C" fn set_class_map(env: Env, map: *mut c_void, free_map: *mut c_void) {
+    CLASS_MAPS.with(|maps| maps.borrow_mut().insert(env, (map, free_map)));
+    let status = napi::napi_add_env_cleanup_hook(env, Some(drop_class_map), env as *mut c_void);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+pub unsafe extern "C" fn create_base(env: Env,
+                                     allocate: CCallback,
+                                     construct: CCallback,
+                                     call: CCallback,
+                                     drop: extern "C" fn(*mut c_void)) -> *mut c_void {
+    let (instances, add_instance, has_instance) = match new_instances(env) {
+        Some(refs) => refs,
+        None => return null_mut()
+    };
+
+    let metadata = Box::into_raw(Box::new(ClassMetadata {
+        env,
+        allocate,
+        construct,
+        call,
+        drop_internals: drop,
+        name: Vec::new(),
+        this_error: CString::default(),
+        call_error: CString::default(),
+        methods: Vec::new(),
+        constructor: null_mut(),
+        instances,
+        add_instance,
+        has_instance
+    })) as *mut c_void;
+
+    if napi::napi_add_env_cleanup_hook(env, Some(drop_metadata), metadata) != napi::napi_status::napi_ok {
+        drop_metadata(metadata);
+        return null_mut();
+    }
+
+    metadata
+}
+
+pub unsafe extern "C" fn get_name<'a>(base_out: &'a mut *mut u8, _env: Env, metadata: *const c_void) -> usize {
+    let metadata = &*(metadata as *const ClassMetadata);
+    *base_out = metadata.name.as_ptr() as *mut u8;
+    metadata.name.len()
+}
+
+pub unsafe extern "C" fn set_name(_env: Env, metadata: *mut c_void, name: *const u8, byte_length: u32) -> bool {
+    let metadata = &mut *(metadata as *mut ClassMetadata);
+    let name = std::slice::from_raw_parts(name, byte_length as usize);
+    let display_name = String::from_utf8_lossy(name);
+
+    match (CString::new(format!("this is not an object of type {}.", display_name)),
+           CString::new(format!("{} constructor called without new.", display_name))) {
+        (Ok(this_error), Ok(call_error)) => {
+            metadata.name = name.to_vec();
+            metadata.this_error = this_error;
+            metadata.call_error = call_error;
+            true
+        }
+        _ => false
+    }
+}
+
+pub unsafe extern "C" fn throw_call_error(env: Env, metadata: *mut c_void) {
+    let metadata = &*(metadata as *const ClassMetadata);
+    napi::napi_throw_type_error(env, null(), metadata.call_error.as_ptr());
+}
+
+pub unsafe extern "C" fn throw_this_error(env: Env, metadata: *mut c_void) {
+    let metadata = &*(metadata as *const ClassMetadata);
+    napi::napi_throw_type_error(env, null(), metadata.this_error.as_ptr());
+}
+
+pub unsafe extern "C" fn add_method(env: Env, metadata: *mut c_void, name: *const u8, byte_length: u32, method: Local) -> bool {
+    let metadata = &mut *(metadata as *mut ClassMetadata);
+    if !metadata.constructor.is_null() {
+        return false;
+    }
+
+    let name = match CString::new(std::slice::from_raw_parts(name, byte_length as usize)) {
+        Ok(name) => name,
+        Err(_) => return false
+    };
+
+    let mut method_ref: napi::napi_ref = null_mut();
+    if napi::napi_create_reference(env, method, 1, &mut method_ref as *mut napi::napi_ref) != napi::napi_status::napi_ok {
+        return false;
+    }
+
+    metadata.methods.push((name, method_ref));
+    true
+}
+
+pub unsafe extern "C" fn metadata_to_constructor(out: &mut Local, env: Env, metadata: *mut c_void) -> bool {
+    match constructor_of(env, &mut *(metadata as *mut ClassMetadata)) {
+        Some(constructor) => {
+            *out = constructor;
+            true
+        }
+        None => false
+    }
+}
 
 // FIXME: get rid of all the "kernel" nomenclature
 
-pub unsafe extern "C" fn get_allocate_kernel(_obj: Local) -> *mut c_void { unimplemented!() }
+// N-API has no `v8::External`, so the data of a class callback is the kernel itself.
+
+pub unsafe extern "C" fn get_allocate_kernel(obj: Local) -> *mut c_void {
+    obj as *mut c_void
+}
 
-pub unsafe extern "C" fn get_construct_kernel(_obj: Local) -> *mut c_void { unimplemented!() }
+pub unsafe extern "C" fn get_construct_kernel(obj: Local) -> *mut c_void {
+    obj as *mut c_void
+}
 
-pub unsafe extern "C" fn get_call_kernel(_obj: Local) -> *mut c_void { unimplemented!() }
+pub unsafe extern "C" fn get_call_kernel(obj: Local) -> *mut c_void {
+    obj as *mut c_void
+}
 
-pub unsafe extern "C" fn constructor(_out: &mut Local, _ft: Local) -> bool { unimplemented!() }
+pub unsafe extern "C" fn constructor(out: &mut Local, ft: Local) -> bool {
+    *out = ft;
+    true
+}
 
-pub unsafe extern "C" fn has_instance(_metadata: *mut c_void, _v: Local) -> bool { unimplemented!() }
+pub unsafe extern "C" fn has_instance(env: Env, metadata: *mut c_void, v: Local) -> bool {
+    let metadata = &*(metadata as *const ClassMetadata);
+    let mut result = false;
+    match call_instances(env, metadata, metadata.has_instance, v) {
+        Some(has) => napi::napi_get_value_bool(env, has, &mut result as *mut bool) == napi::napi_status::napi_ok && result,
+        None => false
+    }
+}
 
-pub unsafe extern "C" fn get_instance_internals(_obj: Local) -> *mut c_void { unimplemented!() }
+pub unsafe extern "C" fn get_instance_internals(env: Env, obj: Local) -> *mut c_void {
+    let mut instance: *mut c_void = null_mut();
+    let status = napi::napi_unwrap(env, obj, &mut instance as *mut *mut c_void);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    (*(instance as *const ClassInstance)).internals
+}
@@ -1,5 +1,19 @@
-use raw::Local;
+//! Helper functions for converting `napi_value`s.
 
-pub unsafe extern "C" fn to_object(_out: &mut Local, _value: &Local) -> bool { unimplemented!() }
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn to_string(_out: &mut Local, _value: Local) -> bool { unimplemented!() }
+use nodejs_sys as napi;
+
+/// Coerces the value provided to an object and mutates the `out` argument provided to refer to
+/// the converted value. Returns `false` if the conversion didn't succeed.
+pub unsafe extern "C" fn to_object(out: &mut Local, env: Env, value: &Local) -> bool {
+    let status = napi::napi_coerce_to_object(env, *value, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
+
+/// Coerces the value provided to a string and mutates the `out` argument provided to refer to
+/// the converted value. Returns `false` if the conversion didn't succeed.
+pub unsafe extern "C" fn to_string(out: &mut Local, env: Env, value: Local) -> bool {
+    let status = napi::napi_coerce_to_string(env, value, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
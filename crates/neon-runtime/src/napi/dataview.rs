@@ -0,0 +1,28 @@
+//! Facilities for working with `DataView`s.
+
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created `DataView` of
+/// `byte_length` bytes of an `ArrayBuffer` starting at `byte_offset`. Returns `false` if the
+/// value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, buffer: Local, byte_offset: usize, byte_length: usize) -> bool {
+    napi::napi_create_dataview(env, byte_length, buffer, byte_offset, out as *mut Local) == napi::napi_status::napi_ok
+}
+
+/// Gets the underlying `ArrayBuffer`, the byte offset and the length in bytes of a `DataView`.
+pub unsafe extern "C" fn info(env: Env, view: Local, buffer: &mut Local, byte_offset: &mut usize, byte_length: &mut usize) {
+    let mut data: *mut c_void = null_mut();
+    let status = napi::napi_get_dataview_info(
+        env,
+        view,
+        byte_length as *mut usize,
+        &mut data as *mut *mut c_void,
+        buffer as *mut Local,
+        byte_offset as *mut usize
+    );
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
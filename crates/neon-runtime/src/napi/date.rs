@@ -0,0 +1,19 @@
+//! Facilities for working with Date `napi_value`s.
+
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created Date with the given number
+/// of milliseconds since the epoch. Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, value: f64) -> bool {
+    napi::napi_create_date(env, value, out as *mut Local) == napi::napi_status::napi_ok
+}
+
+/// Gets the number of milliseconds since the epoch of a Date.
+pub unsafe extern "C" fn value(env: Env, date: Local) -> f64 {
+    let mut value = 0.0;
+    let status = napi::napi_get_date_value(env, date, &mut value as *mut f64);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    value
+}
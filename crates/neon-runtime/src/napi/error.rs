@@ -1,11 +1,60 @@
-use raw::Local;
+//! Facilities for creating and throwing JS errors.
 
-pub unsafe extern "C" fn throw(_val: Local) { unimplemented!() }
+use std::os::raw::c_char;
+use std::ptr::{null, null_mut};
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn new_error(_out: &mut Local, _msg: Local) { unimplemented!() }
+use nodejs_sys as napi;
 
-pub unsafe extern "C" fn new_type_error(_out: &mut Local, _msg: Local) { unimplemented!() }
+/// Throws an `Error` object in the current context.
+pub unsafe extern "C" fn throw(env: Env, val: Local) {
+    let status = napi::napi_throw(env, val);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn new_range_error(_out: &mut Local, _msg: Local) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created `Error` object.
+pub unsafe extern "C" fn new_error(out: &mut Local, env: Env, msg: Local) {
+    let status = napi::napi_create_error(env, null_mut(), msg, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn throw_error_from_utf8(_msg: *const u8, _len: i32) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created `TypeError` object.
+pub unsafe extern "C" fn new_type_error(out: &mut Local, env: Env, msg: Local) {
+    let status = napi::napi_create_type_error(env, null_mut(), msg, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Mutates the `out` argument provided to refer to a newly created `RangeError` object.
+pub unsafe extern "C" fn new_range_error(out: &mut Local, env: Env, msg: Local) {
+    let status = napi::napi_create_range_error(env, null_mut(), msg, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Creates an `Error` object with the UTF-8 message provided.
+unsafe fn error_from_utf8(env: Env, msg: *const u8, len: i32) -> Option<Local> {
+    let mut msg_val: Local = null_mut();
+    if napi::napi_create_string_utf8(env, msg as *const c_char, len as usize, &mut msg_val as *mut Local) != napi::napi_status::napi_ok {
+        return None;
+    }
+
+    let mut err: Local = null_mut();
+    if napi::napi_create_error(env, null_mut(), msg_val, &mut err as *mut Local) != napi::napi_status::napi_ok {
+        return None;
+    }
+
+    Some(err)
+}
+
+/// Throws an `Error` object in the current context, replacing any exception that is
+/// already pending.
+pub unsafe extern "C" fn throw_error_from_utf8(env: Env, msg: *const u8, len: i32) {
+
+    // Unlike V8, N-API refuses to throw while another exception is pending.
+    let mut pending: Local = null_mut();
+    napi::napi_get_and_clear_last_exception(env, &mut pending as *mut Local);
+
+    match error_from_utf8(env, msg, len) {
+        Some(err) => { napi::napi_throw(env, err); }
+        None => { napi::napi_throw_error(env, null(), b"an unknown Neon error occurred\0".as_ptr() as *const c_char); }
+    }
+}
@@ -1,13 +1,69 @@
+//! Facilities for working with JS functions.
+
 use std::os::raw::c_void;
-use call::CCallback;
-use raw::Local;
+use std::ptr::{null, null_mut};
+use call::{self, CCallback};
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+/// The N-API callback of every function created by Neon. The `CCallback` of the function is
+/// boxed as its data.
+unsafe extern "C" fn call_boxed(env: Env, info: napi::napi_callback_info) -> Local {
+    let mut data: *mut c_void = null_mut();
+    let status = napi::napi_get_cb_info(env, info, null_mut(), null_mut(), null_mut(), &mut data as *mut *mut c_void);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    let ((), result) = call::invoke(env, info, &*(data as *const CCallback));
+    result
+}
+
+/// Frees the boxed `CCallback` of a function once it has been garbage collected.
+unsafe extern "C" fn drop_boxed(_env: Env, data: *mut c_void, _hint: *mut c_void) {
+    drop(Box::from_raw(data as *mut CCallback));
+}
+
+/// Mutates the `out` argument provided to refer to a newly created JS function. Returns
+/// `false` if the value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, callback: CCallback) -> bool {
+    let data = Box::into_raw(Box::new(callback)) as *mut c_void;
+    let mut function: Local = null_mut();
+    let created = napi::napi_create_function(env, null(), 0, Some(call_boxed), data, &mut function as *mut Local) == napi::napi_status::napi_ok
+        // A finalizer rather than `napi_wrap`, so that functions can't be mistaken for wrapped
+        // class instances.
+        && napi::napi_add_finalizer(env, function, data, Some(drop_boxed), null_mut(), null_mut()) == napi::napi_status::napi_ok;
+
+    // Until the finalizer is attached nothing else owns the callback, and the function is never
+    // handed out, so it can't be called after the callback is freed.
+    if !created {
+        drop_boxed(env, data, null_mut());
+        return false;
+    }
 
-pub unsafe extern "C" fn new(_out: &mut Local, _isolate: *mut c_void, _callback: CCallback) -> bool { unimplemented!() }
+    *out = function;
+    true
+}
 
-pub unsafe extern "C" fn new_template(_out: &mut Local, _isolate: *mut c_void, _callback: CCallback) -> bool { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created JS function. N-API has no
+/// function templates, so this is the same as `new`.
+pub unsafe extern "C" fn new_template(out: &mut Local, env: Env, callback: CCallback) -> bool {
+    new(out, env, callback)
+}
 
-pub unsafe extern "C" fn get_dynamic_callback(_obj: Local) -> *mut c_void { unimplemented!() }
+/// Gets the dynamic callback pointer provided as the data of a function call.
+pub unsafe extern "C" fn get_dynamic_callback(obj: Local) -> *mut c_void {
+    obj as *mut c_void
+}
 
-pub unsafe extern "C" fn call(_out: &mut Local, _isolate: *mut c_void, _fun: Local, _this: Local, _argc: i32, _argv: *mut c_void) -> bool { unimplemented!() }
+/// Calls the function provided (`fun`) and mutates the `out` argument provided to refer to the
+/// result of the function call. Returns `false` if the call threw.
+pub unsafe extern "C" fn call(out: &mut Local, env: Env, fun: Local, this: Local, argc: i32, argv: *mut c_void) -> bool {
+    let status = napi::napi_call_function(env, this, fun, argc as usize, argv as *const Local, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
 
-pub unsafe extern "C" fn construct(_out: &mut Local, _isolate: *mut c_void, _fun: Local, _argc: i32, _argv: *mut c_void) -> bool { unimplemented!() }
+/// Makes a constructor call with the function provided (`fun`) and mutates the `out` argument
+/// provided to refer to the result of the constructor call. Returns `false` if the call threw.
+pub unsafe extern "C" fn construct(out: &mut Local, env: Env, fun: Local, argc: i32, argv: *mut c_void) -> bool {
+    let status = napi::napi_new_instance(env, fun, argc as usize, argv as *const Local, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
@@ -0,0 +1,117 @@
+//! Facilities for calling a JavaScript function from any thread.
+
+use std::os::raw::{c_char, c_void};
+use std::ptr::null_mut;
+use call::set_current_env;
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+type Complete = unsafe extern "C" fn(Env, Local, Local, *mut c_void);
+
+/// The state of a handler, owned by its thread-safe function.
+struct Handler {
+    this: napi::napi_ref
+}
+
+/// A Rust callback queued from another thread.
+struct Call {
+    rust_callback: *mut c_void,
+    complete: Complete
+}
+
+unsafe extern "C" fn drop_handler(env: Env, data: *mut c_void, _hint: *mut c_void) {
+    let handler = Box::from_raw(data as *mut Handler);
+    napi::napi_delete_reference(env, handler.this);
+}
+
+unsafe extern "C" fn call_js(env: Env, callback: Local, context: *mut c_void, data: *mut c_void) {
+    let call = Box::from_raw(data as *mut Call);
+
+    // Calls that are still queued when the environment is torn down are passed a null
+    // environment, and can only be dropped.
+    if env.is_null() {
+        (call.complete)(env, null_mut(), null_mut(), call.rust_callback);
+        return;
+    }
+
+    let handler = &*(context as *const Handler);
+    let mut this: Local = null_mut();
+    let status = napi::napi_get_reference_value(env, handler.this, &mut this as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    // N-API has already opened a handle scope and a callback scope for us.
+    let previous = set_current_env(env);
+    (call.complete)(env, this, callback, call.rust_callback);
+    set_current_env(previous);
+}
+
+/// Creates a handler that calls `callback` with `this` as its receiver.
+pub unsafe extern "C" fn new(env: Env, this: Local, callback: Local) -> *mut c_void {
+    let mut this_ref: napi::napi_ref = null_mut();
+    let status = napi::napi_create_reference(env, this, 1, &mut this_ref as *mut napi::napi_ref);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    let handler = Box::into_raw(Box::new(Handler { this: this_ref })) as *mut c_void;
+
+    let name = b"neon_event_handler";
+    let mut resource_name: Local = null_mut();
+    let status = napi::napi_create_string_utf8(env, name.as_ptr() as *const c_char, name.len(), &mut resource_name as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    let mut function: napi::napi_threadsafe_function = null_mut();
+    let status = napi::napi_create_threadsafe_function(
+        env,
+        callback,
+        null_mut(),
+        resource_name,
+        0,
+        1,
+        handler,
+        Some(drop_handler),
+        handler,
+        Some(call_js),
+        &mut function as *mut napi::napi_threadsafe_function
+    );
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    function as *mut c_void
+}
+
+/// Queues a Rust callback to be run on the JavaScript thread of the handler. This can be
+/// called from any thread.
+pub unsafe extern "C" fn schedule(handler: *mut c_void, rust_callback: *mut c_void, complete: Complete) -> bool {
+    let call = Box::into_raw(Box::new(Call { rust_callback, complete }));
+    let status = napi::napi_call_threadsafe_function(
+        handler as napi::napi_threadsafe_function,
+        call as *mut c_void,
+        napi::napi_threadsafe_function_call_mode::napi_tsfn_nonblocking
+    );
+
+    if status != napi::napi_status::napi_ok {
+        drop(Box::from_raw(call));
+        return false;
+    }
+
+    true
+}
+
+/// Keeps the event loop alive while the handler is alive, which is the default. This can only
+/// be called on the JavaScript thread of the handler.
+pub unsafe extern "C" fn reference(env: Env, handler: *mut c_void) {
+    napi::napi_ref_threadsafe_function(env, handler as napi::napi_threadsafe_function);
+}
+
+/// Lets the event loop exit while the handler is alive. This can only be called on the
+/// JavaScript thread of the handler.
+pub unsafe extern "C" fn unreference(env: Env, handler: *mut c_void) {
+    napi::napi_unref_threadsafe_function(env, handler as napi::napi_threadsafe_function);
+}
+
+/// Releases a handler. It is freed once the callbacks that are already queued have run.
+pub unsafe extern "C" fn delete(handler: *mut c_void) {
+    napi::napi_release_threadsafe_function(
+        handler as napi::napi_threadsafe_function,
+        napi::napi_threadsafe_function_release_mode::napi_tsfn_release
+    );
+}
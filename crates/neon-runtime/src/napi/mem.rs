@@ -1,3 +1,62 @@
-use raw::Local;
+//! Helper functions for comparing `napi_value`s and keeping them alive.
 
-pub unsafe extern "C" fn same_handle(_h1: Local, _h2: Local) -> bool { unimplemented!() }
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use raw::{Env, Local, Persistent};
+
+use nodejs_sys as napi;
+
+/// Indicates if two `napi_value`s refer to the same value.
+pub unsafe extern "C" fn same_handle(env: Env, h1: Local, h2: Local) -> bool {
+    let mut result = false;
+    let status = napi::napi_strict_equals(env, h1, h2, &mut result as *mut bool);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    result
+}
+
+/// Creates a persistent handle, which keeps `value` alive until it is dropped.
+pub unsafe extern "C" fn new_persistent(env: Env, value: Local) -> Persistent {
+    let mut persistent: Persistent = null_mut();
+    let status = napi::napi_create_reference(env, value, 1, &mut persistent as *mut Persistent);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    persistent
+}
+
+/// Produces a `napi_value` for the value of a persistent handle.
+pub unsafe extern "C" fn get_persistent(out: &mut Local, env: Env, persistent: Persistent) {
+    let status = napi::napi_get_reference_value(env, persistent, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Drops a persistent handle, allowing its value to be garbage collected.
+pub unsafe extern "C" fn drop_persistent(env: Env, persistent: Persistent) {
+    let status = napi::napi_delete_reference(env, persistent);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Creates a weak handle, which produces `value` for as long as it hasn't been garbage collected.
+pub unsafe extern "C" fn new_weak(env: Env, value: Local) -> Persistent {
+    let mut weak: Persistent = null_mut();
+    let status = napi::napi_create_reference(env, value, 0, &mut weak as *mut Persistent);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    weak
+}
+
+/// Produces a `napi_value` for the value of a weak handle, returning `false` if the value has
+/// been garbage collected. Weak handles are dropped with `drop_persistent`.
+pub unsafe extern "C" fn get_weak(out: &mut Local, env: Env, weak: Persistent) -> bool {
+    let status = napi::napi_get_reference_value(env, weak, out as *mut Local);
+    status == napi::napi_status::napi_ok && !out.is_null()
+}
+
+unsafe extern "C" fn call_finalizer(_env: Env, data: *mut c_void, hint: *mut c_void) {
+    let finalize: extern "C" fn(*mut c_void) = mem::transmute(hint);
+    finalize(data);
+}
+
+/// Registers a callback that is passed `data` once the object has been garbage collected.
+pub unsafe extern "C" fn add_finalizer(env: Env, obj: Local, data: *mut c_void, finalize: extern "C" fn(*mut c_void)) {
+    let status = napi::napi_add_finalizer(env, obj, data, Some(call_finalizer), finalize as *mut c_void, null_mut());
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
@@ -1,16 +1,23 @@
 pub mod array;
 pub mod arraybuffer;
+pub mod bigint;
 pub mod buffer;
 pub mod call;
 pub mod class;
 pub mod convert;
+pub mod dataview;
+pub mod date;
 pub mod error;
 pub mod fun;
+pub mod handler;
 pub mod mem;
 pub mod object;
 pub mod primitive;
+pub mod promise;
 pub mod raw;
 pub mod scope;
 pub mod string;
+pub mod symbol;
 pub mod tag;
 pub mod task;
+pub mod typedarray;
@@ -1,19 +1,93 @@
-use raw::{Isolate, Local};
+//! Facilities for working with object `napi_value`s.
 
-pub unsafe extern "C" fn new(_out: &mut Local) { unimplemented!() }
+use std::os::raw::c_char;
+use raw::{Env, Isolate, Local};
+use call::current_env;
 
-pub unsafe extern "C" fn get_own_property_names(_out: &mut Local, _object: Local) -> bool { unimplemented!() }
+use nodejs_sys as napi;
 
-pub unsafe extern "C" fn get_isolate(_obj: Local) -> *mut Isolate { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created object.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env) {
+    let status = napi::napi_create_object(env, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn get_index(_out: &mut Local, _object: Local, _index: u32) -> bool { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created array containing the names
+/// of the object's own enumerable string properties. Returns `false` if the names couldn't be
+/// retrieved.
+pub unsafe extern "C" fn get_own_property_names(out: &mut Local, env: Env, object: Local) -> bool {
+    let status = napi::napi_get_all_property_names(
+        env,
+        object,
+        napi::napi_key_collection_mode::napi_key_own_only,
+        napi::napi_key_filter::napi_key_enumerable | napi::napi_key_filter::napi_key_skip_symbols,
+        napi::napi_key_conversion::napi_key_numbers_to_strings,
+        out as *mut Local
+    );
 
-pub unsafe extern "C" fn set_index(_out: &mut bool, _object: Local, _index: u32, _val: Local) -> bool { unimplemented!() }
+    status == napi::napi_status::napi_ok
+}
 
-pub unsafe extern "C" fn get_string(_out: &mut Local, _object: Local, _key: *const u8, _len: i32) -> bool { unimplemented!() }
+/// Gets the environment that the object belongs to.
+pub unsafe extern "C" fn get_isolate(_obj: Local) -> *mut Isolate {
+    current_env() as *mut Isolate
+}
 
-pub unsafe extern "C" fn set_string(_out: &mut bool, _object: Local, _key: *const u8, _len: i32, _val: Local) -> bool { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the value at the `index` provided of the
+/// object. Returns `false` if the result couldn't be retrieved.
+pub unsafe extern "C" fn get_index(out: &mut Local, env: Env, object: Local, index: u32) -> bool {
+    let status = napi::napi_get_element(env, object, index, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
 
-pub unsafe extern "C" fn get(_out: &mut Local, _object: Local, _key: Local) -> bool { unimplemented!() }
+/// Sets the value of the object at the `index` provided. Also mutates the `out` argument
+/// provided to `true` if the set was successful.
+pub unsafe extern "C" fn set_index(out: &mut bool, env: Env, object: Local, index: u32, val: Local) -> bool {
+    let status = napi::napi_set_element(env, object, index, val);
+    *out = status == napi::napi_status::napi_ok;
+    *out
+}
 
-pub unsafe extern "C" fn set(_out: &mut bool, _object: Local, _key: Local, _val: Local) -> bool { unimplemented!() }
+/// Creates a string `napi_value` to be used as a property key.
+unsafe fn string_key(env: Env, key: *const u8, len: i32) -> Option<Local> {
+    let mut key_val: Local = std::mem::zeroed();
+    if napi::napi_create_string_utf8(env, key as *const c_char, len as usize, &mut key_val as *mut Local) == napi::napi_status::napi_ok {
+        Some(key_val)
+    } else {
+        None
+    }
+}
+
+/// Mutates the `out` argument provided to refer to the value of the property named by the
+/// UTF-8 string `key`. Returns `false` if the value couldn't be retrieved.
+pub unsafe extern "C" fn get_string(out: &mut Local, env: Env, object: Local, key: *const u8, len: i32) -> bool {
+    match string_key(env, key, len) {
+        Some(key) => napi::napi_get_property(env, object, key, out as *mut Local) == napi::napi_status::napi_ok,
+        None => false
+    }
+}
+
+/// Sets the value of the property named by the UTF-8 string `key`. Also mutates the `out`
+/// argument provided to `true` if the set was successful.
+pub unsafe extern "C" fn set_string(out: &mut bool, env: Env, object: Local, key: *const u8, len: i32, val: Local) -> bool {
+    *out = match string_key(env, key, len) {
+        Some(key) => napi::napi_set_property(env, object, key, val) == napi::napi_status::napi_ok,
+        None => false
+    };
+    *out
+}
+
+/// Mutates the `out` argument provided to refer to the value at the `key` provided. Returns
+/// `false` if the result couldn't be retrieved.
+pub unsafe extern "C" fn get(out: &mut Local, env: Env, object: Local, key: Local) -> bool {
+    let status = napi::napi_get_property(env, object, key, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
+
+/// Sets the value of the object at the `key` provided. Also mutates the `out` argument provided
+/// to `true` if the set was successful.
+pub unsafe extern "C" fn set(out: &mut bool, env: Env, object: Local, key: Local, val: Local) -> bool {
+    let status = napi::napi_set_property(env, object, key, val);
+    *out = status == napi::napi_status::napi_ok;
+    *out
+}
@@ -1,23 +1,89 @@
-use raw::{Local, Isolate};
+//! Facilities for working with primitive values.
 
-pub unsafe extern "C" fn undefined(_out: &mut Local) { unimplemented!() }
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn null(_out: &mut Local) { unimplemented!() }
+use nodejs_sys as napi;
 
-pub unsafe extern "C" fn boolean(_out: &mut Local, _b: bool) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the global `undefined` object.
+pub unsafe extern "C" fn undefined(out: &mut Local, env: Env) {
+    let status = napi::napi_get_undefined(env, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn boolean_value(_p: Local) -> bool { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the global `null` object.
+pub unsafe extern "C" fn null(out: &mut Local, env: Env) {
+    let status = napi::napi_get_null(env, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Mutates the `out` argument provided to refer to one of the global `true` or `false` objects.
+pub unsafe extern "C" fn boolean(out: &mut Local, env: Env, b: bool) {
+    let status = napi::napi_get_boolean(env, b, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Gets the underlying value of a boolean `napi_value`.
+pub unsafe extern "C" fn boolean_value(env: Env, p: Local) -> bool {
+    let mut value = false;
+    let status = napi::napi_get_value_bool(env, p, &mut value as *mut bool);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    value
+}
 
 // DEPRECATE(0.2)
-pub unsafe extern "C" fn integer(_out: &mut Local, _isolate: *mut Isolate, _x: i32) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created integer `napi_value`.
+pub unsafe extern "C" fn integer(out: &mut Local, env: Env, x: i32) {
+    let status = napi::napi_create_int32(env, x, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Gets the underlying value of a number `napi_value`, if the value is a number at all.
+unsafe fn number_value_opt(env: Env, p: Local) -> Option<f64> {
+    let mut value = 0.0;
+    if napi::napi_get_value_double(env, p, &mut value as *mut f64) == napi::napi_status::napi_ok {
+        Some(value)
+    } else {
+        None
+    }
+}
 
-pub unsafe extern "C" fn is_u32(_p: Local) -> bool { unimplemented!() }
+/// Indicates if the value is a 32-bit unsigned integer.
+pub unsafe extern "C" fn is_u32(env: Env, p: Local) -> bool {
+    match number_value_opt(env, p) {
+        // Like V8, treat `-0` as a double rather than an integer.
+        Some(v) => v == (v as u32) as f64 && !(v == 0.0 && v.is_sign_negative()),
+        None => false
+    }
+}
 
-pub unsafe extern "C" fn is_i32(_p: Local) -> bool { unimplemented!() }
+/// Indicates if the value is a 32-bit signed integer.
+pub unsafe extern "C" fn is_i32(env: Env, p: Local) -> bool {
+    match number_value_opt(env, p) {
+        // Like V8, treat `-0` as a double rather than an integer.
+        Some(v) => v == (v as i32) as f64 && !(v == 0.0 && v.is_sign_negative()),
+        None => false
+    }
+}
 
 // DEPRECATE(0.2)
-pub unsafe extern "C" fn integer_value(_p: Local) -> i64 { unimplemented!() }
+/// Gets the underlying value of an integer `napi_value`.
+pub unsafe extern "C" fn integer_value(env: Env, p: Local) -> i64 {
+    let mut value = 0;
+    let status = napi::napi_get_value_int64(env, p, &mut value as *mut i64);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    value
+}
 
-pub unsafe extern "C" fn number(_out: &mut Local, _isolate: *mut Isolate, _v: f64) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created number `napi_value`.
+pub unsafe extern "C" fn number(out: &mut Local, env: Env, v: f64) {
+    let status = napi::napi_create_double(env, v, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn number_value(_p: Local) -> f64 { unimplemented!() }
+/// Gets the underlying value of a number `napi_value`.
+pub unsafe extern "C" fn number_value(env: Env, p: Local) -> f64 {
+    let mut value = 0.0;
+    let status = napi::napi_get_value_double(env, p, &mut value as *mut f64);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    value
+}
@@ -0,0 +1,49 @@
+//! Facilities for creating and settling promises.
+
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use raw::{Deferred, Env, Local};
+
+use nodejs_sys as napi;
+
+/// Creates a new promise, producing it in `out` and returning its pending resolution.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env) -> Deferred {
+    let mut deferred: Deferred = null_mut();
+    let status = napi::napi_create_promise(env, &mut deferred as *mut Deferred, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    deferred
+}
+
+/// Resolves the promise of a `Deferred` with a value. The `Deferred` is consumed.
+pub unsafe extern "C" fn resolve(env: Env, deferred: Deferred, value: Local) -> bool {
+    napi::napi_resolve_deferred(env, deferred, value) == napi::napi_status::napi_ok
+}
+
+/// Rejects the promise of a `Deferred` with a value. The `Deferred` is consumed.
+pub unsafe extern "C" fn reject(env: Env, deferred: Deferred, value: Local) -> bool {
+    napi::napi_reject_deferred(env, deferred, value) == napi::napi_status::napi_ok
+}
+
+/// Calls `complete` to produce the value that resolves the promise of a `Deferred`, rejecting
+/// the promise instead if `complete` throws an exception. The `Deferred` is consumed.
+pub unsafe extern "C" fn settle(env: Env,
+                                deferred: Deferred,
+                                complete: unsafe extern fn(*mut c_void, &mut Local),
+                                data: *mut c_void) {
+    let mut value: Local = null_mut();
+    complete(data, &mut value);
+
+    let mut pending = false;
+    napi::napi_is_exception_pending(env, &mut pending as *mut bool);
+    if pending {
+        let mut exception: Local = null_mut();
+        napi::napi_get_and_clear_last_exception(env, &mut exception as *mut Local);
+        napi::napi_reject_deferred(env, deferred, exception);
+        return;
+    }
+
+    if value.is_null() {
+        napi::napi_get_undefined(env, &mut value as *mut Local);
+    }
+    napi::napi_resolve_deferred(env, deferred, value);
+}
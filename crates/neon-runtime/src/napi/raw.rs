@@ -1,16 +1,59 @@
+use std::cell::Cell;
 use std::os::raw::c_void;
 use std::ptr;
 
+use nodejs_sys as napi;
+
+pub type Local = napi::napi_value;
+
+/// Represents the details of how a function was called from JavaScript.
+///
+/// N-API callbacks produce their result by returning it rather than by setting it on
+/// the callback info, so the result is recorded here until the callback returns.
 #[repr(C)]
-#[derive(Clone, Copy)]
-pub struct Local {
-    pub handle: *mut c_void
+pub struct FunctionCallbackInfo {
+    pub env: Env,
+    pub info: napi::napi_callback_info,
+    pub data: *mut c_void,
+    pub return_value: Cell<Local>
 }
 
-pub type FunctionCallbackInfo = c_void;
+impl FunctionCallbackInfo {
+    pub fn new(env: Env, info: napi::napi_callback_info, data: *mut c_void) -> Self {
+        FunctionCallbackInfo {
+            env,
+            info,
+            data,
+            return_value: Cell::new(ptr::null_mut())
+        }
+    }
+}
 
 pub type Isolate = c_void;
 
+pub type Env = napi::napi_env;
+
+pub type Deferred = napi::napi_deferred;
+
+pub type Persistent = napi::napi_ref;
+
+/// The element type of a typed array, numbered like `napi_typedarray_type`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TypedArrayType {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64
+}
+
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct HandleScope {
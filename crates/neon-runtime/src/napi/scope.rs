@@ -1,58 +1,118 @@
+//! Facilities for working with N-API handle scopes.
+
+use std::mem;
 use std::os::raw::c_void;
-use raw::{HandleScope, EscapableHandleScope, InheritedHandleScope, Local, Isolate};
+use raw::{Env, HandleScope, EscapableHandleScope, InheritedHandleScope, Local};
+
+use nodejs_sys as napi;
 
 pub trait Root {
     unsafe fn allocate() -> Self;
-    unsafe fn enter(&mut self, *mut Isolate);
-    unsafe fn exit(&mut self);
+    unsafe fn enter(&mut self, Env);
+    unsafe fn exit(&mut self, Env);
 }
 
 impl Root for HandleScope {
     unsafe fn allocate() -> Self { HandleScope::new() }
-    unsafe fn enter(&mut self, isolate: *mut Isolate) {
-        enter(self, isolate)
+    unsafe fn enter(&mut self, env: Env) {
+        enter(self, env)
     }
-    unsafe fn exit(&mut self) {
-        exit(self)
+    unsafe fn exit(&mut self, env: Env) {
+        exit(self, env)
     }
 }
 
 impl Root for EscapableHandleScope {
     unsafe fn allocate() -> Self { EscapableHandleScope::new() }
-    unsafe fn enter(&mut self, isolate: *mut Isolate) {
-        enter_escapable(self, isolate)
+    unsafe fn enter(&mut self, env: Env) {
+        enter_escapable(self, env)
     }
-    unsafe fn exit(&mut self) {
-        exit_escapable(self)
+    unsafe fn exit(&mut self, env: Env) {
+        exit_escapable(self, env)
     }
 }
 
 impl Root for InheritedHandleScope {
     unsafe fn allocate() -> Self { InheritedHandleScope }
-    unsafe fn enter(&mut self, _: *mut Isolate) { }
-    unsafe fn exit(&mut self) { }
+    unsafe fn enter(&mut self, _: Env) { }
+    unsafe fn exit(&mut self, _: Env) { }
 }
 
-pub unsafe extern "C" fn escape(_out: &mut Local, _scope: *mut EscapableHandleScope, _value: Local) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the newly escaped `napi_value`.
+pub unsafe extern "C" fn escape(out: &mut Local, env: Env, scope: *mut EscapableHandleScope, value: Local) {
+    let status = napi::napi_escape_handle(env, (*scope).word as napi::napi_escapable_handle_scope, value, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn chained(_out: *mut c_void, _closure: *mut c_void, _callback: extern fn(&mut c_void, *mut c_void, *mut c_void, *mut c_void), _parent_scope: *mut c_void) { unimplemented!() }
+/// Opens an escapable handle scope in the environment of the parent scope and calls the
+/// `callback` provided with the argument signature `(out, parent_scope, &scope, closure)`.
+pub unsafe extern "C" fn chained(out: *mut c_void, env: Env, closure: *mut c_void, callback: extern fn(&mut c_void, *mut c_void, *mut c_void, *mut c_void), parent_scope: *mut c_void) {
+    let mut scope = EscapableHandleScope::new();
+    enter_escapable(&mut scope, env);
+    callback(&mut *out, parent_scope, &mut scope as *mut EscapableHandleScope as *mut c_void, closure);
+    exit_escapable(&mut scope, env);
+}
 
-pub unsafe extern "C" fn nested(_out: *mut c_void, _closure: *mut c_void, _callback: extern fn(&mut c_void, *mut c_void, *mut c_void), _realm: *mut c_void) { unimplemented!() }
+/// Opens a handle scope in the environment of the parent scope and calls the `callback`
+/// provided with the argument signature `(out, realm, closure)`.
+pub unsafe extern "C" fn nested(out: *mut c_void, env: Env, closure: *mut c_void, callback: extern fn(&mut c_void, *mut c_void, *mut c_void), realm: *mut c_void) {
+    let mut scope = HandleScope::new();
+    enter(&mut scope, env);
+    callback(&mut *out, realm, closure);
+    exit(&mut scope, env);
+}
 
-pub unsafe extern "C" fn enter(_scope: &mut HandleScope, _isolate: *mut c_void) { unimplemented!() }
+/// Opens a new handle scope, which stays current until it is closed by `exit`.
+pub unsafe extern "C" fn enter(scope: &mut HandleScope, env: Env) {
+    let mut handle_scope: napi::napi_handle_scope = mem::zeroed();
+    let status = napi::napi_open_handle_scope(env, &mut handle_scope as *mut napi::napi_handle_scope);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    scope.word = handle_scope as *mut c_void;
+}
 
-pub unsafe extern "C" fn exit(_scope: &mut HandleScope) { unimplemented!() }
+/// Closes a handle scope opened by `enter`.
+pub unsafe extern "C" fn exit(scope: &mut HandleScope, env: Env) {
+    let status = napi::napi_close_handle_scope(env, scope.word as napi::napi_handle_scope);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn enter_escapable(_scope: &mut EscapableHandleScope, _isolate: *mut c_void) { unimplemented!() }
+/// Opens a new escapable handle scope, which stays current until it is closed by
+/// `exit_escapable`.
+pub unsafe extern "C" fn enter_escapable(scope: &mut EscapableHandleScope, env: Env) {
+    let mut handle_scope: napi::napi_escapable_handle_scope = mem::zeroed();
+    let status = napi::napi_open_escapable_handle_scope(env, &mut handle_scope as *mut napi::napi_escapable_handle_scope);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    scope.word = handle_scope as *mut c_void;
+}
 
-pub unsafe extern "C" fn exit_escapable(_scope: &mut EscapableHandleScope) { unimplemented!() }
+/// Closes an escapable handle scope opened by `enter_escapable`.
+pub unsafe extern "C" fn exit_escapable(scope: &mut EscapableHandleScope, env: Env) {
+    let status = napi::napi_close_escapable_handle_scope(env, scope.word as napi::napi_escapable_handle_scope);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
 
-pub unsafe extern "C" fn size() -> usize { unimplemented!() }
+/// Gets the size of a handle scope.
+pub unsafe extern "C" fn size() -> usize {
+    mem::size_of::<HandleScope>()
+}
 
-pub unsafe extern "C" fn alignment() -> usize { unimplemented!() }
+/// Gets the alignment requirement of a handle scope.
+pub unsafe extern "C" fn alignment() -> usize {
+    mem::align_of::<HandleScope>()
+}
 
-pub unsafe extern "C" fn escapable_size() -> usize { unimplemented!() }
+/// Gets the size of an escapable handle scope.
+pub unsafe extern "C" fn escapable_size() -> usize {
+    mem::size_of::<EscapableHandleScope>()
+}
 
-pub unsafe extern "C" fn escapable_alignment() -> usize { unimplemented!() }
+/// Gets the alignment requirement of an escapable handle scope.
+pub unsafe extern "C" fn escapable_alignment() -> usize {
+    mem::align_of::<EscapableHandleScope>()
+}
 
-pub unsafe extern "C" fn get_global(_isolate: *mut c_void, _out: &mut Local) { unimplemented!() }
+/// Mutates the `out` argument provided to refer to the `napi_value` of the `global` object.
+pub unsafe extern "C" fn get_global(env: Env, out: &mut Local) {
+    let status = napi::napi_get_global(env, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
@@ -1,7 +1,33 @@
-use raw::{Local, Isolate};
+//! Facilities for working with string `napi_value`s.
 
-pub unsafe extern "C" fn new(_out: &mut Local, _isolate: *mut Isolate, _data: *const u8, _len: i32) -> bool { unimplemented!() }
+use std::os::raw::c_char;
+use std::ptr;
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn utf8_len(_str: Local) -> isize { unimplemented!() }
+use nodejs_sys as napi;
 
-pub unsafe extern "C" fn data(_out: *mut u8, _len: isize, _str: Local) -> isize { unimplemented!() }
+/// Mutates the `out` argument provided to refer to a newly created string `napi_value`.
+/// Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, data: *const u8, len: i32) -> bool {
+    let status = napi::napi_create_string_utf8(env, data as *const c_char, len as usize, out as *mut Local);
+    status == napi::napi_status::napi_ok
+}
+
+/// Gets the length of the UTF-8 encoding of a string, in bytes, not including a NUL terminator.
+pub unsafe extern "C" fn utf8_len(env: Env, str: Local) -> isize {
+    let mut len = 0usize;
+    let status = napi::napi_get_value_string_utf8(env, str, ptr::null_mut(), 0, &mut len as *mut usize);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    len as isize
+}
+
+/// Writes the UTF-8 encoding of a string to `out` and returns the number of bytes written.
+///
+/// N-API always NUL-terminates the output, so at most `len - 1` bytes of the string are
+/// written.
+pub unsafe extern "C" fn data(env: Env, out: *mut u8, len: isize, str: Local) -> isize {
+    let mut read = 0usize;
+    let status = napi::napi_get_value_string_utf8(env, str, out as *mut c_char, len as usize, &mut read as *mut usize);
+    assert_eq!(status, napi::napi_status::napi_ok);
+    read as isize
+}
@@ -0,0 +1,23 @@
+//! Facilities for working with Symbol `napi_value`s.
+
+use std::os::raw::c_char;
+use raw::{Env, Local};
+
+use nodejs_sys as napi;
+
+/// Mutates the `out` argument provided to refer to a newly created Symbol. The `description`
+/// is either a string or null for a symbol with no description.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, description: Local) {
+    let status = napi::napi_create_symbol(env, description, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Mutates the `out` argument provided to refer to the description of a Symbol, which is
+/// `undefined` if the symbol has none.
+pub unsafe extern "C" fn description(out: &mut Local, env: Env, symbol: Local) {
+    // N-API has no direct accessor, but property lookups on a primitive go through its wrapper
+    // object, which reaches `Symbol.prototype.description`.
+    let name = b"description\0";
+    let status = napi::napi_get_named_property(env, symbol, name.as_ptr() as *const c_char, out as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
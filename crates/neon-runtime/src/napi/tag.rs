@@ -1,23 +1,107 @@
-use raw::Local;
+//! Facilities for identifying the type of a `napi_value`.
 
-pub unsafe extern "C" fn is_undefined(_val: Local) -> bool { unimplemented!() }
+use raw::{Env, Local};
 
-pub unsafe extern "C" fn is_null(_val: Local) -> bool { unimplemented!() }
+use nodejs_sys as napi;
 
-pub unsafe extern "C" fn is_number(_val: Local) -> bool { unimplemented!() }
+/// Returns `true` if the value `val` has the expected value type.
+unsafe fn is_type(env: Env, val: Local, expect: napi::napi_valuetype) -> bool {
+    let mut actual = napi::napi_valuetype::napi_undefined;
+    if napi::napi_typeof(env, val, &mut actual as *mut _) == napi::napi_status::napi_ok {
+        actual == expect
+    } else {
+        false
+    }
+}
 
-pub unsafe extern "C" fn is_boolean(_val: Local) -> bool { unimplemented!() }
+/// Returns `true` if calling the N-API `predicate` on the value `val` succeeds and produces
+/// `true`.
+unsafe fn check(env: Env, val: Local, predicate: unsafe extern "C" fn(Env, Local, *mut bool) -> napi::napi_status) -> bool {
+    let mut result = false;
+    predicate(env, val, &mut result as *mut bool) == napi::napi_status::napi_ok && result
+}
 
-pub unsafe extern "C" fn is_string(_val: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `Undefined`.
+pub unsafe extern "C" fn is_undefined(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_undefined)
+}
 
-pub unsafe extern "C" fn is_object(_val: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `Null`.
+pub unsafe extern "C" fn is_null(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_null)
+}
 
-pub unsafe extern "C" fn is_array(_val: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `Number`.
+pub unsafe extern "C" fn is_number(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_number)
+}
 
-pub unsafe extern "C" fn is_function(_val: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `Boolean`.
+pub unsafe extern "C" fn is_boolean(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_boolean)
+}
 
-pub unsafe extern "C" fn is_error(_val: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `String`.
+pub unsafe extern "C" fn is_string(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_string)
+}
 
-pub unsafe extern "C" fn is_buffer(_obj: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `Object`. Like V8, functions are considered objects.
+pub unsafe extern "C" fn is_object(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_object) || is_type(env, val, napi::napi_valuetype::napi_function)
+}
 
-pub unsafe extern "C" fn is_arraybuffer(_obj: Local) -> bool { unimplemented!() }
+/// Indicates if the value type is `Array`.
+pub unsafe extern "C" fn is_array(env: Env, val: Local) -> bool {
+    check(env, val, napi::napi_is_array)
+}
+
+/// Indicates if the value type is `Function`.
+pub unsafe extern "C" fn is_function(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_function)
+}
+
+/// Indicates if the value type is `Error`.
+pub unsafe extern "C" fn is_error(env: Env, val: Local) -> bool {
+    check(env, val, napi::napi_is_error)
+}
+
+/// Indicates if the value type is `Buffer`.
+pub unsafe extern "C" fn is_buffer(env: Env, obj: Local) -> bool {
+    check(env, obj, napi::napi_is_buffer)
+}
+
+/// Indicates if the value type is `ArrayBuffer`.
+pub unsafe extern "C" fn is_arraybuffer(env: Env, obj: Local) -> bool {
+    check(env, obj, napi::napi_is_arraybuffer)
+}
+
+/// Indicates if the value type is `Promise`.
+pub unsafe extern "C" fn is_promise(env: Env, val: Local) -> bool {
+    check(env, val, napi::napi_is_promise)
+}
+
+/// Indicates if the value type is `BigInt`.
+pub unsafe extern "C" fn is_bigint(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_bigint)
+}
+
+/// Indicates if the value type is `Date`.
+pub unsafe extern "C" fn is_date(env: Env, val: Local) -> bool {
+    check(env, val, napi::napi_is_date)
+}
+
+/// Indicates if the value type is `Symbol`.
+pub unsafe extern "C" fn is_symbol(env: Env, val: Local) -> bool {
+    is_type(env, val, napi::napi_valuetype::napi_symbol)
+}
+
+/// Indicates if the value type is a typed array, such as `Uint8Array`.
+pub unsafe extern "C" fn is_typedarray(env: Env, val: Local) -> bool {
+    check(env, val, napi::napi_is_typedarray)
+}
+
+/// Indicates if the value type is `DataView`.
+pub unsafe extern "C" fn is_dataview(env: Env, val: Local) -> bool {
+    check(env, val, napi::napi_is_dataview)
+}
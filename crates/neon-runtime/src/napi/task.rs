@@ -1,7 +1,133 @@
-use raw::Local;
-use std::os::raw::c_void;
+//! Facilities for running background tasks in the libuv thread pool.
 
-pub unsafe extern "C" fn schedule(_task: *mut c_void,
-                                  _perform: unsafe extern fn(*mut c_void) -> *mut c_void,
-                                  _complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
-                                  _callback: Local) { unimplemented!() }
+use std::os::raw::{c_char, c_void};
+use std::ptr::null_mut;
+use call::set_current_env;
+use raw::{Deferred, Env, Local};
+
+use nodejs_sys as napi;
+
+/// How the completion of a task is reported back to JavaScript.
+enum Completion {
+    /// A Node-style `(err, value)` callback.
+    Callback(napi::napi_ref),
+    /// A promise that resolves with the value or rejects with the error.
+    Promise(Deferred)
+}
+
+/// The state of a scheduled task, shared between the thread pool and the main thread.
+struct Task {
+    rust_task: *mut c_void,
+    perform: unsafe extern fn(*mut c_void) -> *mut c_void,
+    complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
+    result: *mut c_void,
+    completion: Completion,
+    work: napi::napi_async_work
+}
+
+unsafe extern "C" fn execute_task(_env: Env, data: *mut c_void) {
+    let task = &mut *(data as *mut Task);
+    task.result = (task.perform)(task.rust_task);
+}
+
+unsafe extern "C" fn complete_task(env: Env, _status: napi::napi_status, data: *mut c_void) {
+    let task = Box::from_raw(data as *mut Task);
+    napi::napi_delete_async_work(env, task.work);
+
+    // N-API has already opened a handle scope and a callback scope for us.
+    let previous = set_current_env(env);
+
+    let mut argv: [Local; 2] = [null_mut(), null_mut()];
+    napi::napi_get_null(env, &mut argv[0] as *mut Local);
+    napi::napi_get_undefined(env, &mut argv[1] as *mut Local);
+
+    let mut completion: Local = null_mut();
+    (task.complete)(task.rust_task, task.result, &mut completion);
+
+    let mut pending = false;
+    napi::napi_is_exception_pending(env, &mut pending as *mut bool);
+    if pending {
+        napi::napi_get_and_clear_last_exception(env, &mut argv[0] as *mut Local);
+    } else if !completion.is_null() {
+        argv[1] = completion;
+    }
+
+    match task.completion {
+        Completion::Callback(callback_ref) => {
+            let mut callback: Local = null_mut();
+            napi::napi_get_reference_value(env, callback_ref, &mut callback as *mut Local);
+            napi::napi_delete_reference(env, callback_ref);
+
+            let mut global: Local = null_mut();
+            napi::napi_get_global(env, &mut global as *mut Local);
+
+            let mut result: Local = null_mut();
+            napi::napi_call_function(env, global, callback, argv.len(), argv.as_ptr(), &mut result as *mut Local);
+        }
+        Completion::Promise(deferred) => {
+            if pending {
+                napi::napi_reject_deferred(env, deferred, argv[0]);
+            } else {
+                napi::napi_resolve_deferred(env, deferred, argv[1]);
+            }
+        }
+    }
+
+    set_current_env(previous);
+}
+
+unsafe fn queue(env: Env,
+                task: *mut c_void,
+                perform: unsafe extern fn(*mut c_void) -> *mut c_void,
+                complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
+                completion: Completion) {
+    let task = Box::into_raw(Box::new(Task {
+        rust_task: task,
+        perform,
+        complete,
+        result: null_mut(),
+        completion,
+        work: null_mut()
+    }));
+
+    let name = b"neon_task";
+    let mut resource_name: Local = null_mut();
+    let status = napi::napi_create_string_utf8(env, name.as_ptr() as *const c_char, name.len(), &mut resource_name as *mut Local);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    let status = napi::napi_create_async_work(
+        env,
+        null_mut(),
+        resource_name,
+        Some(execute_task),
+        Some(complete_task),
+        task as *mut c_void,
+        &mut (*task).work as *mut napi::napi_async_work
+    );
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    let status = napi::napi_queue_async_work(env, (*task).work);
+    assert_eq!(status, napi::napi_status::napi_ok);
+}
+
+/// Schedules a background task.
+pub unsafe extern "C" fn schedule(env: Env,
+                                  task: *mut c_void,
+                                  perform: unsafe extern fn(*mut c_void) -> *mut c_void,
+                                  complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
+                                  callback: Local) {
+    let mut callback_ref: napi::napi_ref = null_mut();
+    let status = napi::napi_create_reference(env, callback, 1, &mut callback_ref as *mut napi::napi_ref);
+    assert_eq!(status, napi::napi_status::napi_ok);
+
+    queue(env, task, perform, complete, Completion::Callback(callback_ref));
+}
+
+/// Schedules a background task whose result settles a promise.
+pub unsafe extern "C" fn schedule_promise(env: Env,
+                                          task: *mut c_void,
+                                          perform: unsafe extern fn(*mut c_void) -> *mut c_void,
+                                          complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
+                                          deferred: Deferred) {
+    queue(env, task, perform, complete, Completion::Promise(deferred));
+}
@@ -0,0 +1,64 @@
+//! Facilities for working with typed arrays.
+
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use raw::{Env, Local, TypedArrayType};
+
+use nodejs_sys as napi;
+
+fn to_napi(ty: TypedArrayType) -> napi::napi_typedarray_type {
+    match ty {
+        TypedArrayType::Int8 => napi::napi_typedarray_type::napi_int8_array,
+        TypedArrayType::Uint8 => napi::napi_typedarray_type::napi_uint8_array,
+        TypedArrayType::Uint8Clamped => napi::napi_typedarray_type::napi_uint8_clamped_array,
+        TypedArrayType::Int16 => napi::napi_typedarray_type::napi_int16_array,
+        TypedArrayType::Uint16 => napi::napi_typedarray_type::napi_uint16_array,
+        TypedArrayType::Int32 => napi::napi_typedarray_type::napi_int32_array,
+        TypedArrayType::Uint32 => napi::napi_typedarray_type::napi_uint32_array,
+        TypedArrayType::Float32 => napi::napi_typedarray_type::napi_float32_array,
+        TypedArrayType::Float64 => napi::napi_typedarray_type::napi_float64_array,
+        TypedArrayType::BigInt64 => napi::napi_typedarray_type::napi_bigint64_array,
+        TypedArrayType::BigUint64 => napi::napi_typedarray_type::napi_biguint64_array
+    }
+}
+
+fn from_napi(ty: napi::napi_typedarray_type) -> TypedArrayType {
+    match ty {
+        napi::napi_typedarray_type::napi_int8_array => TypedArrayType::Int8,
+        napi::napi_typedarray_type::napi_uint8_array => TypedArrayType::Uint8,
+        napi::napi_typedarray_type::napi_uint8_clamped_array => TypedArrayType::Uint8Clamped,
+        napi::napi_typedarray_type::napi_int16_array => TypedArrayType::Int16,
+        napi::napi_typedarray_type::napi_uint16_array => TypedArrayType::Uint16,
+        napi::napi_typedarray_type::napi_int32_array => TypedArrayType::Int32,
+        napi::napi_typedarray_type::napi_uint32_array => TypedArrayType::Uint32,
+        napi::napi_typedarray_type::napi_float32_array => TypedArrayType::Float32,
+        napi::napi_typedarray_type::napi_float64_array => TypedArrayType::Float64,
+        napi::napi_typedarray_type::napi_bigint64_array => TypedArrayType::BigInt64,
+        napi::napi_typedarray_type::napi_biguint64_array => TypedArrayType::BigUint64
+    }
+}
+
+/// Mutates the `out` argument provided to refer to a newly created typed array of the given
+/// element type, viewing `length` elements of an `ArrayBuffer` starting at `byte_offset`.
+/// Returns `false` if the value couldn't be created.
+pub unsafe extern "C" fn new(out: &mut Local, env: Env, ty: TypedArrayType, buffer: Local, byte_offset: usize, length: usize) -> bool {
+    napi::napi_create_typedarray(env, to_napi(ty), length, buffer, byte_offset, out as *mut Local) == napi::napi_status::napi_ok
+}
+
+/// Gets the element type, the underlying `ArrayBuffer`, the byte offset and the length in
+/// elements of a typed array.
+pub unsafe extern "C" fn info(env: Env, array: Local, ty: &mut TypedArrayType, buffer: &mut Local, byte_offset: &mut usize, length: &mut usize) {
+    let mut napi_ty = napi::napi_typedarray_type::napi_uint8_array;
+    let mut data: *mut c_void = null_mut();
+    let status = napi::napi_get_typedarray_info(
+        env,
+        array,
+        &mut napi_ty as *mut napi::napi_typedarray_type,
+        length as *mut usize,
+        &mut data as *mut *mut c_void,
+        buffer as *mut Local,
+        byte_offset as *mut usize
+    );
+    assert_eq!(status, napi::napi_status::napi_ok);
+    *ty = from_napi(napi_ty);
+}
@@ -7,7 +7,10 @@
 #include "neon.h"
 #include "neon_string.h"
 #include "neon_class_metadata.h"
+#include "neon_promise.h"
 #include "neon_task.h"
+#include "neon_event.h"
+#include "neon_finalizer.h"
 
 extern "C" void Neon_Call_SetReturn(v8::FunctionCallbackInfo<v8::Value> *info, v8::Local<v8::Value> value) {
   info->GetReturnValue().Set(value);
@@ -47,11 +50,11 @@ extern "C" void Neon_Call_Get(v8::FunctionCallbackInfo<v8::Value> *info, int32_t
   *out = (*info)[i];
 }
 
-extern "C" void Neon_Object_New(v8::Local<v8::Object> *out) {
+extern "C" void Neon_Object_New(v8::Local<v8::Object> *out, v8::Isolate *isolate) {
   *out = Nan::New<v8::Object>();
 }
 
-extern "C" bool Neon_Object_GetOwnPropertyNames(v8::Local<v8::Array> *out, v8::Local<v8::Object> obj) {
+extern "C" bool Neon_Object_GetOwnPropertyNames(v8::Local<v8::Array> *out, v8::Isolate *isolate, v8::Local<v8::Object> obj) {
   Nan::MaybeLocal<v8::Array> maybe = Nan::GetOwnPropertyNames(obj);
   return maybe.ToLocal(out);
 }
@@ -60,19 +63,19 @@ extern "C" void *Neon_Object_GetIsolate(v8::Local<v8::Object> obj) {
   return obj->GetIsolate();
 }
 
-extern "C" void Neon_Primitive_Undefined(v8::Local<v8::Primitive> *out) {
+extern "C" void Neon_Primitive_Undefined(v8::Local<v8::Primitive> *out, v8::Isolate *isolate) {
   *out = Nan::Undefined();
 }
 
-extern "C" void Neon_Primitive_Null(v8::Local<v8::Primitive> *out) {
+extern "C" void Neon_Primitive_Null(v8::Local<v8::Primitive> *out, v8::Isolate *isolate) {
   *out = Nan::Null();
 }
 
-extern "C" void Neon_Primitive_Boolean(v8::Local<v8::Boolean> *out, bool b) {
+extern "C" void Neon_Primitive_Boolean(v8::Local<v8::Boolean> *out, v8::Isolate *isolate, bool b) {
   *out = b ? Nan::True() : Nan::False();
 }
 
-extern "C" bool Neon_Primitive_BooleanValue(v8::Local<v8::Boolean> p) {
+extern "C" bool Neon_Primitive_BooleanValue(v8::Isolate *isolate, v8::Local<v8::Boolean> p) {
   return p->Value();
 }
 
@@ -80,37 +83,37 @@ extern "C" void Neon_Primitive_Number(v8::Local<v8::Number> *out, v8::Isolate *i
   *out = v8::Number::New(isolate, value);
 }
 
-extern "C" double Neon_Primitive_NumberValue(v8::Local<v8::Number> n) {
+extern "C" double Neon_Primitive_NumberValue(v8::Isolate *isolate, v8::Local<v8::Number> n) {
   return n->Value();
 }
 
-extern "C" bool Neon_Primitive_IsUint32(v8::Local<v8::Primitive> p) {
+extern "C" bool Neon_Primitive_IsUint32(v8::Isolate *isolate, v8::Local<v8::Primitive> p) {
   return p->IsUint32();
 }
 
-extern "C" bool Neon_Primitive_IsInt32(v8::Local<v8::Primitive> p) {
+extern "C" bool Neon_Primitive_IsInt32(v8::Isolate *isolate, v8::Local<v8::Primitive> p) {
   return p->IsInt32();
 }
 
-extern "C" bool Neon_Object_Get_Index(v8::Local<v8::Value> *out, v8::Local<v8::Object> obj, uint32_t index) {
+extern "C" bool Neon_Object_Get_Index(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Object> obj, uint32_t index) {
   Nan::MaybeLocal<v8::Value> maybe = Nan::Get(obj, index);
   return maybe.ToLocal(out);
 }
 
-extern "C" bool Neon_Object_Set_Index(bool *out, v8::Local<v8::Object> object, uint32_t index, v8::Local<v8::Value> val) {
+extern "C" bool Neon_Object_Set_Index(bool *out, v8::Isolate *isolate, v8::Local<v8::Object> object, uint32_t index, v8::Local<v8::Value> val) {
   Nan::Maybe<bool> maybe = Nan::Set(object, index, val);
   return maybe.IsJust() && (*out = maybe.FromJust(), true);
 }
 
-bool Neon_ASCII_Key(v8::Local<v8::String> *key, const uint8_t *data, int32_t len) {
-  Nan::MaybeLocal<v8::String> maybe_key = v8::String::NewFromUtf8(v8::Isolate::GetCurrent(), (const char*)data, v8::NewStringType::kNormal, len);
+bool Neon_ASCII_Key(v8::Local<v8::String> *key, v8::Isolate *isolate, const uint8_t *data, int32_t len) {
+  Nan::MaybeLocal<v8::String> maybe_key = v8::String::NewFromUtf8(isolate, (const char*)data, v8::NewStringType::kNormal, len);
   return maybe_key.ToLocal(key);
 }
 
-extern "C" bool Neon_Object_Get_String(v8::Local<v8::Value> *out, v8::Local<v8::Object> obj, const uint8_t *data, int32_t len) {
+extern "C" bool Neon_Object_Get_String(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Object> obj, const uint8_t *data, int32_t len) {
   Nan::EscapableHandleScope scope;
   v8::Local<v8::String> key;
-  if (!Neon_ASCII_Key(&key, data, len)) {
+  if (!Neon_ASCII_Key(&key, isolate, data, len)) {
     return false;
   }
   Nan::MaybeLocal<v8::Value> maybe = Nan::Get(obj, key);
@@ -122,22 +125,22 @@ extern "C" bool Neon_Object_Get_String(v8::Local<v8::Value> *out, v8::Local<v8::
   return true;
 }
 
-extern "C" bool Neon_Object_Set_String(bool *out, v8::Local<v8::Object> obj, const uint8_t *data, int32_t len, v8::Local<v8::Value> val) {
+extern "C" bool Neon_Object_Set_String(bool *out, v8::Isolate *isolate, v8::Local<v8::Object> obj, const uint8_t *data, int32_t len, v8::Local<v8::Value> val) {
   Nan::HandleScope scope;
   v8::Local<v8::String> key;
-  if (!Neon_ASCII_Key(&key, data, len)) {
+  if (!Neon_ASCII_Key(&key, isolate, data, len)) {
     return false;
   }
   Nan::Maybe<bool> maybe = Nan::Set(obj, key, val);
   return maybe.IsJust() && (*out = maybe.FromJust(), true);
 }
 
-extern "C" bool Neon_Object_Get(v8::Local<v8::Value> *out, v8::Local<v8::Object> obj, v8::Local<v8::Value> key) {
+extern "C" bool Neon_Object_Get(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Object> obj, v8::Local<v8::Value> key) {
   Nan::MaybeLocal<v8::Value> maybe = Nan::Get(obj, key);
   return maybe.ToLocal(out);
 }
 
-extern "C" bool Neon_Object_Set(bool *out, v8::Local<v8::Object> obj, v8::Local<v8::Value> key, v8::Local<v8::Value> val) {
+extern "C" bool Neon_Object_Set(bool *out, v8::Isolate *isolate, v8::Local<v8::Object> obj, v8::Local<v8::Value> key, v8::Local<v8::Value> val) {
   Nan::Maybe<bool> maybe = Nan::Set(obj, key, val);
   if (maybe.IsJust()) {
     *out = maybe.FromJust();
@@ -150,7 +153,7 @@ extern "C" void Neon_Array_New(v8::Local<v8::Array> *out, v8::Isolate *isolate,
   *out = v8::Array::New(isolate, length);
 }
 
-extern "C" uint32_t Neon_Array_Length(v8::Local<v8::Array> array) {
+extern "C" uint32_t Neon_Array_Length(v8::Isolate *isolate, v8::Local<v8::Array> array) {
   return array->Length();
 }
 
@@ -159,29 +162,29 @@ extern "C" bool Neon_String_New(v8::Local<v8::String> *out, v8::Isolate *isolate
   return maybe.ToLocal(out);
 }
 
-extern "C" int32_t Neon_String_Utf8Length(v8::Local<v8::String> str) {
+extern "C" int32_t Neon_String_Utf8Length(v8::Isolate *isolate, v8::Local<v8::String> str) {
   #if NODE_MODULE_VERSION >= NODE_11_0_MODULE_VERSION
-    return str->Utf8Length(v8::Isolate::GetCurrent());
+    return str->Utf8Length(isolate);
   #else
     return str->Utf8Length();
   #endif
 }
 
-extern "C" size_t Neon_String_Data(char *out, size_t len, v8::Local<v8::Value> str) {
+extern "C" size_t Neon_String_Data(v8::Isolate *isolate, char *out, size_t len, v8::Local<v8::Value> str) {
   return Nan::DecodeWrite(out, len, str, Nan::UTF8);
 }
 
-extern "C" bool Neon_Convert_ToString(v8::Local<v8::String> *out, v8::Local<v8::Value> value) {
+extern "C" bool Neon_Convert_ToString(v8::Local<v8::String> *out, v8::Isolate *isolate, v8::Local<v8::Value> value) {
   Nan::MaybeLocal<v8::String> maybe = Nan::To<v8::String>(value);
   return maybe.ToLocal(out);
 }
 
-extern "C" bool Neon_Convert_ToObject(v8::Local<v8::Object> *out, v8::Local<v8::Value> *value) {
+extern "C" bool Neon_Convert_ToObject(v8::Local<v8::Object> *out, v8::Isolate *isolate, v8::Local<v8::Value> *value) {
   Nan::MaybeLocal<v8::Object> maybe = Nan::To<v8::Object>(*value);
   return maybe.ToLocal(out);
 }
 
-extern "C" bool Neon_Buffer_New(v8::Local<v8::Object> *out, uint32_t size) {
+extern "C" bool Neon_Buffer_New(v8::Local<v8::Object> *out, v8::Isolate *isolate, uint32_t size) {
   Nan::MaybeLocal<v8::Object> maybe = Nan::NewBuffer(size);
   if (!maybe.ToLocal(out)) {
     return false;
@@ -192,18 +195,24 @@ extern "C" bool Neon_Buffer_New(v8::Local<v8::Object> *out, uint32_t size) {
   return true;
 }
 
-extern "C" bool Neon_Buffer_Uninitialized(v8::Local<v8::Object> *out, uint32_t size) {
+extern "C" bool Neon_Buffer_Uninitialized(v8::Local<v8::Object> *out, v8::Isolate *isolate, uint32_t size) {
   Nan::MaybeLocal<v8::Object> maybe = Nan::NewBuffer(size);
   return maybe.ToLocal(out);
 }
 
-extern "C" size_t Neon_Buffer_Data(void **base_out, v8::Local<v8::Object> obj) {
+extern "C" size_t Neon_Buffer_Data(v8::Isolate *isolate, void **base_out, v8::Local<v8::Object> obj) {
   *base_out = node::Buffer::Data(obj);
 
   return node::Buffer::Length(obj);
 }
 
-extern "C" bool Neon_Tag_IsBuffer(v8::Local<v8::Value> obj) {
+extern "C" bool Neon_Buffer_External(v8::Local<v8::Object> *out, v8::Isolate *isolate, void *base, size_t length, void *data, Neon_DropCallback finalize) {
+  neon::External *external = new neon::External { data, finalize };
+  Nan::MaybeLocal<v8::Object> maybe = Nan::NewBuffer(static_cast<char *>(base), length, neon::External::Free, external);
+  return maybe.ToLocal(out);
+}
+
+extern "C" bool Neon_Tag_IsBuffer(v8::Isolate *isolate, v8::Local<v8::Value> obj) {
   return node::Buffer::HasInstance(obj);
 }
 
@@ -212,7 +221,20 @@ extern "C" bool Neon_ArrayBuffer_New(v8::Local<v8::ArrayBuffer> *out, v8::Isolat
   return true;
 }
 
-extern "C" size_t Neon_ArrayBuffer_Data(void **base_out, v8::Local<v8::ArrayBuffer> buffer) {
+extern "C" bool Neon_ArrayBuffer_External(v8::Local<v8::ArrayBuffer> *out, v8::Isolate *isolate, void *base, size_t length, void *data, Neon_DropCallback finalize) {
+  neon::External *external = new neon::External { data, finalize };
+#if V8_MAJOR_VERSION >= 8
+  std::unique_ptr<v8::BackingStore> store = v8::ArrayBuffer::NewBackingStore(base, length, neon::External::Delete, external);
+  *out = v8::ArrayBuffer::New(isolate, std::move(store));
+#else
+  // Externalized memory is not freed by V8, so release it once the buffer is collected.
+  *out = v8::ArrayBuffer::New(isolate, base, length, v8::ArrayBufferCreationMode::kExternalized);
+  new neon::Finalizer(isolate, *out, external, [](void *hint) { neon::External::Free(nullptr, hint); });
+#endif
+  return true;
+}
+
+extern "C" size_t Neon_ArrayBuffer_Data(v8::Isolate *isolate, void **base_out, v8::Local<v8::ArrayBuffer> buffer) {
   v8::ArrayBuffer::Contents contents = buffer->GetContents();
   *base_out = contents.Data();
 
@@ -220,21 +242,86 @@ extern "C" size_t Neon_ArrayBuffer_Data(void **base_out, v8::Local<v8::ArrayBuff
 }
 
 
-extern "C" bool Neon_Tag_IsArrayBuffer(v8::Local<v8::Value> value) {
+extern "C" bool Neon_Tag_IsArrayBuffer(v8::Isolate *isolate, v8::Local<v8::Value> value) {
   return value->IsArrayBuffer();
 }
 
-extern "C" void Neon_Scope_Escape(v8::Local<v8::Value> *out, Nan::EscapableHandleScope *scope, v8::Local<v8::Value> value) {
+extern "C" bool Neon_TypedArray_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, typedarray_type_t type, v8::Local<v8::ArrayBuffer> buffer, size_t byte_offset, size_t length) {
+  switch (type) {
+  case NEON_INT8_ARRAY: *out = v8::Int8Array::New(buffer, byte_offset, length); return true;
+  case NEON_UINT8_ARRAY: *out = v8::Uint8Array::New(buffer, byte_offset, length); return true;
+  case NEON_UINT8_CLAMPED_ARRAY: *out = v8::Uint8ClampedArray::New(buffer, byte_offset, length); return true;
+  case NEON_INT16_ARRAY: *out = v8::Int16Array::New(buffer, byte_offset, length); return true;
+  case NEON_UINT16_ARRAY: *out = v8::Uint16Array::New(buffer, byte_offset, length); return true;
+  case NEON_INT32_ARRAY: *out = v8::Int32Array::New(buffer, byte_offset, length); return true;
+  case NEON_UINT32_ARRAY: *out = v8::Uint32Array::New(buffer, byte_offset, length); return true;
+  case NEON_FLOAT32_ARRAY: *out = v8::Float32Array::New(buffer, byte_offset, length); return true;
+  case NEON_FLOAT64_ARRAY: *out = v8::Float64Array::New(buffer, byte_offset, length); return true;
+#if NODE_MODULE_VERSION >= NODE_11_0_MODULE_VERSION
+  case NEON_BIGINT64_ARRAY: *out = v8::BigInt64Array::New(buffer, byte_offset, length); return true;
+  case NEON_BIGUINT64_ARRAY: *out = v8::BigUint64Array::New(buffer, byte_offset, length); return true;
+#endif
+  default:
+    Nan::ThrowTypeError("typed array type is not supported by this version of Node");
+    return false;
+  }
+}
+
+static typedarray_type_t typedarray_type(v8::Local<v8::Value> array) {
+  if (array->IsInt8Array()) return NEON_INT8_ARRAY;
+  if (array->IsUint8Array()) return NEON_UINT8_ARRAY;
+  if (array->IsUint8ClampedArray()) return NEON_UINT8_CLAMPED_ARRAY;
+  if (array->IsInt16Array()) return NEON_INT16_ARRAY;
+  if (array->IsUint16Array()) return NEON_UINT16_ARRAY;
+  if (array->IsInt32Array()) return NEON_INT32_ARRAY;
+  if (array->IsUint32Array()) return NEON_UINT32_ARRAY;
+  if (array->IsFloat32Array()) return NEON_FLOAT32_ARRAY;
+#if NODE_MODULE_VERSION >= NODE_11_0_MODULE_VERSION
+  if (array->IsBigInt64Array()) return NEON_BIGINT64_ARRAY;
+  if (array->IsBigUint64Array()) return NEON_BIGUINT64_ARRAY;
+#endif
+  return NEON_FLOAT64_ARRAY;
+}
+
+extern "C" void Neon_TypedArray_Info(v8::Isolate *isolate, v8::Local<v8::Value> array, typedarray_type_t *type, v8::Local<v8::ArrayBuffer> *buffer, size_t *byte_offset, size_t *length) {
+  v8::Local<v8::TypedArray> typed = array.As<v8::TypedArray>();
+  *type = typedarray_type(array);
+  *buffer = typed->Buffer();
+  *byte_offset = typed->ByteOffset();
+  *length = typed->Length();
+}
+
+extern "C" bool Neon_Tag_IsTypedArray(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return val->IsTypedArray();
+}
+
+extern "C" bool Neon_DataView_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::ArrayBuffer> buffer, size_t byte_offset, size_t byte_length) {
+  *out = v8::DataView::New(buffer, byte_offset, byte_length);
+  return true;
+}
+
+extern "C" void Neon_DataView_Info(v8::Isolate *isolate, v8::Local<v8::Value> view, v8::Local<v8::ArrayBuffer> *buffer, size_t *byte_offset, size_t *byte_length) {
+  v8::Local<v8::DataView> data_view = view.As<v8::DataView>();
+  *buffer = data_view->Buffer();
+  *byte_offset = data_view->ByteOffset();
+  *byte_length = data_view->ByteLength();
+}
+
+extern "C" bool Neon_Tag_IsDataView(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return val->IsDataView();
+}
+
+extern "C" void Neon_Scope_Escape(v8::Local<v8::Value> *out, v8::Isolate *isolate, Nan::EscapableHandleScope *scope, v8::Local<v8::Value> value) {
   *out = scope->Escape(value);
 }
 
-extern "C" void Neon_Scope_Chained(void *out, void *closure, Neon_ChainedScopeCallback callback, void *parent_scope) {
-  Nan::EscapableHandleScope v8_scope;
+extern "C" void Neon_Scope_Chained(void *out, v8::Isolate *isolate, void *closure, Neon_ChainedScopeCallback callback, void *parent_scope) {
+  v8::EscapableHandleScope v8_scope(isolate);
   callback(out, parent_scope, &v8_scope, closure);
 }
 
-extern "C" void Neon_Scope_Nested(void *out, void *closure, Neon_NestedScopeCallback callback, void *realm) {
-  Nan::HandleScope v8_scope;
+extern "C" void Neon_Scope_Nested(void *out, v8::Isolate *isolate, void *closure, Neon_NestedScopeCallback callback, void *realm) {
+  v8::HandleScope v8_scope(isolate);
   callback(out, realm, closure);
 }
 
@@ -243,7 +330,7 @@ extern "C" void Neon_Scope_Enter(v8::HandleScope *scope, v8::Isolate *isolate) {
   ::new (p) v8::HandleScope(isolate);
 }
 
-extern "C" void Neon_Scope_Exit(v8::HandleScope *scope) {
+extern "C" void Neon_Scope_Exit(v8::HandleScope *scope, v8::Isolate *isolate) {
   scope->HandleScope::~HandleScope();
 }
 extern "C" void Neon_Scope_Enter_Escapable(v8::EscapableHandleScope *scope, v8::Isolate *isolate) {
@@ -251,7 +338,7 @@ extern "C" void Neon_Scope_Enter_Escapable(v8::EscapableHandleScope *scope, v8::
   ::new (p) v8::EscapableHandleScope(isolate);
 }
 
-extern "C" void Neon_Scope_Exit_Escapable(v8::EscapableHandleScope *scope) {
+extern "C" void Neon_Scope_Exit_Escapable(v8::EscapableHandleScope *scope, v8::Isolate *isolate) {
   scope->EscapableHandleScope::~EscapableHandleScope();
 }
 
@@ -318,14 +405,20 @@ extern "C" void *Neon_Class_GetClassMap(v8::Isolate *isolate) {
 
 void cleanup_class_map(void *arg) {
   neon::ClassMapHolder *holder = static_cast<neon::ClassMapHolder *>(arg);
+  holder->GetIsolate()->SetData(NEON_ISOLATE_SLOT, nullptr);
   delete holder;
 }
 
 extern "C" void Neon_Class_SetClassMap(v8::Isolate *isolate, void *map, Neon_DropCallback drop_map) {
-  neon::ClassMapHolder *holder = new neon::ClassMapHolder(map, drop_map);
+  neon::ClassMapHolder *holder = new neon::ClassMapHolder(isolate, map, drop_map);
   isolate->SetData(NEON_ISOLATE_SLOT, holder);
-  // ISSUE(#77): When workers land in node, this will need to be generalized to a per-worker version.
-  node::AtExit(cleanup_class_map, holder);
+  // Every worker thread has its own environment, which runs its cleanup hooks when the worker
+  // exits, and the main thread's environment runs them when the process exits.
+  #if NODE_MODULE_VERSION >= NODE_10_0_MODULE_VERSION
+    node::AddEnvironmentCleanupHook(isolate, cleanup_class_map, holder);
+  #else
+    node::AtExit(cleanup_class_map, holder);
+  #endif
 }
 
 extern "C" void *Neon_Class_GetCallKernel(v8::Local<v8::External> wrapper) {
@@ -348,9 +441,9 @@ extern "C" bool Neon_Class_Constructor(v8::Local<v8::Function> *out, v8::Local<v
   return maybe.ToLocal(out);
 }
 
-extern "C" bool Neon_Class_HasInstance(void *metadata_pointer, v8::Local<v8::Value> v) {
+extern "C" bool Neon_Class_HasInstance(v8::Isolate *isolate, void *metadata_pointer, v8::Local<v8::Value> v) {
   neon::ClassMetadata *metadata = static_cast<neon::ClassMetadata *>(metadata_pointer);
-  return metadata->GetTemplate(v8::Isolate::GetCurrent())->HasInstance(v);
+  return metadata->GetTemplate(isolate)->HasInstance(v);
 }
 
 extern "C" bool Neon_Class_SetName(v8::Isolate *isolate, void *metadata_pointer, const char *name, uint32_t byte_length) {
@@ -405,7 +498,7 @@ extern "C" bool Neon_Class_MetadataToConstructor(v8::Local<v8::Function> *out, v
   return maybe.ToLocal(out);
 }
 
-extern "C" void *Neon_Class_GetInstanceInternals(v8::Local<v8::Object> obj) {
+extern "C" void *Neon_Class_GetInstanceInternals(v8::Isolate *isolate, v8::Local<v8::Object> obj) {
   return static_cast<neon::BaseClassInstanceMetadata *>(obj->GetAlignedPointerFromInternalField(0))->GetInternals();
 }
 
@@ -445,60 +538,191 @@ extern "C" bool Neon_Fun_Construct(v8::Local<v8::Object> *out, v8::Isolate *isol
   return maybe_result.ToLocal(out);
 }
 
-extern "C" bool Neon_Tag_IsUndefined(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsUndefined(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsUndefined();
 }
 
-extern "C" bool Neon_Tag_IsNull(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsNull(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsNull();
 }
 
-extern "C" bool Neon_Tag_IsNumber(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsNumber(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsNumber();
 }
 
-extern "C" bool Neon_Tag_IsBoolean(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsBoolean(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsBoolean();
 }
 
-extern "C" bool Neon_Tag_IsString(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsString(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsString();
 }
 
-extern "C" bool Neon_Tag_IsObject(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsObject(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsObject();
 }
 
-extern "C" bool Neon_Tag_IsArray(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsArray(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsArray();
 }
 
-extern "C" bool Neon_Tag_IsFunction(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsFunction(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsFunction();
 }
 
-extern "C" bool Neon_Tag_IsError(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsError(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   return val->IsNativeError();
 }
 
-extern "C" void Neon_Error_Throw(v8::Local<v8::Value> val) {
+extern "C" bool Neon_Tag_IsPromise(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return val->IsPromise();
+}
+
+extern "C" bool Neon_Tag_IsDate(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return val->IsDate();
+}
+
+extern "C" bool Neon_Tag_IsSymbol(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return val->IsSymbol();
+}
+
+extern "C" void Neon_Symbol_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Value> description) {
+  if (description.IsEmpty()) {
+    *out = v8::Symbol::New(isolate);
+  } else {
+    *out = v8::Symbol::New(isolate, description.As<v8::String>());
+  }
+}
+
+extern "C" void Neon_Symbol_Description(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Value> symbol) {
+#if V8_MAJOR_VERSION >= 9
+  *out = symbol.As<v8::Symbol>()->Description(isolate);
+#else
+  *out = symbol.As<v8::Symbol>()->Name();
+#endif
+}
+
+extern "C" bool Neon_Date_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, double value) {
+  Nan::MaybeLocal<v8::Date> maybe = Nan::New<v8::Date>(value);
+  v8::Local<v8::Date> result;
+  if (!maybe.ToLocal(&result)) {
+    return false;
+  }
+  *out = result;
+  return true;
+}
+
+extern "C" double Neon_Date_Value(v8::Isolate *isolate, v8::Local<v8::Value> date) {
+  return date.As<v8::Date>()->ValueOf();
+}
+
+// The BigInt API is complete as of V8 7.0, which first shipped with Node 11. With older
+// versions, no value is a BigInt and creating one throws.
+#if NODE_MODULE_VERSION >= NODE_11_0_MODULE_VERSION
+
+extern "C" bool Neon_Tag_IsBigInt(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return val->IsBigInt();
+}
+
+extern "C" bool Neon_BigInt_NewI64(v8::Local<v8::Value> *out, v8::Isolate *isolate, int64_t value) {
+  *out = v8::BigInt::New(isolate, value);
+  return true;
+}
+
+extern "C" bool Neon_BigInt_NewU64(v8::Local<v8::Value> *out, v8::Isolate *isolate, uint64_t value) {
+  *out = v8::BigInt::NewFromUnsigned(isolate, value);
+  return true;
+}
+
+extern "C" bool Neon_BigInt_NewWords(v8::Local<v8::Value> *out, v8::Isolate *isolate, bool negative, size_t count, const uint64_t *words) {
+  v8::MaybeLocal<v8::BigInt> maybe = v8::BigInt::NewFromWords(isolate->GetCurrentContext(), negative ? 1 : 0, (int)count, words);
+  v8::Local<v8::BigInt> result;
+  if (!maybe.ToLocal(&result)) {
+    return false;
+  }
+  *out = result;
+  return true;
+}
+
+extern "C" bool Neon_BigInt_ToI64(v8::Isolate *isolate, int64_t *out, v8::Local<v8::Value> bigint) {
+  bool lossless = false;
+  *out = bigint.As<v8::BigInt>()->Int64Value(&lossless);
+  return lossless;
+}
+
+extern "C" bool Neon_BigInt_ToU64(v8::Isolate *isolate, uint64_t *out, v8::Local<v8::Value> bigint) {
+  bool lossless = false;
+  *out = bigint.As<v8::BigInt>()->Uint64Value(&lossless);
+  return lossless;
+}
+
+extern "C" size_t Neon_BigInt_ToWords(v8::Isolate *isolate, bool *negative, uint64_t *words, size_t capacity, v8::Local<v8::Value> bigint) {
+  v8::Local<v8::BigInt> value = bigint.As<v8::BigInt>();
+  int sign_bit = 0;
+  int count = (int)capacity;
+  size_t needed = value->WordCount();
+  value->ToWordsArray(&sign_bit, &count, words);
+  *negative = sign_bit != 0;
+  return needed;
+}
+
+#else
+
+extern "C" bool Neon_Tag_IsBigInt(v8::Isolate *isolate, v8::Local<v8::Value> val) {
+  return false;
+}
+
+extern "C" bool Neon_BigInt_NewI64(v8::Local<v8::Value> *out, v8::Isolate *isolate, int64_t value) {
+  Nan::ThrowError("BigInt is not supported by this version of Node");
+  return false;
+}
+
+extern "C" bool Neon_BigInt_NewU64(v8::Local<v8::Value> *out, v8::Isolate *isolate, uint64_t value) {
+  Nan::ThrowError("BigInt is not supported by this version of Node");
+  return false;
+}
+
+extern "C" bool Neon_BigInt_NewWords(v8::Local<v8::Value> *out, v8::Isolate *isolate, bool negative, size_t count, const uint64_t *words) {
+  Nan::ThrowError("BigInt is not supported by this version of Node");
+  return false;
+}
+
+// Since no value is a BigInt, the conversions are never called.
+
+extern "C" bool Neon_BigInt_ToI64(v8::Isolate *isolate, int64_t *out, v8::Local<v8::Value> bigint) {
+  *out = 0;
+  return false;
+}
+
+extern "C" bool Neon_BigInt_ToU64(v8::Isolate *isolate, uint64_t *out, v8::Local<v8::Value> bigint) {
+  *out = 0;
+  return false;
+}
+
+extern "C" size_t Neon_BigInt_ToWords(v8::Isolate *isolate, bool *negative, uint64_t *words, size_t capacity, v8::Local<v8::Value> bigint) {
+  *negative = false;
+  return 0;
+}
+
+#endif
+
+extern "C" void Neon_Error_Throw(v8::Isolate *isolate, v8::Local<v8::Value> val) {
   Nan::ThrowError(val);
 }
 
-extern "C" void Neon_Error_NewError(v8::Local<v8::Value> *out, v8::Local<v8::String> msg) {
+extern "C" void Neon_Error_NewError(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::String> msg) {
   *out = v8::Exception::Error(msg);
 }
 
-extern "C" void Neon_Error_NewTypeError(v8::Local<v8::Value> *out, v8::Local<v8::String> msg) {
+extern "C" void Neon_Error_NewTypeError(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::String> msg) {
   *out = v8::Exception::TypeError(msg);
 }
 
-extern "C" void Neon_Error_NewRangeError(v8::Local<v8::Value> *out, v8::Local<v8::String> msg) {
+extern "C" void Neon_Error_NewRangeError(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::String> msg) {
   *out = v8::Exception::RangeError(msg);
 }
 
-extern "C" void Neon_Error_ThrowErrorFromUtf8(const uint8_t *data, int32_t len) {
-  v8::Isolate *isolate = v8::Isolate::GetCurrent();
+extern "C" void Neon_Error_ThrowErrorFromUtf8(v8::Isolate *isolate, const uint8_t *data, int32_t len) {
   Nan::MaybeLocal<v8::String> maybe = v8::String::NewFromUtf8(isolate, (const char*)data, v8::NewStringType::kNormal, len);
 
   v8::Local<v8::String> msg;
@@ -511,12 +735,121 @@ extern "C" void Neon_Error_ThrowErrorFromUtf8(const uint8_t *data, int32_t len)
   Nan::ThrowError(err);
 }
 
-extern "C" bool Neon_Mem_SameHandle(v8::Local<v8::Value> v1, v8::Local<v8::Value> v2) {
+extern "C" void *Neon_Promise_New(v8::Local<v8::Promise> *out, v8::Isolate *isolate) {
+  v8::Local<v8::Promise::Resolver> resolver = v8::Promise::Resolver::New(isolate->GetCurrentContext()).ToLocalChecked();
+  *out = resolver->GetPromise();
+  return neon::new_deferred(isolate, resolver);
+}
+
+extern "C" bool Neon_Promise_Resolve(v8::Isolate *isolate, void *deferred, v8::Local<v8::Value> value) {
+  v8::Local<v8::Promise::Resolver> resolver = neon::take_deferred(isolate, deferred);
+  return resolver->Resolve(isolate->GetCurrentContext(), value).FromMaybe(false);
+}
+
+extern "C" bool Neon_Promise_Reject(v8::Isolate *isolate, void *deferred, v8::Local<v8::Value> value) {
+  v8::Local<v8::Promise::Resolver> resolver = neon::take_deferred(isolate, deferred);
+  return resolver->Reject(isolate->GetCurrentContext(), value).FromMaybe(false);
+}
+
+extern "C" uint32_t Neon_Promise_State(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Promise> promise) {
+  v8::Promise::PromiseState state = promise->State();
+  if (state != v8::Promise::kPending) {
+    *out = promise->Result();
+  }
+  return state;
+}
+
+extern "C" void Neon_Promise_Settle(v8::Isolate *isolate, void *deferred, Neon_PromiseSettleCallback complete, void *data) {
+  v8::Local<v8::Promise::Resolver> resolver = neon::take_deferred(isolate, deferred);
+  v8::Local<v8::Context> context = isolate->GetCurrentContext();
+  v8::Local<v8::Value> value;
+  v8::Local<v8::Value> exception;
+  bool failed = false;
+
+  {
+    v8::TryCatch trycatch(isolate);
+    complete(data, &value);
+    if (trycatch.HasCaught()) {
+      failed = true;
+      exception = trycatch.Exception();
+    }
+  }
+
+  if (failed) {
+    resolver->Reject(context, exception).FromMaybe(false);
+  } else {
+    if (value.IsEmpty()) {
+      value = v8::Undefined(isolate);
+    }
+    resolver->Resolve(context, value).FromMaybe(false);
+  }
+}
+
+extern "C" bool Neon_Mem_SameHandle(v8::Isolate *isolate, v8::Local<v8::Value> v1, v8::Local<v8::Value> v2) {
   return v1 == v2;
 }
 
-extern "C" void Neon_Task_Schedule(void *task, Neon_TaskPerformCallback perform, Neon_TaskCompleteCallback complete, v8::Local<v8::Function> callback) {
-  v8::Isolate *isolate = v8::Isolate::GetCurrent();
+extern "C" void *Neon_Mem_NewPersistent(v8::Isolate *isolate, v8::Local<v8::Value> value) {
+  return new v8::Persistent<v8::Value>(isolate, value);
+}
+
+extern "C" void Neon_Mem_GetPersistent(v8::Local<v8::Value> *out, v8::Isolate *isolate, void *persistent) {
+  *out = v8::Local<v8::Value>::New(isolate, *static_cast<v8::Persistent<v8::Value> *>(persistent));
+}
+
+extern "C" void Neon_Mem_DropPersistent(v8::Isolate *isolate, void *persistent) {
+  v8::Persistent<v8::Value> *handle = static_cast<v8::Persistent<v8::Value> *>(persistent);
+  handle->Reset();
+  delete handle;
+}
+
+extern "C" void *Neon_Mem_NewWeak(v8::Isolate *isolate, v8::Local<v8::Value> value) {
+  v8::Persistent<v8::Value> *handle = new v8::Persistent<v8::Value>(isolate, value);
+  // Without a callback, the handle is simply emptied when its value is collected.
+  handle->SetWeak();
+  return handle;
+}
+
+extern "C" bool Neon_Mem_GetWeak(v8::Local<v8::Value> *out, v8::Isolate *isolate, void *weak) {
+  v8::Persistent<v8::Value> *handle = static_cast<v8::Persistent<v8::Value> *>(weak);
+  if (handle->IsEmpty()) {
+    return false;
+  }
+  *out = v8::Local<v8::Value>::New(isolate, *handle);
+  return true;
+}
+
+extern "C" void Neon_Mem_AddFinalizer(v8::Isolate *isolate, v8::Local<v8::Object> obj, void *data, Neon_DropCallback finalize) {
+  new neon::Finalizer(isolate, obj, data, finalize);
+}
+
+extern "C" void Neon_Task_Schedule(v8::Isolate *isolate, void *task, Neon_TaskPerformCallback perform, Neon_TaskCompleteCallback complete, v8::Local<v8::Function> callback) {
   neon::Task *internal_task = new neon::Task(isolate, task, perform, complete, callback);
-  neon::queue_task(internal_task);
+  neon::queue_task(isolate, internal_task);
+}
+
+extern "C" void Neon_Task_SchedulePromise(v8::Isolate *isolate, void *task, Neon_TaskPerformCallback perform, Neon_TaskCompleteCallback complete, void *deferred) {
+  neon::Task *internal_task = new neon::Task(isolate, task, perform, complete, deferred);
+  neon::queue_task(isolate, internal_task);
+}
+
+extern "C" void *Neon_EventHandler_New(v8::Isolate *isolate, v8::Local<v8::Value> self, v8::Local<v8::Function> callback) {
+  return new neon::EventHandler(isolate, self, callback);
+}
+
+extern "C" bool Neon_EventHandler_Schedule(void *handler, void *rust_callback, Neon_EventHandlerCallback complete) {
+  static_cast<neon::EventHandler *>(handler)->schedule(rust_callback, complete);
+  return true;
+}
+
+extern "C" void Neon_EventHandler_Ref(v8::Isolate *isolate, void *handler) {
+  static_cast<neon::EventHandler *>(handler)->ref();
+}
+
+extern "C" void Neon_EventHandler_Unref(v8::Isolate *isolate, void *handler) {
+  static_cast<neon::EventHandler *>(handler)->unref();
+}
+
+extern "C" void Neon_EventHandler_Delete(void *handler) {
+  static_cast<neon::EventHandler *>(handler)->close();
 }
@@ -11,8 +11,26 @@ typedef struct {
   void* dynamic_callback;
 } callback_t;
 
+// corresponding Rust enum `TypedArrayType` defined in lib.rs, numbered like N-API's
+// `napi_typedarray_type`
+typedef enum {
+  NEON_INT8_ARRAY,
+  NEON_UINT8_ARRAY,
+  NEON_UINT8_CLAMPED_ARRAY,
+  NEON_INT16_ARRAY,
+  NEON_UINT16_ARRAY,
+  NEON_INT32_ARRAY,
+  NEON_UINT32_ARRAY,
+  NEON_FLOAT32_ARRAY,
+  NEON_FLOAT64_ARRAY,
+  NEON_BIGINT64_ARRAY,
+  NEON_BIGUINT64_ARRAY
+} typedarray_type_t;
+
 extern "C" {
 
+  typedef void (*Neon_DropCallback)(void *);
+
   void Neon_Call_SetReturn(v8::FunctionCallbackInfo<v8::Value> *info, v8::Local<v8::Value> value);
   void *Neon_Call_GetIsolate(v8::FunctionCallbackInfo<v8::Value> *info);
   void *Neon_Call_CurrentIsolate();
@@ -23,50 +41,58 @@ extern "C" {
   void Neon_Call_Get(v8::FunctionCallbackInfo<v8::Value> *info, int32_t i, v8::Local<v8::Value> *out);
 
   void Neon_Primitive_Number(v8::Local<v8::Number> *out, v8::Isolate *isolate, double value);
-  void Neon_Primitive_Undefined(v8::Local<v8::Primitive> *out);
-  void Neon_Primitive_Null(v8::Local<v8::Primitive> *out);
-  void Neon_Primitive_Boolean(v8::Local<v8::Boolean> *out, bool b);
-  bool Neon_Primitive_IsUint32(v8::Local<v8::Primitive> p);
-  bool Neon_Primitive_IsInt32(v8::Local<v8::Primitive> p);
-
-  void Neon_Object_New(v8::Local<v8::Object> *out);
-  bool Neon_Object_GetOwnPropertyNames(v8::Local<v8::Array> *out, v8::Local<v8::Object> obj);
+  void Neon_Primitive_Undefined(v8::Local<v8::Primitive> *out, v8::Isolate *isolate);
+  void Neon_Primitive_Null(v8::Local<v8::Primitive> *out, v8::Isolate *isolate);
+  void Neon_Primitive_Boolean(v8::Local<v8::Boolean> *out, v8::Isolate *isolate, bool b);
+  bool Neon_Primitive_IsUint32(v8::Isolate *isolate, v8::Local<v8::Primitive> p);
+  bool Neon_Primitive_IsInt32(v8::Isolate *isolate, v8::Local<v8::Primitive> p);
+
+  void Neon_Object_New(v8::Local<v8::Object> *out, v8::Isolate *isolate);
+  bool Neon_Object_GetOwnPropertyNames(v8::Local<v8::Array> *out, v8::Isolate *isolate, v8::Local<v8::Object> obj);
   void *Neon_Object_GetIsolate(v8::Local<v8::Object> obj);
-  bool Neon_Object_Get_Index(v8::Local<v8::Value> *out, v8::Local<v8::Object> object, uint32_t index);
-  bool Neon_Object_Set_Index(bool *out, v8::Local<v8::Object> object, uint32_t index, v8::Local<v8::Value> val);
-  bool Neon_Object_Get_String(v8::Local<v8::Value> *out, v8::Local<v8::Object> object, const uint8_t *key, int32_t len);
-  bool Neon_Object_Set_String(bool *out, v8::Local<v8::Object> object, const uint8_t *key, int32_t len, v8::Local<v8::Value> val);
-  bool Neon_Object_Get(v8::Local<v8::Value> *out, v8::Local<v8::Object> object, v8::Local<v8::Value> key);
-  bool Neon_Object_Set(bool *out, v8::Local<v8::Object> obj, v8::Local<v8::Value> key, v8::Local<v8::Value> val);
+  bool Neon_Object_Get_Index(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Object> object, uint32_t index);
+  bool Neon_Object_Set_Index(bool *out, v8::Isolate *isolate, v8::Local<v8::Object> object, uint32_t index, v8::Local<v8::Value> val);
+  bool Neon_Object_Get_String(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Object> object, const uint8_t *key, int32_t len);
+  bool Neon_Object_Set_String(bool *out, v8::Isolate *isolate, v8::Local<v8::Object> object, const uint8_t *key, int32_t len, v8::Local<v8::Value> val);
+  bool Neon_Object_Get(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Object> object, v8::Local<v8::Value> key);
+  bool Neon_Object_Set(bool *out, v8::Isolate *isolate, v8::Local<v8::Object> obj, v8::Local<v8::Value> key, v8::Local<v8::Value> val);
 
   void Neon_Array_New(v8::Local<v8::Array> *out, v8::Isolate *isolate, uint32_t length);
-  uint32_t Neon_Array_Length(v8::Local<v8::Array> array);
+  uint32_t Neon_Array_Length(v8::Isolate *isolate, v8::Local<v8::Array> array);
 
   bool Neon_String_New(v8::Local<v8::String> *out, v8::Isolate *isolate, const uint8_t *data, int32_t len);
-  int32_t Neon_String_Utf8Length(v8::Local<v8::String> str);
-  size_t Neon_String_Data(char *out, size_t len, v8::Local<v8::Value> str);
+  int32_t Neon_String_Utf8Length(v8::Isolate *isolate, v8::Local<v8::String> str);
+  size_t Neon_String_Data(v8::Isolate *isolate, char *out, size_t len, v8::Local<v8::Value> str);
 
-  bool Neon_Convert_ToString(v8::Local<v8::String> *out, v8::Local<v8::Value> value);
-  bool Neon_Convert_ToObject(v8::Local<v8::Object> *out, v8::Local<v8::Value> *value);
+  bool Neon_Convert_ToString(v8::Local<v8::String> *out, v8::Isolate *isolate, v8::Local<v8::Value> value);
+  bool Neon_Convert_ToObject(v8::Local<v8::Object> *out, v8::Isolate *isolate, v8::Local<v8::Value> *value);
 
-  bool Neon_Buffer_New(v8::Local<v8::Object> *out, uint32_t size);
-  size_t Neon_Buffer_Data(void **base_out, v8::Local<v8::Object> obj);
+  bool Neon_Buffer_New(v8::Local<v8::Object> *out, v8::Isolate *isolate, uint32_t size);
+  size_t Neon_Buffer_Data(v8::Isolate *isolate, void **base_out, v8::Local<v8::Object> obj);
+  bool Neon_Buffer_External(v8::Local<v8::Object> *out, v8::Isolate *isolate, void *base, size_t length, void *data, Neon_DropCallback finalize);
 
   bool Neon_ArrayBuffer_New(v8::Local<v8::ArrayBuffer> *out, v8::Isolate *isolate, uint32_t size);
   bool Neon_ArrayBuffer_Uninitialized(v8::Local<v8::ArrayBuffer> *out, v8::Isolate *isolate, uint32_t size);
-  size_t Neon_ArrayBuffer_Data(void **base_out, v8::Local<v8::ArrayBuffer> buffer);
+  size_t Neon_ArrayBuffer_Data(v8::Isolate *isolate, void **base_out, v8::Local<v8::ArrayBuffer> buffer);
+  bool Neon_ArrayBuffer_External(v8::Local<v8::ArrayBuffer> *out, v8::Isolate *isolate, void *base, size_t length, void *data, Neon_DropCallback finalize);
+
+  bool Neon_TypedArray_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, typedarray_type_t type, v8::Local<v8::ArrayBuffer> buffer, size_t byte_offset, size_t length);
+  void Neon_TypedArray_Info(v8::Isolate *isolate, v8::Local<v8::Value> array, typedarray_type_t *type, v8::Local<v8::ArrayBuffer> *buffer, size_t *byte_offset, size_t *length);
+
+  bool Neon_DataView_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::ArrayBuffer> buffer, size_t byte_offset, size_t byte_length);
+  void Neon_DataView_Info(v8::Isolate *isolate, v8::Local<v8::Value> view, v8::Local<v8::ArrayBuffer> *buffer, size_t *byte_offset, size_t *byte_length);
 
   typedef void(*Neon_ChainedScopeCallback)(void *, void *, void *, void *);
   typedef void(*Neon_NestedScopeCallback)(void *, void *, void *);
   typedef void(*Neon_RootScopeCallback)(void *, void *, void *);
 
-  void Neon_Scope_Escape(v8::Local<v8::Value> *out, Nan::EscapableHandleScope *scope, v8::Local<v8::Value> value);
-  void Neon_Scope_Nested(void *out, void *closure, Neon_NestedScopeCallback callback, void *realm);
-  void Neon_Scope_Chained(void *out, void *closure, Neon_ChainedScopeCallback callback, void *parent_scope);
+  void Neon_Scope_Escape(v8::Local<v8::Value> *out, v8::Isolate *isolate, Nan::EscapableHandleScope *scope, v8::Local<v8::Value> value);
+  void Neon_Scope_Nested(void *out, v8::Isolate *isolate, void *closure, Neon_NestedScopeCallback callback, void *realm);
+  void Neon_Scope_Chained(void *out, v8::Isolate *isolate, void *closure, Neon_ChainedScopeCallback callback, void *parent_scope);
   void Neon_Scope_Enter(v8::HandleScope *scope, v8::Isolate *isolate);
-  void Neon_Scope_Exit(v8::HandleScope *scope);
+  void Neon_Scope_Exit(v8::HandleScope *scope, v8::Isolate *isolate);
   void Neon_Scope_Enter_Escapable(v8::EscapableHandleScope *scope, v8::Isolate *isolate);
-  void Neon_Scope_Exit_Escapable(v8::EscapableHandleScope *scope);
+  void Neon_Scope_Exit_Escapable(v8::EscapableHandleScope *scope, v8::Isolate *isolate);
   size_t Neon_Scope_Sizeof();
   size_t Neon_Scope_Alignof();
   size_t Neon_Scope_SizeofEscapable();
@@ -85,8 +111,6 @@ extern "C" {
   void Neon_Class_ForConstructor(v8::FunctionCallbackInfo<v8::Value> *info, v8::Local<v8::FunctionTemplate> *out);
   void Neon_Class_ForMethod(v8::FunctionCallbackInfo<v8::Value> *info, v8::Local<v8::FunctionTemplate> *out);
 
-  typedef void (*Neon_DropCallback)(void *);
-
   void *Neon_Class_GetClassMap(v8::Isolate *isolate);
   void Neon_Class_SetClassMap(v8::Isolate *isolate, void *map, Neon_DropCallback free_map);
   void *Neon_Class_CreateBase(v8::Isolate *isolate,
@@ -99,40 +123,83 @@ extern "C" {
   void *Neon_Class_GetConstructKernel(v8::Local<v8::External> wrapper);
   void *Neon_Class_GetAllocateKernel(v8::Local<v8::External> wrapper);
   bool Neon_Class_Constructor(v8::Local<v8::Function> *out, v8::Local<v8::FunctionTemplate> ft);
-  bool Neon_Class_HasInstance(void *metadata, v8::Local<v8::Value> v);
+  bool Neon_Class_HasInstance(v8::Isolate *isolate, void *metadata, v8::Local<v8::Value> v);
   bool Neon_Class_SetName(v8::Isolate *isolate, void *metadata, const char *name, uint32_t byte_length);
   size_t Neon_Class_GetName(const char **chars_out, v8::Isolate *isolate, void *metadata);
   void Neon_Class_ThrowThisError(v8::Isolate *isolate, void *metadata_pointer);
   bool Neon_Class_AddMethod(v8::Isolate *isolate, void *metadata, const char *name, uint32_t byte_length, v8::Local<v8::FunctionTemplate> method);
   bool Neon_Class_MetadataToConstructor(v8::Local<v8::Function> *out, v8::Isolate *isolate, void *metadata);
-  void *Neon_Class_GetInstanceInternals(v8::Local<v8::Object> obj);
+  void *Neon_Class_GetInstanceInternals(v8::Isolate *isolate, v8::Local<v8::Object> obj);
 
   uint32_t Neon_Module_GetVersion();
 
-  bool Neon_Tag_IsUndefined(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsNull(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsBoolean(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsNumber(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsString(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsObject(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsArray(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsFunction(v8::Local<v8::Value> val);
-  bool Neon_Tag_IsBuffer(v8::Local<v8::Value> obj);
-  bool Neon_Tag_IsArrayBuffer(v8::Local<v8::Value> obj);
-  bool Neon_Tag_IsError(v8::Local<v8::Value> val);
-
-  void Neon_Error_NewError(v8::Local<v8::Value> *out, v8::Local<v8::String> msg);
-  void Neon_Error_NewTypeError(v8::Local<v8::Value> *out, v8::Local<v8::String> msg);
-  void Neon_Error_NewRangeError(v8::Local<v8::Value> *out, v8::Local<v8::String> msg);
-  void Neon_Error_Throw(v8::Local<v8::Value> val);
-  void Neon_Error_ThrowErrorFromUtf8(const uint8_t *data, int32_t len);
-
-  bool Neon_Mem_SameHandle(v8::Local<v8::Value> v1, v8::Local<v8::Value> v2);
+  bool Neon_Tag_IsUndefined(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsNull(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsBoolean(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsNumber(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsString(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsObject(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsArray(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsFunction(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsBuffer(v8::Isolate *isolate, v8::Local<v8::Value> obj);
+  bool Neon_Tag_IsArrayBuffer(v8::Isolate *isolate, v8::Local<v8::Value> obj);
+  bool Neon_Tag_IsTypedArray(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsDataView(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsError(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsPromise(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsBigInt(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsDate(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  bool Neon_Tag_IsSymbol(v8::Isolate *isolate, v8::Local<v8::Value> val);
+
+  void Neon_Error_NewError(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::String> msg);
+  void Neon_Error_NewTypeError(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::String> msg);
+  void Neon_Error_NewRangeError(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::String> msg);
+  void Neon_Error_Throw(v8::Isolate *isolate, v8::Local<v8::Value> val);
+  void Neon_Error_ThrowErrorFromUtf8(v8::Isolate *isolate, const uint8_t *data, int32_t len);
+
+  void Neon_Symbol_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Value> description);
+  void Neon_Symbol_Description(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Value> symbol);
+
+  bool Neon_Date_New(v8::Local<v8::Value> *out, v8::Isolate *isolate, double value);
+  double Neon_Date_Value(v8::Isolate *isolate, v8::Local<v8::Value> date);
+
+  bool Neon_BigInt_NewI64(v8::Local<v8::Value> *out, v8::Isolate *isolate, int64_t value);
+  bool Neon_BigInt_NewU64(v8::Local<v8::Value> *out, v8::Isolate *isolate, uint64_t value);
+  bool Neon_BigInt_NewWords(v8::Local<v8::Value> *out, v8::Isolate *isolate, bool negative, size_t count, const uint64_t *words);
+  bool Neon_BigInt_ToI64(v8::Isolate *isolate, int64_t *out, v8::Local<v8::Value> bigint);
+  bool Neon_BigInt_ToU64(v8::Isolate *isolate, uint64_t *out, v8::Local<v8::Value> bigint);
+  size_t Neon_BigInt_ToWords(v8::Isolate *isolate, bool *negative, uint64_t *words, size_t capacity, v8::Local<v8::Value> bigint);
+
+  void *Neon_Promise_New(v8::Local<v8::Promise> *out, v8::Isolate *isolate);
+  bool Neon_Promise_Resolve(v8::Isolate *isolate, void *deferred, v8::Local<v8::Value> value);
+  bool Neon_Promise_Reject(v8::Isolate *isolate, void *deferred, v8::Local<v8::Value> value);
+  uint32_t Neon_Promise_State(v8::Local<v8::Value> *out, v8::Isolate *isolate, v8::Local<v8::Promise> promise);
+
+  typedef void (*Neon_PromiseSettleCallback)(void *, v8::Local<v8::Value> *out);
+
+  void Neon_Promise_Settle(v8::Isolate *isolate, void *deferred, Neon_PromiseSettleCallback complete, void *data);
+
+  bool Neon_Mem_SameHandle(v8::Isolate *isolate, v8::Local<v8::Value> v1, v8::Local<v8::Value> v2);
+  void *Neon_Mem_NewPersistent(v8::Isolate *isolate, v8::Local<v8::Value> value);
+  void Neon_Mem_GetPersistent(v8::Local<v8::Value> *out, v8::Isolate *isolate, void *persistent);
+  void Neon_Mem_DropPersistent(v8::Isolate *isolate, void *persistent);
+  void *Neon_Mem_NewWeak(v8::Isolate *isolate, v8::Local<v8::Value> value);
+  bool Neon_Mem_GetWeak(v8::Local<v8::Value> *out, v8::Isolate *isolate, void *weak);
+  void Neon_Mem_AddFinalizer(v8::Isolate *isolate, v8::Local<v8::Object> obj, void *data, Neon_DropCallback finalize);
 
   typedef void* (*Neon_TaskPerformCallback)(void *);
   typedef void (*Neon_TaskCompleteCallback)(void *, void *, v8::Local<v8::Value> *out);
 
-  void Neon_Task_Schedule(void *task, Neon_TaskPerformCallback perform, Neon_TaskCompleteCallback complete, v8::Local<v8::Function> callback);
+  void Neon_Task_Schedule(v8::Isolate *isolate, void *task, Neon_TaskPerformCallback perform, Neon_TaskCompleteCallback complete, v8::Local<v8::Function> callback);
+  void Neon_Task_SchedulePromise(v8::Isolate *isolate, void *task, Neon_TaskPerformCallback perform, Neon_TaskCompleteCallback complete, void *deferred);
+
+  typedef void (*Neon_EventHandlerCallback)(v8::Isolate *, v8::Local<v8::Value>, v8::Local<v8::Function>, void *);
+
+  void *Neon_EventHandler_New(v8::Isolate *isolate, v8::Local<v8::Value> self, v8::Local<v8::Function> callback);
+  bool Neon_EventHandler_Schedule(void *handler, void *rust_callback, Neon_EventHandlerCallback complete);
+  void Neon_EventHandler_Ref(v8::Isolate *isolate, void *handler);
+  void Neon_EventHandler_Unref(v8::Isolate *isolate, void *handler);
+  void Neon_EventHandler_Delete(void *handler);
 }
 
 #endif
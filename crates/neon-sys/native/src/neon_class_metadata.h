@@ -8,11 +8,10 @@
 #include "neon.h"
 #include "neon_string.h"
 
-// Currently, Node only ever has one isolate so we could get away with storing
-// Neon metadata in a global variable. But when workers land in Node, they will
-// each have a separate isolate.
+// Node runs every worker thread in a separate isolate, so we can't store Neon
+// metadata in a global variable.
 //
-// See: https://github.com/nodejs/node/pull/2133
+// See: https://nodejs.org/api/worker_threads.html
 //
 // So instead we have to store per-isolate metadata in one of the isolate's
 // extensible data slots.
@@ -32,8 +31,8 @@ namespace neon {
 
 class ClassMapHolder {
 public:
-  ClassMapHolder(void *map, Neon_DropCallback drop_map)
-    : map_(map), drop_map_(drop_map)
+  ClassMapHolder(v8::Isolate *isolate, void *map, Neon_DropCallback drop_map)
+    : isolate_(isolate), map_(map), drop_map_(drop_map)
   {
   }
 
@@ -46,7 +45,12 @@ public:
     return map_;
   }
 
+  v8::Isolate *GetIsolate() {
+    return isolate_;
+  }
+
 private:
+  v8::Isolate *isolate_;
   void *map_;
   Neon_DropCallback drop_map_;
 };
@@ -0,0 +1,141 @@
+#ifndef NEON_EVENT_H_
+#define NEON_EVENT_H_
+
+#include <mutex>
+#include <vector>
+#include <uv.h>
+#include "neon.h"
+#include "v8.h"
+
+namespace neon {
+
+// A call scheduled from another thread, which runs its Rust callback on the
+// JavaScript thread.
+struct EventCall {
+  void *rust_callback;
+  Neon_EventHandlerCallback complete;
+};
+
+class EventHandler {
+public:
+  EventHandler(v8::Isolate *isolate,
+               v8::Local<v8::Value> self,
+               v8::Local<v8::Function> callback)
+    : isolate_(isolate),
+      closing_(false)
+  {
+    // Save the receiver and the function to be called by every event.
+    self_.Reset(isolate, self);
+    callback_.Reset(isolate, callback);
+    // Save the context (aka realm) to be used when calling the function.
+    context_.Reset(isolate, isolate->GetCurrentContext());
+
+    #if NODE_MODULE_VERSION >= NODE_10_0_MODULE_VERSION
+      uv_loop_t *loop = node::GetCurrentEventLoop(isolate);
+    #else
+      uv_loop_t *loop = uv_default_loop();
+    #endif
+    async_.data = this;
+    uv_async_init(loop, &async_, dispatch_calls);
+  }
+
+  // Queues a call. This can be called from any thread.
+  void schedule(void *rust_callback, Neon_EventHandlerCallback complete) {
+    {
+      std::lock_guard<std::mutex> lock(mutex_);
+      calls_.push_back({ rust_callback, complete });
+    }
+    uv_async_send(&async_);
+  }
+
+  // Keeps the event loop alive while the handler is open, which is the default.
+  // This can only be called on the JavaScript thread.
+  void ref() {
+    uv_ref(reinterpret_cast<uv_handle_t *>(&async_));
+  }
+
+  // Lets the event loop exit while the handler is open. This can only be called
+  // on the JavaScript thread.
+  void unref() {
+    uv_unref(reinterpret_cast<uv_handle_t *>(&async_));
+  }
+
+  // Closes the handler once the calls that are already queued have run. This
+  // can be called from any thread, but libuv handles can only be closed on
+  // the thread of their loop, so the handler is freed by that thread.
+  void close() {
+    {
+      std::lock_guard<std::mutex> lock(mutex_);
+      closing_ = true;
+    }
+    uv_async_send(&async_);
+  }
+
+private:
+  static void dispatch_calls(uv_async_t *async) {
+    static_cast<EventHandler *>(async->data)->dispatch();
+  }
+
+  static void free_handler(uv_handle_t *handle) {
+    delete static_cast<EventHandler *>(handle->data);
+  }
+
+  void dispatch() {
+    std::vector<EventCall> calls;
+    bool closing;
+
+    {
+      std::lock_guard<std::mutex> lock(mutex_);
+      calls.swap(calls_);
+      closing = closing_;
+    }
+
+    // Ensure that we have all the proper scopes installed on the C++ stack before
+    // running the callbacks, and use the context (i.e. realm) we saved with the handler.
+    v8::Isolate::Scope isolate_scope(isolate_);
+    v8::HandleScope handle_scope(isolate_);
+    v8::Local<v8::Context> context = v8::Local<v8::Context>::New(isolate_, context_);
+    v8::Context::Scope context_scope(context);
+
+    {
+      #if NODE_MODULE_VERSION >= NODE_10_0_MODULE_VERSION
+        node::CallbackScope callback_scope(isolate_, context->Global(), node::async_context{0, 0});
+      #endif
+
+      v8::Local<v8::Value> self = v8::Local<v8::Value>::New(isolate_, self_);
+      v8::Local<v8::Function> callback = v8::Local<v8::Function>::New(isolate_, callback_);
+
+      for (EventCall &call : calls) {
+        v8::TryCatch trycatch(isolate_);
+        call.complete(isolate_, self, callback, call.rust_callback);
+        if (trycatch.HasCaught()) {
+          Nan::FatalException(trycatch);
+        }
+      }
+    }
+
+    #if NODE_MODULE_VERSION < NODE_10_0_MODULE_VERSION
+      isolate_->RunMicrotasks();
+    #endif
+
+    if (closing) {
+      self_.Reset();
+      callback_.Reset();
+      context_.Reset();
+      uv_close(reinterpret_cast<uv_handle_t *>(&async_), free_handler);
+    }
+  }
+
+  v8::Isolate *isolate_;
+  uv_async_t async_;
+  std::mutex mutex_;
+  std::vector<EventCall> calls_;
+  bool closing_;
+  v8::Persistent<v8::Value> self_;
+  v8::Persistent<v8::Function> callback_;
+  v8::Persistent<v8::Context> context_;
+};
+
+}
+
+#endif
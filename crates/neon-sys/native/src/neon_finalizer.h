@@ -0,0 +1,58 @@
+#ifndef NEON_FINALIZER_H_
+#define NEON_FINALIZER_H_
+
+#include "neon.h"
+#include "v8.h"
+
+namespace neon {
+
+// Runs a Rust callback once an object has been garbage collected, the same way
+// class instances drop their internals.
+class Finalizer {
+public:
+  Finalizer(v8::Isolate *isolate, v8::Local<v8::Object> object, void *data, Neon_DropCallback finalize)
+    : data_(data), finalize_(finalize)
+  {
+    object_.Reset(isolate, object);
+    object_.SetWeak(this, Finalize, v8::WeakCallbackType::kParameter);
+  }
+
+private:
+  ~Finalizer() {
+    object_.Reset();
+    finalize_(data_);
+    data_ = nullptr;
+  }
+
+  static void Finalize(const v8::WeakCallbackInfo<Finalizer>& data) {
+    Finalizer *finalizer = data.GetParameter();
+    delete finalizer;
+  }
+
+  v8::Global<v8::Object> object_;
+  void *data_;
+  Neon_DropCallback finalize_;
+};
+
+// The Rust value that owns the memory of an external `ArrayBuffer` or `Buffer`. The engine
+// may release the memory on any thread.
+struct External {
+  void *data;
+  Neon_DropCallback finalize;
+
+  static void Free(char *base, void *hint) {
+    External *external = static_cast<External *>(hint);
+    external->finalize(external->data);
+    delete external;
+  }
+
+#if V8_MAJOR_VERSION >= 8
+  static void Delete(void *base, size_t length, void *deleter_data) {
+    Free(static_cast<char *>(base), deleter_data);
+  }
+#endif
+};
+
+}
+
+#endif
@@ -0,0 +1,28 @@
+#ifndef NEON_PROMISE_H_
+#define NEON_PROMISE_H_
+
+#include "v8.h"
+
+namespace neon {
+
+// A deferred is the resolver of a promise, kept alive in a persistent handle
+// until the promise is settled.
+typedef v8::Persistent<v8::Promise::Resolver> Deferred;
+
+void *new_deferred(v8::Isolate *isolate, v8::Local<v8::Promise::Resolver> resolver) {
+  return new Deferred(isolate, resolver);
+}
+
+// Takes back the resolver of a deferred and frees the deferred, which can only
+// be settled once.
+v8::Local<v8::Promise::Resolver> take_deferred(v8::Isolate *isolate, void *deferred) {
+  Deferred *persistent = static_cast<Deferred *>(deferred);
+  v8::Local<v8::Promise::Resolver> resolver = v8::Local<v8::Promise::Resolver>::New(isolate, *persistent);
+  persistent->Reset();
+  delete persistent;
+  return resolver;
+}
+
+}
+
+#endif
@@ -3,6 +3,7 @@
 
 #include <uv.h>
 #include "neon.h"
+#include "neon_promise.h"
 #include "v8.h"
 
 namespace neon {
@@ -17,7 +18,8 @@ public:
     : isolate_(isolate),
       rust_task_(rust_task),
       perform_(perform),
-      complete_(complete)
+      complete_(complete),
+      deferred_(nullptr)
   {
     request_.data = this;
     result_ = nullptr;
@@ -27,6 +29,23 @@ public:
     context_.Reset(isolate, isolate->GetCurrentContext());
   }
 
+  Task(v8::Isolate *isolate,
+       void *rust_task,
+       Neon_TaskPerformCallback perform,
+       Neon_TaskCompleteCallback complete,
+       void *deferred)
+    : isolate_(isolate),
+      rust_task_(rust_task),
+      perform_(perform),
+      complete_(complete),
+      deferred_(deferred)
+  {
+    request_.data = this;
+    result_ = nullptr;
+    // Save the context (aka realm) to be used when settling the promise.
+    context_.Reset(isolate, isolate->GetCurrentContext());
+  }
+
   void execute() {
     result_ = perform_(rust_task_);
   }
@@ -44,6 +63,8 @@ public:
     argv[0] = v8::Null(isolate_);
     argv[1] = v8::Undefined(isolate_);
 
+    bool failed = false;
+
     {
       v8::TryCatch trycatch(isolate_);
 
@@ -52,15 +73,20 @@ public:
       complete_(rust_task_, result_, &completion);
 
       if (trycatch.HasCaught()) {
+        failed = true;
         argv[0] = trycatch.Exception();
       } else {
         argv[1] = completion;
       }
     }
 
-    v8::Local<v8::Function> callback = v8::Local<v8::Function>::New(isolate_, callback_);
-    node::MakeCallback(isolate_, context->Global(), callback, 2, argv);
-    callback_.Reset();
+    if (deferred_ != nullptr) {
+      settle(context, failed, failed ? argv[0] : argv[1]);
+    } else {
+      v8::Local<v8::Function> callback = v8::Local<v8::Function>::New(isolate_, callback_);
+      node::MakeCallback(isolate_, context->Global(), callback, 2, argv);
+      callback_.Reset();
+    }
     context_.Reset();
   }
 
@@ -71,10 +97,32 @@ public:
   uv_work_t request_;
 
 private:
+  void settle(v8::Local<v8::Context> context, bool failed, v8::Local<v8::Value> value) {
+    v8::Local<v8::Promise::Resolver> resolver = take_deferred(isolate_, deferred_);
+    deferred_ = nullptr;
+
+    // Unlike `MakeCallback`, settling the promise doesn't run the microtask queue,
+    // so the callback scope is needed for the promise's reactions to run right away.
+    #if NODE_MODULE_VERSION >= NODE_10_0_MODULE_VERSION
+      node::CallbackScope callback_scope(isolate_, context->Global(), node::async_context{0, 0});
+    #endif
+
+    if (failed) {
+      resolver->Reject(context, value).FromMaybe(false);
+    } else {
+      resolver->Resolve(context, value).FromMaybe(false);
+    }
+
+    #if NODE_MODULE_VERSION < NODE_10_0_MODULE_VERSION
+      isolate_->RunMicrotasks();
+    #endif
+  }
+
   v8::Isolate *isolate_;
   void *rust_task_;
   Neon_TaskPerformCallback perform_;
   Neon_TaskCompleteCallback complete_;
+  void *deferred_;
   void *result_;
   v8::Persistent<v8::Function> callback_;
   v8::Persistent<v8::Context> context_;
@@ -91,8 +139,15 @@ void complete_task(uv_work_t *request) {
   delete task;
 }
 
-void queue_task(Task *task) {
-  uv_queue_work(uv_default_loop(),
+void queue_task(v8::Isolate *isolate, Task *task) {
+  // Each worker thread runs its own event loop, so the task has to be queued
+  // on the loop of the thread that scheduled it.
+  #if NODE_MODULE_VERSION >= NODE_10_0_MODULE_VERSION
+    uv_loop_t *loop = node::GetCurrentEventLoop(isolate);
+  #else
+    uv_loop_t *loop = uv_default_loop();
+  #endif
+  uv_queue_work(loop,
                 &task->request_,
                 execute_task,
                 (uv_after_work_cb)complete_task);
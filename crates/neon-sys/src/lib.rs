@@ -21,6 +21,12 @@ pub type FunctionCallbackInfo = c_void;
 /// Represents an instance of the V8 runtime.
 pub type Isolate = c_void;
 
+/// The pending resolution of a V8 `Promise`, which can be settled exactly once.
+pub type Deferred = c_void;
+
+/// A V8 `Persistent` handle, which keeps its value alive until it is dropped.
+pub type Persistent = c_void;
+
 const HANDLE_SCOPE_SIZE: usize = 24;
 
 /// A V8 `HandleScope`.
@@ -57,6 +63,23 @@ impl EscapableHandleScope {
     pub fn new() -> EscapableHandleScope { unsafe { mem::zeroed() } }
 }
 
+/// The element type of a typed array, numbered like N-API's `napi_typedarray_type`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TypedArrayType {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64
+}
+
 #[repr(C)]
 pub struct CCallback {
     pub static_callback: *mut c_void,
@@ -78,14 +101,22 @@ pub struct InheritedHandleScope;
 extern "C" {
 
     pub fn Neon_Array_New(out: &mut Local, isolate: *mut Isolate, length: u32);
-    pub fn Neon_Array_Length(array: Local) -> u32;
+    pub fn Neon_Array_Length(isolate: *mut Isolate, array: Local) -> u32;
 
     pub fn Neon_ArrayBuffer_New(out: &mut Local, isolate: *mut c_void, size: u32) -> bool;
-    pub fn Neon_ArrayBuffer_Data<'a, 'b>(base_out: &'a mut *mut c_void, obj: Local) -> usize;
+    pub fn Neon_ArrayBuffer_Data<'a, 'b>(isolate: *mut Isolate, base_out: &'a mut *mut c_void, obj: Local) -> usize;
+    pub fn Neon_ArrayBuffer_External(out: &mut Local, isolate: *mut Isolate, base: *mut c_void, length: usize, data: *mut c_void, finalize: extern "C" fn(*mut c_void)) -> bool;
+
+    pub fn Neon_TypedArray_New(out: &mut Local, isolate: *mut Isolate, ty: TypedArrayType, buffer: Local, byte_offset: usize, length: usize) -> bool;
+    pub fn Neon_TypedArray_Info(isolate: *mut Isolate, array: Local, ty: &mut TypedArrayType, buffer: &mut Local, byte_offset: &mut usize, length: &mut usize);
 
-    pub fn Neon_Buffer_New(out: &mut Local, size: u32) -> bool;
-    pub fn Neon_Buffer_Uninitialized(out: &mut Local, size: u32) -> bool;
-    pub fn Neon_Buffer_Data<'a, 'b>(base_out: &'a mut *mut c_void, obj: Local) -> usize;
+    pub fn Neon_DataView_New(out: &mut Local, isolate: *mut Isolate, buffer: Local, byte_offset: usize, byte_length: usize) -> bool;
+    pub fn Neon_DataView_Info(isolate: *mut Isolate, view: Local, buffer: &mut Local, byte_offset: &mut usize, byte_length: &mut usize);
+
+    pub fn Neon_Buffer_New(out: &mut Local, isolate: *mut Isolate, size: u32) -> bool;
+    pub fn Neon_Buffer_Uninitialized(out: &mut Local, isolate: *mut Isolate, size: u32) -> bool;
+    pub fn Neon_Buffer_Data<'a, 'b>(isolate: *mut Isolate, base_out: &'a mut *mut c_void, obj: Local) -> usize;
+    pub fn Neon_Buffer_External(out: &mut Local, isolate: *mut Isolate, base: *mut c_void, length: usize, data: *mut c_void, finalize: extern "C" fn(*mut c_void)) -> bool;
 
     pub fn Neon_Call_SetReturn(info: &FunctionCallbackInfo, value: Local);
     pub fn Neon_Call_GetIsolate(info: &FunctionCallbackInfo) -> *mut Isolate;
@@ -113,17 +144,17 @@ extern "C" {
     pub fn Neon_Class_GetConstructKernel(obj: Local) -> *mut c_void;
     pub fn Neon_Class_GetCallKernel(obj: Local) -> *mut c_void;
     pub fn Neon_Class_Constructor(out: &mut Local, ft: Local) -> bool;
-    pub fn Neon_Class_HasInstance(metadata: *mut c_void, v: Local) -> bool;
-    pub fn Neon_Class_GetInstanceInternals(obj: Local) -> *mut c_void;
+    pub fn Neon_Class_HasInstance(isolate: *mut Isolate, metadata: *mut c_void, v: Local) -> bool;
+    pub fn Neon_Class_GetInstanceInternals(isolate: *mut Isolate, obj: Local) -> *mut c_void;
 
-    pub fn Neon_Convert_ToObject(out: &mut Local, value: &Local) -> bool;
-    pub fn Neon_Convert_ToString(out: &mut Local, value: Local) -> bool;
+    pub fn Neon_Convert_ToObject(out: &mut Local, isolate: *mut Isolate, value: &Local) -> bool;
+    pub fn Neon_Convert_ToString(out: &mut Local, isolate: *mut Isolate, value: Local) -> bool;
 
-    pub fn Neon_Error_Throw(val: Local);
-    pub fn Neon_Error_NewError(out: &mut Local, msg: Local);
-    pub fn Neon_Error_NewTypeError(out: &mut Local, msg: Local);
-    pub fn Neon_Error_NewRangeError(out: &mut Local, msg: Local);
-    pub fn Neon_Error_ThrowErrorFromUtf8(msg: *const u8, len: i32);
+    pub fn Neon_Error_Throw(isolate: *mut Isolate, val: Local);
+    pub fn Neon_Error_NewError(out: &mut Local, isolate: *mut Isolate, msg: Local);
+    pub fn Neon_Error_NewTypeError(out: &mut Local, isolate: *mut Isolate, msg: Local);
+    pub fn Neon_Error_NewRangeError(out: &mut Local, isolate: *mut Isolate, msg: Local);
+    pub fn Neon_Error_ThrowErrorFromUtf8(isolate: *mut Isolate, msg: *const u8, len: i32);
 
     pub fn Neon_Fun_New(out: &mut Local, isolate: *mut c_void, callback: CCallback) -> bool;
     pub fn Neon_Fun_Template_New(out: &mut Local, isolate: *mut c_void, callback: CCallback) -> bool;
@@ -131,40 +162,46 @@ extern "C" {
     pub fn Neon_Fun_Call(out: &mut Local, isolate: *mut c_void, fun: Local, this: Local, argc: i32, argv: *mut c_void) -> bool;
     pub fn Neon_Fun_Construct(out: &mut Local, isolate: *mut c_void, fun: Local, argc: i32, argv: *mut c_void) -> bool;
 
-    pub fn Neon_Mem_SameHandle(h1: Local, h2: Local) -> bool;
+    pub fn Neon_Mem_SameHandle(isolate: *mut Isolate, h1: Local, h2: Local) -> bool;
+    pub fn Neon_Mem_NewPersistent(isolate: *mut Isolate, value: Local) -> *mut Persistent;
+    pub fn Neon_Mem_GetPersistent(out: &mut Local, isolate: *mut Isolate, persistent: *mut Persistent);
+    pub fn Neon_Mem_DropPersistent(isolate: *mut Isolate, persistent: *mut Persistent);
+    pub fn Neon_Mem_NewWeak(isolate: *mut Isolate, value: Local) -> *mut Persistent;
+    pub fn Neon_Mem_GetWeak(out: &mut Local, isolate: *mut Isolate, weak: *mut Persistent) -> bool;
+    pub fn Neon_Mem_AddFinalizer(isolate: *mut Isolate, obj: Local, data: *mut c_void, finalize: extern "C" fn(*mut c_void));
 
     pub fn Neon_Module_ExecKernel(kernel: *mut c_void, callback: extern fn(*mut c_void, *mut c_void, *mut c_void, *mut c_void), exports: Local, scope: *mut c_void, vm: *mut c_void);
     pub fn Neon_Module_ExecCallback(callback: CCallback, exports: Local, vm: *mut c_void);
     pub fn Neon_Module_GetVersion() -> i32;
 
-    pub fn Neon_Object_New(out: &mut Local);
-    pub fn Neon_Object_GetOwnPropertyNames(out: &mut Local, object: Local) -> bool;
+    pub fn Neon_Object_New(out: &mut Local, isolate: *mut Isolate);
+    pub fn Neon_Object_GetOwnPropertyNames(out: &mut Local, isolate: *mut Isolate, object: Local) -> bool;
     pub fn Neon_Object_GetIsolate(obj: Local) -> *mut Isolate;
-    pub fn Neon_Object_Get_Index(out: &mut Local, object: Local, index: u32) -> bool;
-    pub fn Neon_Object_Set_Index(out: &mut bool, object: Local, index: u32, val: Local) -> bool;
-    pub fn Neon_Object_Get_String(out: &mut Local, object: Local, key: *const u8, len: i32) -> bool;
-    pub fn Neon_Object_Set_String(out: &mut bool, object: Local, key: *const u8, len: i32, val: Local) -> bool;
-    pub fn Neon_Object_Get(out: &mut Local, object: Local, key: Local) -> bool;
-    pub fn Neon_Object_Set(out: &mut bool, object: Local, key: Local, val: Local) -> bool;
-
-    pub fn Neon_Primitive_Undefined(out: &mut Local);
-    pub fn Neon_Primitive_Null(out: &mut Local);
-    pub fn Neon_Primitive_Boolean(out: &mut Local, b: bool);
-    pub fn Neon_Primitive_BooleanValue(p: Local) -> bool;
+    pub fn Neon_Object_Get_Index(out: &mut Local, isolate: *mut Isolate, object: Local, index: u32) -> bool;
+    pub fn Neon_Object_Set_Index(out: &mut bool, isolate: *mut Isolate, object: Local, index: u32, val: Local) -> bool;
+    pub fn Neon_Object_Get_String(out: &mut Local, isolate: *mut Isolate, object: Local, key: *const u8, len: i32) -> bool;
+    pub fn Neon_Object_Set_String(out: &mut bool, isolate: *mut Isolate, object: Local, key: *const u8, len: i32, val: Local) -> bool;
+    pub fn Neon_Object_Get(out: &mut Local, isolate: *mut Isolate, object: Local, key: Local) -> bool;
+    pub fn Neon_Object_Set(out: &mut bool, isolate: *mut Isolate, object: Local, key: Local, val: Local) -> bool;
+
+    pub fn Neon_Primitive_Undefined(out: &mut Local, isolate: *mut Isolate);
+    pub fn Neon_Primitive_Null(out: &mut Local, isolate: *mut Isolate);
+    pub fn Neon_Primitive_Boolean(out: &mut Local, isolate: *mut Isolate, b: bool);
+    pub fn Neon_Primitive_BooleanValue(isolate: *mut Isolate, p: Local) -> bool;
     pub fn Neon_Primitive_Integer(out: &mut Local, isolate: *mut Isolate, x: i32);
-    pub fn Neon_Primitive_IsUint32(p: Local) -> bool;
-    pub fn Neon_Primitive_IsInt32(p: Local) -> bool;
-    pub fn Neon_Primitive_IntegerValue(p: Local) -> i64;
+    pub fn Neon_Primitive_IsUint32(isolate: *mut Isolate, p: Local) -> bool;
+    pub fn Neon_Primitive_IsInt32(isolate: *mut Isolate, p: Local) -> bool;
+    pub fn Neon_Primitive_IntegerValue(isolate: *mut Isolate, p: Local) -> i64;
     pub fn Neon_Primitive_Number(out: &mut Local, isolate: *mut Isolate, v: f64);
-    pub fn Neon_Primitive_NumberValue(p: Local) -> f64;
+    pub fn Neon_Primitive_NumberValue(isolate: *mut Isolate, p: Local) -> f64;
 
-    pub fn Neon_Scope_Escape(out: &mut Local, scope: *mut EscapableHandleScope, value: Local);
-    pub fn Neon_Scope_Chained(out: *mut c_void, closure: *mut c_void, callback: extern fn(&mut c_void, *mut c_void, *mut c_void, *mut c_void), parent_scope: *mut c_void);
-    pub fn Neon_Scope_Nested(out: *mut c_void, closure: *mut c_void, callback: extern fn(&mut c_void, *mut c_void, *mut c_void), realm: *mut c_void);
+    pub fn Neon_Scope_Escape(out: &mut Local, isolate: *mut Isolate, scope: *mut EscapableHandleScope, value: Local);
+    pub fn Neon_Scope_Chained(out: *mut c_void, isolate: *mut Isolate, closure: *mut c_void, callback: extern fn(&mut c_void, *mut c_void, *mut c_void, *mut c_void), parent_scope: *mut c_void);
+    pub fn Neon_Scope_Nested(out: *mut c_void, isolate: *mut Isolate, closure: *mut c_void, callback: extern fn(&mut c_void, *mut c_void, *mut c_void), realm: *mut c_void);
     pub fn Neon_Scope_Enter(scope: &mut HandleScope, isolate: *mut c_void);
-    pub fn Neon_Scope_Exit(scope: &mut HandleScope);
+    pub fn Neon_Scope_Exit(scope: &mut HandleScope, isolate: *mut Isolate);
     pub fn Neon_Scope_Enter_Escapable(scope: &mut EscapableHandleScope, isolate: *mut c_void);
-    pub fn Neon_Scope_Exit_Escapable(scope: &mut EscapableHandleScope);
+    pub fn Neon_Scope_Exit_Escapable(scope: &mut EscapableHandleScope, isolate: *mut Isolate);
     pub fn Neon_Scope_Sizeof() -> usize;
     pub fn Neon_Scope_Alignof() -> usize;
     pub fn Neon_Scope_SizeofEscapable() -> usize;
@@ -172,24 +209,67 @@ extern "C" {
     pub fn Neon_Scope_GetGlobal(isolate: *mut c_void, out: &mut Local);
 
     pub fn Neon_String_New(out: &mut Local, isolate: *mut Isolate, data: *const u8, len: i32) -> bool;
-    pub fn Neon_String_Utf8Length(str: Local) -> isize;
-    pub fn Neon_String_Data(out: *mut u8, len: isize, str: Local) -> isize;
-
-    pub fn Neon_Tag_IsUndefined(val: Local) -> bool;
-    pub fn Neon_Tag_IsNull(val: Local) -> bool;
-    pub fn Neon_Tag_IsNumber(val: Local) -> bool;
-    pub fn Neon_Tag_IsBoolean(val: Local) -> bool;
-    pub fn Neon_Tag_IsString(val: Local) -> bool;
-    pub fn Neon_Tag_IsObject(val: Local) -> bool;
-    pub fn Neon_Tag_IsArray(val: Local) -> bool;
-    pub fn Neon_Tag_IsFunction(val: Local) -> bool;
-    pub fn Neon_Tag_IsError(val: Local) -> bool;
-    pub fn Neon_Tag_IsBuffer(obj: Local) -> bool;
-    pub fn Neon_Tag_IsArrayBuffer(obj: Local) -> bool;
-
-    pub fn Neon_Task_Schedule(task: *mut c_void,
+    pub fn Neon_String_Utf8Length(isolate: *mut Isolate, str: Local) -> isize;
+    pub fn Neon_String_Data(isolate: *mut Isolate, out: *mut u8, len: isize, str: Local) -> isize;
+
+    pub fn Neon_Tag_IsUndefined(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsNull(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsNumber(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsBoolean(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsString(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsObject(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsArray(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsFunction(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsError(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsBuffer(isolate: *mut Isolate, obj: Local) -> bool;
+    pub fn Neon_Tag_IsArrayBuffer(isolate: *mut Isolate, obj: Local) -> bool;
+    pub fn Neon_Tag_IsTypedArray(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsDataView(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsPromise(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsBigInt(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsDate(isolate: *mut Isolate, val: Local) -> bool;
+    pub fn Neon_Tag_IsSymbol(isolate: *mut Isolate, val: Local) -> bool;
+
+    pub fn Neon_Symbol_New(out: &mut Local, isolate: *mut Isolate, description: Local);
+    pub fn Neon_Symbol_Description(out: &mut Local, isolate: *mut Isolate, symbol: Local);
+
+    pub fn Neon_Date_New(out: &mut Local, isolate: *mut Isolate, value: f64) -> bool;
+    pub fn Neon_Date_Value(isolate: *mut Isolate, date: Local) -> f64;
+
+    pub fn Neon_BigInt_NewI64(out: &mut Local, isolate: *mut Isolate, value: i64) -> bool;
+    pub fn Neon_BigInt_NewU64(out: &mut Local, isolate: *mut Isolate, value: u64) -> bool;
+    pub fn Neon_BigInt_NewWords(out: &mut Local, isolate: *mut Isolate, negative: bool, count: usize, words: *const u64) -> bool;
+    pub fn Neon_BigInt_ToI64(isolate: *mut Isolate, out: &mut i64, bigint: Local) -> bool;
+    pub fn Neon_BigInt_ToU64(isolate: *mut Isolate, out: &mut u64, bigint: Local) -> bool;
+    pub fn Neon_BigInt_ToWords(isolate: *mut Isolate, negative: &mut bool, words: *mut u64, capacity: usize, bigint: Local) -> usize;
+
+    pub fn Neon_Promise_New(out: &mut Local, isolate: *mut Isolate) -> *mut Deferred;
+    pub fn Neon_Promise_Resolve(isolate: *mut Isolate, deferred: *mut Deferred, value: Local) -> bool;
+    pub fn Neon_Promise_Reject(isolate: *mut Isolate, deferred: *mut Deferred, value: Local) -> bool;
+    pub fn Neon_Promise_State(out: &mut Local, isolate: *mut Isolate, promise: Local) -> u32;
+    pub fn Neon_Promise_Settle(isolate: *mut Isolate,
+                               deferred: *mut Deferred,
+                               complete: unsafe extern fn(*mut c_void, &mut Local),
+                               data: *mut c_void);
+
+    pub fn Neon_Task_Schedule(isolate: *mut Isolate,
+                              task: *mut c_void,
                               perform: unsafe extern fn(*mut c_void) -> *mut c_void,
                               complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
                               callback: Local);
 
+    pub fn Neon_Task_SchedulePromise(isolate: *mut Isolate,
+                                     task: *mut c_void,
+                                     perform: unsafe extern fn(*mut c_void) -> *mut c_void,
+                                     complete: unsafe extern fn(*mut c_void, *mut c_void, &mut Local),
+                                     deferred: *mut Deferred);
+
+    pub fn Neon_EventHandler_New(isolate: *mut Isolate, this: Local, callback: Local) -> *mut c_void;
+    pub fn Neon_EventHandler_Schedule(handler: *mut c_void,
+                                      rust_callback: *mut c_void,
+                                      complete: unsafe extern fn(*mut Isolate, Local, Local, *mut c_void)) -> bool;
+    pub fn Neon_EventHandler_Ref(isolate: *mut Isolate, handler: *mut c_void);
+    pub fn Neon_EventHandler_Unref(isolate: *mut Isolate, handler: *mut c_void);
+    pub fn Neon_EventHandler_Delete(handler: *mut c_void);
+
 }
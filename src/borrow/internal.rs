@@ -1,11 +1,14 @@
 use std;
 use std::os::raw::c_void;
-use std::collections::HashSet;
+use std::mem;
 use borrow::LoanError;
 
 pub unsafe trait Pointer {
     unsafe fn as_ptr(&self) -> *const c_void;
     unsafe fn as_mut(&mut self) -> *mut c_void;
+
+    /// The number of bytes of memory the pointer grants access to.
+    fn size(&self) -> usize;
 }
 
 unsafe impl<T> Pointer for *mut T {
@@ -16,7 +19,12 @@ unsafe impl<T> Pointer for *mut T {
     unsafe fn as_mut(&mut self) -> *mut c_void {
         *self as *mut c_void
     }
+
+    fn size(&self) -> usize {
+        mem::size_of::<T>()
+    }
 }
+
 unsafe impl<'a, T> Pointer for &'a mut T {
     unsafe fn as_ptr(&self) -> *const c_void {
         let r: &T = &**self;
@@ -27,48 +35,79 @@ unsafe impl<'a, T> Pointer for &'a mut T {
         let r: &mut T = &mut **self;
         std::mem::transmute(r)
     }
+
+    fn size(&self) -> usize {
+        mem::size_of::<T>()
+    }
+}
+
+/// A range of addresses that has been loaned out.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Loan {
+    start: usize,
+    end: usize
+}
+
+impl Loan {
+    fn new(p: *const c_void, size: usize) -> Self {
+        let start = p as usize;
+        Loan { start, end: start.saturating_add(size) }
+    }
+
+    /// Two loans conflict if their address ranges overlap. Loans that start at the same
+    /// address always conflict, so that loans of empty memory are still tracked.
+    fn conflicts(&self, other: &Loan) -> bool {
+        self.start == other.start || (self.start < other.end && other.start < self.end)
+    }
 }
 
+/// The outstanding loans of a `Lock`. Loans are tracked as address ranges rather than
+/// objects, since different JS values can share memory, such as a `Buffer` and the
+/// `ArrayBuffer` behind it, or two typed arrays viewing the same `ArrayBuffer`.
 pub struct Ledger {
-    immutable_loans: HashSet<*const c_void>,
-    mutable_loans: HashSet<*const c_void>
+    immutable_loans: Vec<Loan>,
+    mutable_loans: Vec<Loan>
 }
 
 impl Ledger {
     pub fn new() -> Self {
         Ledger {
-            immutable_loans: HashSet::new(),
-            mutable_loans: HashSet::new()
+            immutable_loans: Vec::new(),
+            mutable_loans: Vec::new()
         }
     }
 
-    pub fn try_borrow<T>(&mut self, p: *const T) -> Result<(), LoanError> {
-        let p = p as *const c_void;
-        if self.mutable_loans.contains(&p) {
-            return Err(LoanError::Mutating(p));
+    pub fn try_borrow(&mut self, p: *const c_void, size: usize) -> Result<(), LoanError> {
+        let loan = Loan::new(p, size);
+        if let Some(other) = self.mutable_loans.iter().find(|other| loan.conflicts(other)) {
+            return Err(LoanError::Mutating(other.start as *const c_void));
         }
-        self.immutable_loans.insert(p);
+        self.immutable_loans.push(loan);
         Ok(())
     }
 
-    pub fn settle<T>(&mut self, p: *const T) {
-        let p = p as *const c_void;
-        self.immutable_loans.remove(&p);
+    pub fn settle(&mut self, p: *const c_void, size: usize) {
+        Ledger::remove(&mut self.immutable_loans, Loan::new(p, size));
     }
 
-    pub fn try_borrow_mut<T>(&mut self, p: *mut T) -> Result<(), LoanError> {
-        let p = p as *const c_void;
-        if self.mutable_loans.contains(&p) {
-            return Err(LoanError::Mutating(p));
-        } else if self.immutable_loans.contains(&p) {
-            return Err(LoanError::Frozen(p));
+    pub fn try_borrow_mut(&mut self, p: *mut c_void, size: usize) -> Result<(), LoanError> {
+        let loan = Loan::new(p, size);
+        if let Some(other) = self.mutable_loans.iter().find(|other| loan.conflicts(other)) {
+            return Err(LoanError::Mutating(other.start as *const c_void));
+        } else if let Some(other) = self.immutable_loans.iter().find(|other| loan.conflicts(other)) {
+            return Err(LoanError::Frozen(other.start as *const c_void));
         }
-        self.mutable_loans.insert(p);
+        self.mutable_loans.push(loan);
         Ok(())
     }
 
-    pub fn settle_mut<T>(&mut self, p: *mut T) {
-        let p = p as *const c_void;
-        self.mutable_loans.remove(&p);
+    pub fn settle_mut(&mut self, p: *mut c_void, size: usize) {
+        Ledger::remove(&mut self.mutable_loans, Loan::new(p, size));
+    }
+
+    fn remove(loans: &mut Vec<Loan>, loan: Loan) {
+        if let Some(i) = loans.iter().position(|other| *other == loan) {
+            loans.swap_remove(i);
+        }
     }
 }
@@ -55,10 +55,12 @@ pub trait BorrowMut: Borrow {
 /// An error produced by a failed loan in the `Borrow` or `BorrowMut` traits.
 pub enum LoanError {
 
-    /// Indicates that there is already an outstanding mutable loan for the object at this address.
+    /// Indicates that there is already an outstanding mutable loan for memory overlapping the
+    /// object, which starts at this address.
     Mutating(*const c_void),
 
-    /// Indicates that there is already an outstanding immutable loan for the object at this address.
+    /// Indicates that there is already an outstanding immutable loan for memory overlapping the
+    /// object, which starts at this address.
     Frozen(*const c_void)
 
 }
@@ -85,7 +87,7 @@ pub struct Ref<'a, T: Pointer> {
 impl<'a, T: Pointer> Ref<'a, T> {
     pub(crate) unsafe fn new(lock: &'a Lock<'a>, pointer: T) -> Result<Self, LoanError> {
         let mut ledger = lock.ledger.borrow_mut();
-        ledger.try_borrow(pointer.as_ptr())?;
+        ledger.try_borrow(pointer.as_ptr(), pointer.size())?;
         Ok(Ref { pointer, lock })
     }
 }
@@ -93,7 +95,7 @@ impl<'a, T: Pointer> Ref<'a, T> {
 impl<'a, T: Pointer> Drop for Ref<'a, T> {
     fn drop(&mut self) {
         let mut ledger = self.lock.ledger.borrow_mut();
-        ledger.settle(unsafe { self.pointer.as_ptr() });
+        ledger.settle(unsafe { self.pointer.as_ptr() }, self.pointer.size());
     }
 }
 
@@ -114,7 +116,8 @@ pub struct RefMut<'a, T: Pointer> {
 impl<'a, T: Pointer> RefMut<'a, T> {
     pub(crate) unsafe fn new(lock: &'a Lock<'a>, mut pointer: T) -> Result<Self, LoanError> {
         let mut ledger = lock.ledger.borrow_mut();
-        ledger.try_borrow_mut(pointer.as_mut())?;
+        let size = pointer.size();
+        ledger.try_borrow_mut(pointer.as_mut(), size)?;
         Ok(RefMut { pointer, lock })
     }
 }
@@ -122,7 +125,8 @@ impl<'a, T: Pointer> RefMut<'a, T> {
 impl<'a, T: Pointer> Drop for RefMut<'a, T> {
     fn drop(&mut self) {
         let mut ledger = self.lock.ledger.borrow_mut();
-        ledger.settle_mut(unsafe { self.pointer.as_mut() });
+        let size = self.pointer.size();
+        ledger.settle_mut(unsafe { self.pointer.as_mut() }, size);
     }
 }
 
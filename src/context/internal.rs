@@ -1,52 +1,116 @@
 use std;
+use std::any::{Any, TypeId};
 use std::cell::Cell;
+use std::collections::HashMap;
 use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
 use neon_runtime;
 use neon_runtime::raw;
 use neon_runtime::scope::Root;
 use types::JsObject;
-use handle::Handle;
+use handle::{Handle, Managed};
 use object::class::ClassMap;
 use result::NeonResult;
+#[cfg(feature = "macros")]
+use macro_internal::{EXPORTS, MAINS};
+use event::SharedHandler;
 use super::ModuleContext;
 
+/// The environment that handles and contexts belong to. Each thread running JavaScript,
+/// such as the main thread or a worker thread, has its own environment.
 #[repr(C)]
-#[derive(Clone, Copy)]
-pub struct Isolate(*mut raw::Isolate);
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Env(raw::Env);
 
-extern "C" fn drop_class_map(map: Box<ClassMap>) {
-    std::mem::drop(map);
+/// The state of a module that belongs to a single environment. Every environment that loads
+/// the module gets its own, and it is dropped when the environment is torn down.
+pub(crate) struct InstanceData {
+    classes: ClassMap,
+    data: HashMap<TypeId, Box<dyn Any>>,
+    cleanup_hooks: Vec<Box<dyn FnOnce()>>
 }
 
-impl Isolate {
-    pub(crate) fn to_raw(self) -> *mut raw::Isolate {
-        let Isolate(ptr) = self;
+impl InstanceData {
+    fn new() -> Self {
+        InstanceData {
+            classes: ClassMap::new(),
+            data: HashMap::new(),
+            cleanup_hooks: Vec::new()
+        }
+    }
+
+    pub(crate) fn get<T: 'static>(&mut self) -> Option<&mut T> {
+        self.data.get_mut(&TypeId::of::<T>())
+            .and_then(|data| data.downcast_mut())
+    }
+
+    pub(crate) fn set<T: 'static>(&mut self, data: T) -> Option<T> {
+        self.data.insert(TypeId::of::<T>(), Box::new(data))
+            .and_then(|previous| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    pub(crate) fn add_cleanup_hook<F: FnOnce() + 'static>(&mut self, hook: F) {
+        self.cleanup_hooks.push(Box::new(hook));
+    }
+}
+
+impl Drop for InstanceData {
+    fn drop(&mut self) {
+        // Hooks run in the reverse order they were added, and a panic can't be allowed to
+        // unwind into the engine that is tearing down the environment.
+        while let Some(hook) = self.cleanup_hooks.pop() {
+            let _ = panic::catch_unwind(AssertUnwindSafe(hook));
+        }
+    }
+}
+
+extern "C" fn drop_instance_data(data: Box<InstanceData>) {
+    std::mem::drop(data);
+}
+
+impl Env {
+    pub(crate) fn to_raw(self) -> raw::Env {
+        let Env(ptr) = self;
         ptr
     }
 
-    pub(crate) fn class_map(&mut self) -> &mut ClassMap {
+    pub(crate) fn from_raw(env: raw::Env) -> Env {
+        Env(env)
+    }
+
+    // The runtime stores the instance data in the slot it reserves for the class map, which
+    // is one part of it.
+    pub(crate) fn instance_data(&mut self) -> &mut InstanceData {
         let mut ptr: *mut c_void = unsafe { neon_runtime::class::get_class_map(self.to_raw()) };
         if ptr.is_null() {
-            let b: Box<ClassMap> = Box::new(ClassMap::new());
+            let b: Box<InstanceData> = Box::new(InstanceData::new());
             let raw = Box::into_raw(b);
             ptr = unsafe { std::mem::transmute(raw) };
-            let free_map: *mut c_void = unsafe { std::mem::transmute(drop_class_map as usize) };
+            let free_data: *mut c_void = unsafe { std::mem::transmute(drop_instance_data as usize) };
             unsafe {
-                neon_runtime::class::set_class_map(self.to_raw(), ptr, free_map);
+                neon_runtime::class::set_class_map(self.to_raw(), ptr, free_data);
             }
         }
         unsafe { std::mem::transmute(ptr) }
     }
 
-    pub(crate) fn current() -> Isolate {
+    pub(crate) fn class_map(&mut self) -> &mut ClassMap {
+        &mut self.instance_data().classes
+    }
+
+    /// Gets the environment of the JavaScript code currently running on this thread.
+    ///
+    /// This should only be used by APIs that don't have access to a context or a handle.
+    pub(crate) fn current() -> Env {
         unsafe {
-            std::mem::transmute(neon_runtime::call::current_isolate())
+            Env(neon_runtime::call::current_isolate())
         }
     }
 }
 
 pub struct ScopeMetadata {
-    isolate: Isolate,
+    env: Env,
     active: Cell<bool>
 }
 
@@ -56,16 +120,15 @@ pub struct Scope<'a, R: Root + 'static> {
 }
 
 impl<'a, R: Root + 'static> Scope<'a, R> {
-    pub fn with<T, F: for<'b> FnOnce(Scope<'b, R>) -> T>(f: F) -> T {
+    pub fn with<T, F: for<'b> FnOnce(Scope<'b, R>) -> T>(env: Env, f: F) -> T {
         let mut handle_scope: R = unsafe { R::allocate() };
-        let isolate = Isolate::current();
         unsafe {
-            handle_scope.enter(isolate.to_raw());
+            handle_scope.enter(env.to_raw());
         }
         let result = {
             let scope = Scope {
                 metadata: ScopeMetadata {
-                    isolate,
+                    env,
                     active: Cell::new(true)
                 },
                 handle_scope: &mut handle_scope
@@ -73,7 +136,7 @@ impl<'a, R: Root + 'static> Scope<'a, R> {
             f(scope)
         };
         unsafe {
-            handle_scope.exit();
+            handle_scope.exit(env.to_raw());
         }
         result
     }
@@ -82,8 +145,8 @@ impl<'a, R: Root + 'static> Scope<'a, R> {
 pub trait ContextInternal<'a>: Sized {
     fn scope_metadata(&self) -> &ScopeMetadata;
 
-    fn isolate(&self) -> Isolate {
-        self.scope_metadata().isolate
+    fn env(&self) -> Env {
+        self.scope_metadata().env
     }
 
     fn is_active(&self) -> bool {
@@ -100,8 +163,134 @@ pub trait ContextInternal<'a>: Sized {
     fn deactivate(&self) { self.scope_metadata().active.set(false); }
 }
 
-pub fn initialize_module(exports: Handle<JsObject>, init: fn(ModuleContext) -> NeonResult<()>) {
-    ModuleContext::with(exports, |cx| {
-        let _ = init(cx);
+/// Exports the functions registered with `neon::export`.
+#[cfg(feature = "macros")]
+fn register_exports(cx: &mut ModuleContext) -> NeonResult<()> {
+    for export in EXPORTS.iter() {
+        export(cx)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "macros"))]
+fn register_exports(_: &mut ModuleContext) -> NeonResult<()> {
+    Ok(())
+}
+
+#[cfg(feature = "legacy-runtime")]
+pub fn initialize_module(exports: raw::Local, init: fn(ModuleContext) -> NeonResult<()>) {
+    let env = Env::current();
+    let exports = Handle::new_internal(env, JsObject::from_raw(exports));
+    ModuleContext::with(env, exports, |mut cx| {
+        let _ = SharedHandler::init(&mut cx)
+            .and_then(|_| register_exports(&mut cx))
+            .and_then(|_| init(cx));
     });
 }
+
+#[cfg(feature = "napi-runtime")]
+pub fn initialize_module(env: raw::Env, exports: raw::Local, init: fn(ModuleContext) -> NeonResult<()>) {
+    // Record the environment for the APIs that can't reach it through a context or a
+    // handle while the module initializes, and restore the previous one afterwards.
+    let previous = unsafe { neon_runtime::call::set_current_env(env) };
+    let env = Env::from_raw(env);
+    let exports = Handle::new_internal(env, JsObject::from_raw(exports));
+    ModuleContext::with(env, exports, |mut cx| {
+        let _ = SharedHandler::init(&mut cx)
+            .and_then(|_| register_exports(&mut cx))
+            .and_then(|_| init(cx));
+    });
+    unsafe {
+        neon_runtime::call::set_current_env(previous);
+    }
+}
+
+/// Registers a module that exports the functions registered with `neon::export` and nothing
+/// else, unless the crate registers its own module with `register_module!` or `neon::main`.
+/// This is called by a static constructor that `neon::export` generates, so it may be called
+/// more than once, but the module is only registered the first time.
+#[cfg(feature = "macros")]
+pub fn register_default_module() {
+    use std::sync::Once;
+
+    static REGISTER: Once = Once::new();
+    if !MAINS.is_empty() {
+        return;
+    }
+    REGISTER.call_once(|| {
+        // Suppress the default Rust panic hook, which prints diagnostics to stderr.
+        panic::set_hook(Box::new(|_| { }));
+        unsafe {
+            register_node_module();
+        }
+    });
+}
+
+#[cfg(feature = "macros")]
+fn init_default_module(_: ModuleContext) -> NeonResult<()> {
+    Ok(())
+}
+
+#[cfg(all(feature = "macros", feature = "legacy-runtime"))]
+unsafe fn register_node_module() {
+    use std::ptr;
+
+    #[repr(C)]
+    struct NodeModule {
+        version: i32,
+        flags: u32,
+        dso_handle: *mut u8,
+        filename: *const u8,
+        register_func: Option<extern "C" fn(raw::Local, *mut u8, *mut u8)>,
+        context_register_func: Option<extern "C" fn(raw::Local, *mut u8, *mut u8, *mut u8)>,
+        modname: *const u8,
+        priv_data: *mut u8,
+        link: *mut NodeModule
+    }
+
+    extern "C" fn register(m: raw::Local, _: *mut u8, _: *mut u8, _: *mut u8) {
+        initialize_module(m, init_default_module);
+    }
+
+    extern "C" {
+        fn node_module_register(module: *mut NodeModule);
+    }
+
+    // Node holds on to the module for as long as the process lives.
+    let module = Box::into_raw(Box::new(NodeModule {
+        version: neon_runtime::module::get_version(),
+        flags: 0,
+        dso_handle: ptr::null_mut(),
+        filename: b"neon_source.rs\0".as_ptr(),
+        register_func: None,
+        context_register_func: Some(register),
+        modname: b"neon_module\0".as_ptr(),
+        priv_data: ptr::null_mut(),
+        link: ptr::null_mut()
+    }));
+    node_module_register(module);
+}
+
+#[cfg(all(feature = "macros", feature = "napi-runtime"))]
+unsafe fn register_node_module() {
+    use std::os::raw::c_char;
+    use std::ptr;
+    use neon_runtime::nodejs_sys as napi;
+
+    unsafe extern "C" fn register(env: napi::napi_env, exports: napi::napi_value) -> napi::napi_value {
+        initialize_module(env, exports, init_default_module);
+        exports
+    }
+
+    // Node holds on to the module for as long as the process lives.
+    let module = Box::into_raw(Box::new(napi::napi_module {
+        nm_version: 1,
+        nm_flags: 0,
+        nm_filename: b"neon_source.rs\0".as_ptr() as *const c_char,
+        nm_register_func: Some(register),
+        nm_modname: b"neon_module\0".as_ptr() as *const c_char,
+        nm_priv: ptr::null_mut(),
+        reserved: [ptr::null_mut(); 4]
+    }));
+    napi::napi_module_register(module);
+}
@@ -18,7 +18,8 @@ use types::error::JsError;
 use object::{Object, This};
 use object::class::Class;
 use result::{NeonResult, JsResult, Throw};
-use self::internal::{ContextInternal, Scope, ScopeMetadata};
+use convert::{IntoJs, FromArgs};
+use self::internal::{ContextInternal, Env, Scope, ScopeMetadata};
 
 #[repr(C)]
 pub(crate) struct CallbackInfo {
@@ -26,11 +27,17 @@ pub(crate) struct CallbackInfo {
 }
 
 impl CallbackInfo {
+    pub fn env(&self) -> Env {
+        unsafe {
+            Env::from_raw(neon_runtime::call::get_isolate(&self.info))
+        }
+    }
+
     pub fn data<'a>(&self) -> Handle<'a, JsValue> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
             neon_runtime::call::data(&self.info, &mut local);
-            Handle::new_internal(JsValue::from_raw(local))
+            Handle::new_internal(self.env(), JsValue::from_raw(local))
         }
     }
 
@@ -58,14 +65,14 @@ impl CallbackInfo {
         }
     }
 
-    pub fn get<'b, C: Context<'b>>(&self, _: &mut C, i: i32) -> Option<Handle<'b, JsValue>> {
+    pub fn get<'b, C: Context<'b>>(&self, cx: &mut C, i: i32) -> Option<Handle<'b, JsValue>> {
         if i < 0 || i >= self.len() {
             return None;
         }
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
             neon_runtime::call::get(&self.info, i, &mut local);
-            Some(Handle::new_internal(JsValue::from_raw(local)))
+            Some(Handle::new_internal(cx.env(), JsValue::from_raw(local)))
         }
     }
 
@@ -76,7 +83,7 @@ impl CallbackInfo {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
             neon_runtime::call::get(&self.info, i, &mut local);
-            Ok(Handle::new_internal(JsValue::from_raw(local)))
+            Ok(Handle::new_internal(cx.env(), JsValue::from_raw(local)))
         }
     }
 
@@ -101,13 +108,15 @@ pub enum CallKind {
 /// Types of JS values that support the `Borrow` and `BorrowMut` traits can be inspected while the engine is locked by passing a reference to a `Lock` to their methods.
 pub struct Lock<'a> {
     pub(crate) ledger: RefCell<Ledger>,
+    pub(crate) env: Env,
     phantom: PhantomData<&'a ()>
 }
 
 impl<'a> Lock<'a> {
-    fn new() -> Self {
+    fn new(env: Env) -> Self {
         Lock {
             ledger: RefCell::new(Ledger::new()),
+            env,
             phantom: PhantomData
         }
     }
@@ -123,7 +132,7 @@ pub trait Context<'a>: ContextInternal<'a> {
     /// If this is not the currently active context (for example, if it was used to spawn a scoped context with `execute_scoped` or `compute_scoped`), this method will panic.
     fn lock(&self) -> Lock {
         self.check_active();
-        Lock::new()
+        Lock::new(self.env())
     }
 
     /// Convenience method for locking the JavaScript engine and borrowing a single JS value's internals.
@@ -196,7 +205,7 @@ pub trait Context<'a>: ContextInternal<'a> {
     {
         self.check_active();
         self.deactivate();
-        let result = ExecuteContext::with(f);
+        let result = ExecuteContext::with(self.env(), f);
         self.activate();
         result
     }
@@ -212,19 +221,48 @@ pub trait Context<'a>: ContextInternal<'a> {
     {
         self.check_active();
         self.deactivate();
-        let result = ComputeContext::with(|cx| {
+        let env = self.env();
+        let result = ComputeContext::with(env, |cx| {
             unsafe {
                 let escapable_handle_scope = cx.scope.handle_scope as *mut raw::EscapableHandleScope;
                 let escapee = f(cx)?;
                 let mut result_local: raw::Local = std::mem::zeroed();
-                neon_runtime::scope::escape(&mut result_local, escapable_handle_scope, escapee.to_raw());
-                Ok(Handle::new_internal(V::from_raw(result_local)))
+                neon_runtime::scope::escape(&mut result_local, env.to_raw(), escapable_handle_scope, escapee.to_raw());
+                Ok(Handle::new_internal(env, V::from_raw(result_local)))
             }
         });
         self.activate();
         result
     }
 
+    /// Produces a mutable reference to the value of type `T` stored for the current environment,
+    /// if one has been stored with `set_instance_data`.
+    ///
+    /// Every environment that loads the module, such as the main thread or a
+    /// [worker thread](https://nodejs.org/api/worker_threads.html), has its own separate
+    /// instance data, which is dropped when the environment is torn down.
+    fn instance_data<T: 'static>(&mut self) -> Option<&mut T> {
+        self.check_active();
+        let mut env = self.env();
+        env.instance_data()
+            .get::<T>()
+            .map(|data| unsafe { &mut *(data as *mut T) })
+    }
+
+    /// Stores a value of type `T` for the current environment, returning the value of the
+    /// same type that was previously stored, if any.
+    fn set_instance_data<T: 'static>(&mut self, data: T) -> Option<T> {
+        self.check_active();
+        self.env().instance_data().set(data)
+    }
+
+    /// Adds a hook that runs when the current environment is torn down, for example when a
+    /// worker thread exits. Hooks run in the reverse order they were added.
+    fn add_cleanup_hook<F: FnOnce() + 'static>(&mut self, hook: F) {
+        self.check_active();
+        self.env().instance_data().add_cleanup_hook(hook);
+    }
+
     /// Convenience method for creating a `JsBoolean` value.
     fn boolean(&mut self, b: bool) -> Handle<'a, JsBoolean> {
         JsBoolean::new(self, b)
@@ -251,12 +289,12 @@ pub trait Context<'a>: ContextInternal<'a> {
 
     /// Convenience method for creating a `JsNull` value.
     fn null(&mut self) -> Handle<'a, JsNull> {
-        JsNull::new()
+        JsNull::new_internal(self.env())
     }
 
     /// Convenience method for creating a `JsUndefined` value.
     fn undefined(&mut self) -> Handle<'a, JsUndefined> {
-        JsUndefined::new()
+        JsUndefined::new_internal(self.env())
     }
 
     /// Convenience method for creating an empty `JsObject` value.
@@ -281,9 +319,10 @@ pub trait Context<'a>: ContextInternal<'a> {
 
     /// Produces a handle to the JavaScript global object.
     fn global(&mut self) -> Handle<'a, JsObject> {
-        JsObject::build(|out| {
+        let env = self.env();
+        JsObject::build(env, |out| {
             unsafe {
-                neon_runtime::scope::get_global(self.isolate().to_raw(), out);
+                neon_runtime::scope::get_global(env.to_raw(), out);
             }
         })
     }
@@ -291,7 +330,7 @@ pub trait Context<'a>: ContextInternal<'a> {
     /// Throws a JS value.
     fn throw<'b, T: Value, U>(&mut self, v: Handle<'b, T>) -> NeonResult<U> {
         unsafe {
-            neon_runtime::error::throw(v.to_raw());
+            neon_runtime::error::throw(self.env().to_raw(), v.to_raw());
         }
         Err(Throw)
     }
@@ -339,10 +378,10 @@ pub struct ModuleContext<'a> {
 impl<'a> UnwindSafe for ModuleContext<'a> { }
 
 impl<'a> ModuleContext<'a> {
-    pub(crate) fn with<T, F: for<'b> FnOnce(ModuleContext<'b>) -> T>(exports: Handle<'a, JsObject>, f: F) -> T {
+    pub(crate) fn with<T, F: for<'b> FnOnce(ModuleContext<'b>) -> T>(env: Env, exports: Handle<'a, JsObject>, f: F) -> T {
         debug_assert!(unsafe { neon_runtime::scope::size() } <= std::mem::size_of::<raw::HandleScope>());
         debug_assert!(unsafe { neon_runtime::scope::alignment() } <= std::mem::align_of::<raw::HandleScope>());
-        Scope::with(|scope| {
+        Scope::with(env, |scope| {
             f(ModuleContext {
                 scope,
                 exports
@@ -351,12 +390,23 @@ impl<'a> ModuleContext<'a> {
     }
 
     /// Convenience method for exporting a Neon function from a module.
-    pub fn export_function<T: Value>(&mut self, key: &str, f: fn(FunctionContext) -> JsResult<T>) -> NeonResult<()> {
+    pub fn export_function<F, T>(&mut self, key: &str, f: F) -> NeonResult<()>
+        where F: Fn(FunctionContext) -> JsResult<T> + 'static,
+              T: Value
+    {
         let value = JsFunction::new(self, f)?.upcast::<JsValue>();
         self.exports.set(self, key, value)?;
         Ok(())
     }
 
+    /// Convenience method for exporting a Neon function that returns a Rust value, which is
+    /// converted to a JavaScript value with `IntoJs`.
+    pub fn export_typed_function<T: IntoJs>(&mut self, key: &str, f: fn(FunctionContext) -> NeonResult<T>) -> NeonResult<()> {
+        let value = JsFunction::new_typed(self, f)?.upcast::<JsValue>();
+        self.exports.set(self, key, value)?;
+        Ok(())
+    }
+
     /// Convenience method for exporting a Neon class constructor from a module.
     pub fn export_class<T: Class>(&mut self, key: &str) -> NeonResult<()> {
         let constructor = T::constructor(self)?;
@@ -390,8 +440,8 @@ pub struct ExecuteContext<'a> {
 }
 
 impl<'a> ExecuteContext<'a> {
-    pub(crate) fn with<T, F: for<'b> FnOnce(ExecuteContext<'b>) -> T>(f: F) -> T {
-        Scope::with(|scope| {
+    pub(crate) fn with<T, F: for<'b> FnOnce(ExecuteContext<'b>) -> T>(env: Env, f: F) -> T {
+        Scope::with(env, |scope| {
             f(ExecuteContext { scope })
         })
     }
@@ -413,8 +463,8 @@ pub struct ComputeContext<'a, 'outer> {
 }
 
 impl<'a, 'b> ComputeContext<'a, 'b> {
-    pub(crate) fn with<T, F: for<'c, 'd> FnOnce(ComputeContext<'c, 'd>) -> T>(f: F) -> T {
-        Scope::with(|scope| {
+    pub(crate) fn with<T, F: for<'c, 'd> FnOnce(ComputeContext<'c, 'd>) -> T>(env: Env, f: F) -> T {
+        Scope::with(env, |scope| {
             f(ComputeContext {
                 scope,
                 phantom_inner: PhantomData,
@@ -436,7 +486,13 @@ impl<'a, 'b> Context<'a> for ComputeContext<'a, 'b> { }
 /// 
 /// The type parameter `T` is the type of the `this`-binding.
 pub struct CallContext<'a, T: This> {
+    #[cfg(feature = "legacy-runtime")]
     scope: Scope<'a, raw::HandleScope>,
+    // N-API produces the return value of a call after the callback returns, so it
+    // can't be allocated in a scope that closes first. The engine already opens a
+    // handle scope around every native function call.
+    #[cfg(feature = "napi-runtime")]
+    scope: Scope<'a, raw::InheritedHandleScope>,
     info: &'a CallbackInfo,
     phantom_type: PhantomData<T>
 }
@@ -448,7 +504,7 @@ impl<'a, T: This> CallContext<'a, T> {
     pub fn kind(&self) -> CallKind { self.info.kind() }
 
     pub(crate) fn with<U, F: for<'b> FnOnce(CallContext<'b, T>) -> U>(info: &'a CallbackInfo, f: F) -> U {
-        Scope::with(|scope| {
+        Scope::with(info.env(), |scope| {
             f(CallContext {
                 scope,
                 info,
@@ -471,9 +527,25 @@ impl<'a, T: This> CallContext<'a, T> {
         a.downcast_or_throw(self)
     }
 
+    /// Converts the arguments to a tuple of Rust values with `FromJs`, or throws a `TypeError`
+    /// naming the first argument that cannot be converted.
+    ///
+    /// ```no_run
+    /// # use neon::prelude::*;
+    /// fn greet(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let (name, excited) = cx.args::<(String, Option<bool>)>()?;
+    ///     let punctuation = if excited.unwrap_or(false) { "!" } else { "." };
+    ///     Ok(cx.string(format!("Hello, {}{}", name, punctuation)))
+    /// }
+    /// ```
+    pub fn args<A: FromArgs>(&mut self) -> NeonResult<A> {
+        A::from_args(self)
+    }
+
     /// Produces a handle to the `this`-binding.
     pub fn this(&mut self) -> Handle<'a, T> {
-        Handle::new_internal(T::as_this(self.info.this(self)))
+        let env = self.env();
+        Handle::new_internal(env, T::as_this(env, self.info.this(self)))
     }
 }
 
@@ -499,8 +571,8 @@ pub struct TaskContext<'a> {
 }
 
 impl<'a> TaskContext<'a> {
-    pub(crate) fn with<T, F: for<'b> FnOnce(TaskContext<'b>) -> T>(f: F) -> T {
-        Scope::with(|scope| {
+    pub(crate) fn with<T, F: for<'b> FnOnce(TaskContext<'b>) -> T>(env: Env, f: F) -> T {
+        Scope::with(env, |scope| {
             f(TaskContext { scope })
         })
     }
@@ -0,0 +1,425 @@
+//! Traits for converting between Rust values and JavaScript values.
+//!
+//! The `FromJs` trait converts a JavaScript value to a Rust value, checking that the value has
+//! the expected type, and the `IntoJs` trait converts a Rust value to a JavaScript value.
+//! Together with `CallContext::args`, they let a function work with plain Rust values instead
+//! of unwrapping each argument by hand:
+//!
+//! ```no_run
+//! # use neon::prelude::*;
+//! fn repeat(mut cx: FunctionContext) -> NeonResult<String> {
+//!     let (s, n) = cx.args::<(String, Option<u32>)>()?;
+//!     Ok(s.repeat(n.unwrap_or(2) as usize))
+//! }
+//!
+//! register_module!(mut cx, {
+//!     cx.export_typed_function("repeat", repeat)
+//! });
+//! ```
+//!
+//! # Deriving
+//!
+//! With the `macros` feature, which is disabled by default, `FromJs` and `IntoJs` can be derived
+//! for structs and enums:
+//!
+//! - A struct with named fields converts to and from a plain object with a property for each
+//!   field. Fields of type `Option<T>` may be missing from the object, and are left out of it
+//!   when they are `None`.
+//! - A newtype struct converts like the type it wraps, and any other tuple struct converts to
+//!   and from an array.
+//! - An enum whose variants have no fields converts to and from a string union of the variant
+//!   names.
+//! - Any other enum converts to and from a tagged object, whose `type` property names the
+//!   variant. The fields of a struct variant are properties of the same object, and the
+//!   fields of a tuple variant are stored in its `value` property.
+//!
+//! The conversions can be customized with `#[neon(...)]` attributes:
+//!
+//! - `#[neon(rename_all = "camelCase")]` on a struct renames its fields, and on an enum or
+//!   enum variant renames its variants or fields. The conventions are `lowercase`,
+//!   `UPPERCASE`, `camelCase`, `PascalCase`, `snake_case`, `SCREAMING_SNAKE_CASE` and
+//!   `kebab-case`.
+//! - `#[neon(rename = "name")]` on a field or variant sets its name.
+//! - `#[neon(default)]` on a field uses `Default::default()` when the property is missing or
+//!   `undefined`.
+//! - `#[neon(tag = "kind")]` and `#[neon(content = "data")]` on an enum change the tag and
+//!   value properties. An enum with a `tag` attribute is always converted to tagged objects.
+//!
+//! ```rust,ignore
+//! # use neon::prelude::*;
+//! #[derive(FromJs, IntoJs)]
+//! #[neon(rename_all = "camelCase")]
+//! struct User {
+//!     user_name: String,
+//!     display_name: Option<String>,
+//!     #[neon(default)]
+//!     login_count: u32,
+//!     status: Status
+//! }
+//!
+//! #[derive(FromJs, IntoJs)]
+//! #[neon(rename_all = "lowercase")]
+//! enum Status {
+//!     Active,
+//!     Suspended
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::hash::{BuildHasher, Hash};
+
+use context::{CallContext, Context};
+use handle::{Handle, Managed};
+use object::{Object, This};
+use result::{NeonResult, JsResult, Throw};
+use types::{Value, JsValue, JsUndefined, JsNull, JsBoolean, JsNumber, JsString, JsBigInt, JsSymbol, JsFunction, JsObject, JsArray};
+
+#[cfg(feature = "macros")]
+pub use neon_macros::{FromJs, IntoJs};
+
+/// An error produced when a JavaScript value cannot be converted to a Rust value.
+#[derive(Debug)]
+pub enum ConversionError {
+    /// A JavaScript exception was thrown during the conversion, for example by a getter, and is
+    /// still pending.
+    Throw,
+    /// The value had the wrong type or was out of range for the Rust type.
+    Invalid(String)
+}
+
+impl ConversionError {
+    /// Creates an error for a value that does not have the expected type.
+    pub fn expected<V: Value>(expected: &str, found: Handle<V>) -> ConversionError {
+        ConversionError::Invalid(format!("expected {}, found {}", expected, type_name(found)))
+    }
+
+    /// Prefixes the error message with the location of the value that failed to convert, such
+    /// as `argument 1` or `property "name"`.
+    pub fn context<D: Display>(self, location: D) -> ConversionError {
+        match self {
+            ConversionError::Throw => ConversionError::Throw,
+            ConversionError::Invalid(message) => ConversionError::Invalid(format!("{}: {}", location, message))
+        }
+    }
+
+    /// Throws the error as a JavaScript `TypeError`, unless an exception is already pending.
+    pub fn throw<'a, C: Context<'a>, T>(self, cx: &mut C) -> NeonResult<T> {
+        match self {
+            ConversionError::Throw => Err(Throw),
+            ConversionError::Invalid(message) => cx.throw_type_error(message)
+        }
+    }
+}
+
+impl From<Throw> for ConversionError {
+    fn from(_: Throw) -> ConversionError {
+        ConversionError::Throw
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        match *self {
+            ConversionError::Throw => fmt.write_str("JavaScript Error"),
+            ConversionError::Invalid(ref message) => fmt.write_str(message)
+        }
+    }
+}
+
+/// The result of converting a JavaScript value to a Rust value.
+pub type ConversionResult<T> = Result<T, ConversionError>;
+
+/// A Rust type that can be converted from a JavaScript value.
+pub trait FromJs: Sized {
+    fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<Self>;
+}
+
+/// A Rust type that can be converted to a JavaScript value.
+pub trait IntoJs {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue>;
+}
+
+/// A tuple of Rust types that can be converted from the arguments of a function call, as
+/// produced by `CallContext::args`.
+///
+/// Missing arguments are treated as `undefined`, so trailing `Option` arguments may be omitted
+/// by the caller. Extra arguments are ignored.
+pub trait FromArgs: Sized {
+    fn from_args<'a, T: This>(cx: &mut CallContext<'a, T>) -> NeonResult<Self>;
+}
+
+/// A short description of the type of a JavaScript value, for error messages.
+fn type_name<V: Value>(value: Handle<V>) -> &'static str {
+    if value.is_a::<JsUndefined>() {
+        "undefined"
+    } else if value.is_a::<JsNull>() {
+        "null"
+    } else if value.is_a::<JsBoolean>() {
+        "boolean"
+    } else if value.is_a::<JsNumber>() {
+        "number"
+    } else if value.is_a::<JsString>() {
+        "string"
+    } else if value.is_a::<JsBigInt>() {
+        "bigint"
+    } else if value.is_a::<JsSymbol>() {
+        "symbol"
+    } else if value.is_a::<JsFunction>() {
+        "function"
+    } else if value.is_a::<JsArray>() {
+        "array"
+    } else {
+        "object"
+    }
+}
+
+fn downcast<'a, V: Value>(value: Handle<'a, JsValue>, expected: &str) -> ConversionResult<Handle<'a, V>> {
+    value.downcast::<V>().map_err(|_| ConversionError::expected(expected, value))
+}
+
+fn is_nullish(value: Handle<JsValue>) -> bool {
+    value.is_a::<JsUndefined>() || value.is_a::<JsNull>()
+}
+
+impl<'b, V: Value> IntoJs for Handle<'b, V> {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        Ok(JsValue::new_internal(cx.env(), self.to_raw()))
+    }
+}
+
+impl IntoJs for () {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        Ok(cx.undefined().upcast())
+    }
+}
+
+impl FromJs for bool {
+    fn from_js<'a, C: Context<'a>>(_: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<bool> {
+        Ok(downcast::<JsBoolean>(value, "a boolean")?.value())
+    }
+}
+
+impl IntoJs for bool {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        Ok(cx.boolean(self).upcast())
+    }
+}
+
+impl FromJs for f64 {
+    fn from_js<'a, C: Context<'a>>(_: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<f64> {
+        Ok(downcast::<JsNumber>(value, "a number")?.value())
+    }
+}
+
+impl IntoJs for f64 {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        Ok(cx.number(self).upcast())
+    }
+}
+
+/// An `f32` is converted from a number rounded to the nearest `f32`. Finite numbers too large
+/// for an `f32` are rejected rather than becoming infinite.
+impl FromJs for f32 {
+    fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<f32> {
+        let n = f64::from_js(cx, value)?;
+        let rounded = n as f32;
+        if n.is_finite() && rounded.is_infinite() {
+            return Err(ConversionError::Invalid(format!("{} is out of range for f32", n)));
+        }
+        Ok(rounded)
+    }
+}
+
+impl IntoJs for f32 {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        Ok(cx.number(self).upcast())
+    }
+}
+
+/// The largest integer that a JavaScript number represents exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+macro_rules! impl_integer {
+    ($($t:ident),*) => { $(
+        impl FromJs for $t {
+            fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<$t> {
+                let n = f64::from_js(cx, value)?;
+                if n.fract() != 0.0 || !n.is_finite() {
+                    return Err(ConversionError::Invalid(format!("expected an integer, found {}", n)));
+                }
+                // Adding one to the maximum keeps the bound exact even for 64-bit types,
+                // whose maximum rounds up to a power of two as an `f64`.
+                if n < ($t::min_value() as f64) || n >= ($t::max_value() as f64) + 1.0 {
+                    return Err(ConversionError::Invalid(format!("{} is out of range for {}", n, stringify!($t))));
+                }
+                Ok(n as $t)
+            }
+        }
+
+        impl IntoJs for $t {
+            fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+                let n = self as f64;
+                if n.abs() > MAX_SAFE_INTEGER {
+                    return cx.throw_range_error(format!("{} cannot be represented exactly as a number", self));
+                }
+                Ok(cx.number(n).upcast())
+            }
+        }
+    )* }
+}
+
+impl_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromJs for String {
+    fn from_js<'a, C: Context<'a>>(_: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<String> {
+        Ok(downcast::<JsString>(value, "a string")?.value())
+    }
+}
+
+impl IntoJs for String {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        self.as_str().into_js(cx)
+    }
+}
+
+impl<'s> IntoJs for &'s str {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        match JsString::new_internal(cx.env(), self) {
+            Some(s) => Ok(s.upcast()),
+            None => cx.throw_range_error("string is too long")
+        }
+    }
+}
+
+/// `None` is converted from `undefined` or `null`, and converted to `null`.
+impl<T: FromJs> FromJs for Option<T> {
+    fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<Option<T>> {
+        if is_nullish(value) {
+            Ok(None)
+        } else {
+            T::from_js(cx, value).map(Some)
+        }
+    }
+}
+
+impl<T: IntoJs> IntoJs for Option<T> {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        match self {
+            Some(v) => v.into_js(cx),
+            None => Ok(cx.null().upcast())
+        }
+    }
+}
+
+/// A `Vec` is converted from and to an array.
+impl<T: FromJs> FromJs for Vec<T> {
+    fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<Vec<T>> {
+        let elements = downcast::<JsArray>(value, "an array")?.to_vec(cx)?;
+        let mut result = Vec::with_capacity(elements.len());
+        for (i, element) in elements.into_iter().enumerate() {
+            result.push(T::from_js(cx, element).map_err(|e| e.context(format!("element {}", i)))?);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: IntoJs> IntoJs for Vec<T> {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        let array = JsArray::new(cx, self.len() as u32);
+        for (i, element) in self.into_iter().enumerate() {
+            let element = element.into_js(cx)?;
+            array.set(cx, i as u32, element)?;
+        }
+        Ok(array.upcast())
+    }
+}
+
+/// A `HashMap` is converted from the own enumerable properties of an object, and to a plain
+/// object.
+impl<T: FromJs, S: BuildHasher + Default> FromJs for HashMap<String, T, S> {
+    fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<HashMap<String, T, S>> {
+        if value.is_a::<JsArray>() || value.is_a::<JsFunction>() {
+            return Err(ConversionError::expected("an object", value));
+        }
+        let object = downcast::<JsObject>(value, "an object")?;
+        let keys = object.get_own_property_names(cx)?.to_vec(cx)?;
+        let mut result = HashMap::with_capacity_and_hasher(keys.len(), S::default());
+        for key in keys {
+            let name = key.to_string(cx)?.value();
+            let value = object.get(cx, key)?;
+            let value = T::from_js(cx, value).map_err(|e| e.context(format!("property {:?}", name)))?;
+            result.insert(name, value);
+        }
+        Ok(result)
+    }
+}
+
+impl<K: AsRef<str> + Eq + Hash, T: IntoJs, S: BuildHasher> IntoJs for HashMap<K, T, S> {
+    fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        let object = JsObject::new(cx);
+        for (key, value) in self {
+            let value = value.into_js(cx)?;
+            object.set(cx, key.as_ref(), value)?;
+        }
+        Ok(object.upcast())
+    }
+}
+
+macro_rules! impl_tuple {
+    ($len:expr; $($t:ident $i:tt),*) => {
+        /// A tuple is converted from and to an array of the same length.
+        impl<$($t: FromJs),*> FromJs for ($($t,)*) {
+            fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> ConversionResult<Self> {
+                let elements = downcast::<JsArray>(value, concat!("an array of length ", $len))?.to_vec(cx)?;
+                if elements.len() != $len {
+                    return Err(ConversionError::Invalid(format!("expected an array of length {}, found length {}", $len, elements.len())));
+                }
+                Ok(($(
+                    $t::from_js(cx, elements[$i]).map_err(|e| e.context(concat!("element ", $i)))?,
+                )*))
+            }
+        }
+
+        impl<$($t: IntoJs),*> IntoJs for ($($t,)*) {
+            fn into_js<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+                let array = JsArray::new(cx, $len);
+                $(
+                    let element = self.$i.into_js(cx)?;
+                    array.set(cx, $i, element)?;
+                )*
+                Ok(array.upcast())
+            }
+        }
+
+        impl<$($t: FromJs),*> FromArgs for ($($t,)*) {
+            fn from_args<'a, T: This>(cx: &mut CallContext<'a, T>) -> NeonResult<Self> {
+                Ok(($({
+                    let arg = match cx.argument_opt($i) {
+                        Some(arg) => arg,
+                        None => cx.undefined().upcast()
+                    };
+                    match $t::from_js(cx, arg) {
+                        Ok(v) => v,
+                        Err(e) => return e.context(concat!("argument ", $i)).throw(cx)
+                    }
+                },)*))
+            }
+        }
+    }
+}
+
+impl_tuple!(1; T0 0);
+impl_tuple!(2; T0 0, T1 1);
+impl_tuple!(3; T0 0, T1 1, T2 2);
+impl_tuple!(4; T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(5; T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(6; T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_tuple!(7; T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_tuple!(8; T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+
+impl FromArgs for () {
+    fn from_args<'a, T: This>(_: &mut CallContext<'a, T>) -> NeonResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+//! Calling JavaScript from Rust threads other than the JavaScript thread.
+
+use std::os::raw::c_void;
+use std::sync::Arc;
+
+use context::{Context, FunctionContext, TaskContext};
+use context::internal::Env;
+use handle::{Handle, Managed};
+use result::{JsResult, NeonResult};
+use types::{JsFunction, JsUndefined, JsValue, Value};
+use neon_runtime;
+use neon_runtime::raw;
+
+struct EventHandlerInner(*mut c_void);
+
+// The runtime's handler can be used and released from any thread.
+unsafe impl Send for EventHandlerInner { }
+unsafe impl Sync for EventHandlerInner { }
+
+impl Drop for EventHandlerInner {
+    fn drop(&mut self) {
+        unsafe {
+            neon_runtime::handler::delete(self.0);
+        }
+    }
+}
+
+/// A handle to a JavaScript function that can be sent to, and called from, any Rust thread.
+///
+/// Calls are queued and run on the JavaScript thread that created the handler, each with a
+/// `TaskContext` of its own. The handler keeps the Node event loop alive until every clone
+/// of it has been dropped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn watch(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let callback = cx.argument::<JsFunction>(0)?;
+///     let handler = EventHandler::new(&mut cx, callback);
+///
+///     std::thread::spawn(move || {
+///         for progress in 0..100 {
+///             handler.schedule(move |cx| vec![cx.number(progress)]);
+///         }
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct EventHandler(Arc<EventHandlerInner>);
+
+impl EventHandler {
+    /// Creates a handler that calls `callback` with `undefined` as its receiver.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, callback: Handle<JsFunction>) -> Self {
+        let this = cx.undefined();
+        EventHandler::bind(this, callback)
+    }
+
+    /// Creates a handler that calls `callback` with `this` as its receiver.
+    pub fn bind<T: Value>(this: Handle<T>, callback: Handle<JsFunction>) -> Self {
+        let handler = unsafe {
+            neon_runtime::handler::new(callback.env().to_raw(), this.to_raw(), callback.to_raw())
+        };
+        EventHandler(Arc::new(EventHandlerInner(handler)))
+    }
+
+    /// Queues a call of the function with the arguments produced by `arg_cb`, which runs on
+    /// the JavaScript thread.
+    pub fn schedule<T, F>(&self, arg_cb: F)
+        where T: Value,
+              F: for<'a> FnOnce(&mut TaskContext<'a>) -> Vec<Handle<'a, T>>,
+              F: Send + 'static
+    {
+        self.schedule_with(move |cx, this, callback| {
+            let args = arg_cb(cx);
+            // An exception thrown by the function is reported as uncaught once this returns.
+            let _ = callback.call(cx, this, args);
+        });
+    }
+
+    /// Queues a Rust callback that runs on the JavaScript thread with the receiver and the
+    /// function of the handler, which it is free to call any number of times.
+    pub fn schedule_with<F>(&self, arg_cb: F)
+        where F: FnOnce(&mut TaskContext, Handle<JsValue>, Handle<JsFunction>),
+              F: Send + 'static
+    {
+        let callback = Box::into_raw(Box::new(arg_cb)) as *mut c_void;
+        unsafe {
+            if !neon_runtime::handler::schedule((self.0).0, callback, handle_callback::<F>) {
+                drop(Box::from_raw(callback as *mut F));
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn handle_callback<F>(env: raw::Env, this: raw::Local, callback: raw::Local, arg_cb: *mut c_void)
+    where F: FnOnce(&mut TaskContext, Handle<JsValue>, Handle<JsFunction>),
+          F: Send + 'static
+{
+    let arg_cb: Box<F> = Box::from_raw(arg_cb as *mut F);
+
+    // The environment is gone, so the callback can only be dropped.
+    if env.is_null() {
+        return;
+    }
+
+    let env = Env::from_raw(env);
+    TaskContext::with(env, |mut cx| {
+        let this = Handle::new_internal(env, JsValue::from_raw(this));
+        let callback = Handle::new_internal(env, JsFunction::from_raw(callback));
+        arg_cb(&mut cx, this, callback);
+    });
+}
+
+/// The handler that Neon itself uses to get back onto the JavaScript thread of an environment,
+/// for example to release a `Persistent` that was dropped on another thread. Each environment
+/// gets one when the module is initialized. Unlike other handlers, it only keeps the event loop
+/// alive while it is referenced.
+pub(crate) struct SharedHandler {
+    handler: EventHandler,
+    refs: usize
+}
+
+impl SharedHandler {
+    /// Creates the shared handler of the context's environment.
+    pub(crate) fn init<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<()> {
+        // Only Rust callbacks are scheduled on the handler, so its function is never called.
+        let function = JsFunction::new(cx, ignore)?;
+        let handler = EventHandler::new(cx, function);
+        unsafe {
+            neon_runtime::handler::unreference(cx.env().to_raw(), (handler.0).0);
+        }
+        cx.set_instance_data(SharedHandler { handler, refs: 0 });
+        Ok(())
+    }
+
+    /// Gets the shared handler of the context's environment.
+    pub(crate) fn get<'a, C: Context<'a>>(cx: &mut C) -> EventHandler {
+        SharedHandler::with(cx, |shared| shared.handler.clone())
+    }
+
+    /// Keeps the event loop of the context's environment alive until the `KeepAlive` is
+    /// dropped, which can happen on any thread.
+    pub(crate) fn keep_alive<'a, C: Context<'a>>(cx: &mut C) -> KeepAlive {
+        SharedHandler::reference(cx);
+        KeepAlive(SharedHandler::get(cx))
+    }
+
+    fn reference<'a, C: Context<'a>>(cx: &mut C) {
+        let env = cx.env();
+        SharedHandler::with(cx, |shared| {
+            if shared.refs == 0 {
+                unsafe { neon_runtime::handler::reference(env.to_raw(), (shared.handler.0).0); }
+            }
+            shared.refs += 1;
+        });
+    }
+
+    fn unreference<'a, C: Context<'a>>(cx: &mut C) {
+        let env = cx.env();
+        SharedHandler::with(cx, |shared| {
+            shared.refs -= 1;
+            if shared.refs == 0 {
+                unsafe { neon_runtime::handler::unreference(env.to_raw(), (shared.handler.0).0); }
+            }
+        });
+    }
+
+    fn with<'a, C: Context<'a>, T, F: FnOnce(&mut SharedHandler) -> T>(cx: &mut C, f: F) -> T {
+        let shared = cx.instance_data::<SharedHandler>()
+            .expect("the shared event handler is created when the module is initialized");
+        f(shared)
+    }
+}
+
+/// A reference to the shared handler of an environment, which keeps its event loop alive.
+pub(crate) struct KeepAlive(EventHandler);
+
+impl Drop for KeepAlive {
+    fn drop(&mut self) {
+        self.0.schedule_with(|cx, _, _| SharedHandler::unreference(cx));
+    }
+}
+
+fn ignore(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    Ok(cx.undefined())
+}
@@ -0,0 +1,194 @@
+//! Running Rust futures in the background and settling JavaScript promises with their output.
+//!
+//! Futures are driven by an [`Executor`](trait.Executor.html). Unless another one is
+//! installed with [`set_executor`](fn.set_executor.html), Neon uses a
+//! [`ThreadExecutor`](struct.ThreadExecutor.html), which runs every future on a single
+//! background thread.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn fetch(mut cx: FunctionContext) -> JsResult<JsPromise> {
+//!     let url = cx.argument::<JsString>(0)?.value();
+//!     neon::future::promise(&mut cx, async move { get(&url).await }, |cx, body| match body {
+//!         Ok(body) => Ok(cx.string(body)),
+//!         Err(err) => cx.throw_error(err.to_string())
+//!     })
+//! }
+//! ```
+
+use std;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::mpsc::{self, Sender};
+use std::task::{Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread;
+
+use context::{Context, TaskContext};
+use event::SharedHandler;
+use result::JsResult;
+use types::{JsPromise, Value};
+
+/// A future that can be spawned onto an `Executor`.
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// An executor that drives futures to completion in the background.
+pub trait Executor: Send + Sync + 'static {
+    /// Spawns a future, which the executor polls until it completes.
+    fn spawn(&self, future: BoxFuture);
+}
+
+static EXECUTOR: OnceLock<Box<dyn Executor>> = OnceLock::new();
+
+/// Installs the executor that drives the futures of every module instance in the process.
+///
+/// The executor can only be installed once, before the first future is spawned. Returns
+/// `false`, dropping `executor`, if an executor is already in use.
+pub fn set_executor<E: Executor>(executor: E) -> bool {
+    EXECUTOR.set(Box::new(executor)).is_ok()
+}
+
+fn executor() -> &'static dyn Executor {
+    &**EXECUTOR.get_or_init(|| Box::new(ThreadExecutor::new()))
+}
+
+/// Spawns `future` onto the executor, producing a promise for its output.
+///
+/// Once the future completes, `complete` converts its output to a JavaScript value on the
+/// JavaScript thread, and the promise resolves with that value, or rejects with the
+/// exception that `complete` throws.
+pub fn promise<'a, C, F, V, S>(cx: &mut C, future: F, complete: S) -> JsResult<'a, JsPromise>
+    where C: Context<'a>,
+          F: Future + Send + 'static,
+          F::Output: Send + 'static,
+          V: Value,
+          S: for<'b> FnOnce(&mut TaskContext<'b>, F::Output) -> JsResult<'b, V>,
+          S: Send + 'static
+{
+    let (promise, deferred) = JsPromise::new(cx);
+    let handler = SharedHandler::get(cx);
+
+    // The event loop stays alive while the future is pending. If the executor drops the
+    // future before it completes, the promise is rejected when `deferred` is dropped.
+    let keep_alive = SharedHandler::keep_alive(cx);
+
+    executor().spawn(Box::pin(Settle {
+        future: Box::pin(future),
+        settle: Some(Box::new(move |output: F::Output| {
+            handler.schedule_with(move |cx, _, _| {
+                deferred.settle_with(cx, move |cx| complete(cx, output));
+                drop(keep_alive);
+            });
+        }))
+    }));
+
+    Ok(promise)
+}
+
+/// A future that passes the output of another future to a callback.
+struct Settle<F: Future> {
+    future: Pin<Box<F>>,
+    settle: Option<Box<dyn FnOnce(F::Output) + Send>>
+}
+
+impl<F: Future> Future for Settle<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<()> {
+        match self.future.as_mut().poll(cx) {
+            Poll::Ready(output) => {
+                if let Some(settle) = self.settle.take() {
+                    settle(output);
+                }
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
+
+/// The default executor, which runs every future on a single background thread.
+pub struct ThreadExecutor {
+    queue: Mutex<Sender<Arc<Spawned>>>
+}
+
+impl ThreadExecutor {
+    /// Starts the background thread of a new executor.
+    pub fn new() -> Self {
+        let (queue, spawned) = mpsc::channel::<Arc<Spawned>>();
+        thread::Builder::new()
+            .name("neon-executor".to_string())
+            .spawn(move || {
+                for spawned in spawned {
+                    poll_spawned(spawned);
+                }
+            })
+            .expect("failed to start the executor thread");
+        ThreadExecutor { queue: Mutex::new(queue) }
+    }
+}
+
+impl Executor for ThreadExecutor {
+    fn spawn(&self, future: BoxFuture) {
+        let queue = self.queue.lock().unwrap().clone();
+        let spawned = Arc::new(Spawned {
+            future: Mutex::new(Some(future)),
+            queue: Mutex::new(queue.clone())
+        });
+        let _ = queue.send(spawned);
+    }
+}
+
+/// A future spawned onto a `ThreadExecutor`, which is queued again whenever it is woken.
+struct Spawned {
+    future: Mutex<Option<BoxFuture>>,
+    queue: Mutex<Sender<Arc<Spawned>>>
+}
+
+fn poll_spawned(spawned: Arc<Spawned>) {
+    let waker = unsafe { Waker::from_raw(raw_waker(spawned.clone())) };
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut slot = spawned.future.lock().unwrap();
+
+    // A future that was woken more than once is queued more than once, but it is gone
+    // once it has completed.
+    if let Some(mut future) = slot.take() {
+        if future.as_mut().poll(&mut cx).is_pending() {
+            *slot = Some(future);
+        }
+    }
+}
+
+fn requeue(spawned: Arc<Spawned>) {
+    let queue = spawned.queue.lock().unwrap().clone();
+    let _ = queue.send(spawned);
+}
+
+static WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+fn raw_waker(spawned: Arc<Spawned>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(spawned) as *const (), &WAKER_VTABLE)
+}
+
+unsafe fn clone_waker(data: *const ()) -> RawWaker {
+    let spawned = Arc::from_raw(data as *const Spawned);
+    let waker = raw_waker(spawned.clone());
+    mem::forget(spawned);
+    waker
+}
+
+unsafe fn wake(data: *const ()) {
+    requeue(Arc::from_raw(data as *const Spawned));
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    let spawned = Arc::from_raw(data as *const Spawned);
+    requeue(spawned.clone());
+    mem::forget(spawned);
+}
+
+unsafe fn drop_waker(data: *const ()) {
+    mem::drop(Arc::from_raw(data as *const Spawned));
+}
@@ -1,6 +1,8 @@
 //! Safe _handles_ to managed JavaScript memory.
 
 pub(crate) mod internal;
+mod persistent;
+mod weak;
 
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
@@ -10,9 +12,13 @@ use neon_runtime;
 use neon_runtime::raw;
 use types::Value;
 use context::Context;
+use context::internal::Env;
 use result::{JsResult, JsResultExt};
 use self::internal::SuperType;
 
+pub use self::persistent::Persistent;
+pub use self::weak::Weak;
+
 /// The trait of data that is managed by the JS garbage collector and can only be accessed via handles.
 pub trait Managed: Copy {
     fn to_raw(self) -> raw::Local;
@@ -25,24 +31,31 @@ pub trait Managed: Copy {
 #[derive(Clone, Copy)]
 pub struct Handle<'a, T: Managed + 'a> {
     value: T,
+    env: Env,
     phantom: PhantomData<&'a T>
 }
 
 impl<'a, T: Managed + 'a> PartialEq for Handle<'a, T> {
     fn eq(&self, other: &Self) -> bool {
-        unsafe { neon_runtime::mem::same_handle(self.to_raw(), other.to_raw()) }
+        unsafe { neon_runtime::mem::same_handle(self.env.to_raw(), self.to_raw(), other.to_raw()) }
     }
 }
 
 impl<'a, T: Managed + 'a> Eq for Handle<'a, T> { }
 
 impl<'a, T: Managed + 'a> Handle<'a, T> {
-    pub(crate) fn new_internal(value: T) -> Handle<'a, T> {
+    pub(crate) fn new_internal(env: Env, value: T) -> Handle<'a, T> {
         Handle {
             value: value,
+            env: env,
             phantom: PhantomData
         }
     }
+
+    /// The environment that the handle belongs to.
+    pub(crate) fn env(&self) -> Env {
+        self.env
+    }
 }
 
 /// An error representing a failed downcast.
@@ -99,7 +112,7 @@ impl<'a, T: Value> Handle<'a, T> {
     /// 
     /// This method does not require an execution context because it only copies a handle.
     pub fn upcast<U: Value + SuperType<T>>(&self) -> Handle<'a, U> {
-        Handle::new_internal(SuperType::upcast_internal(self.value))
+        Handle::new_internal(self.env, SuperType::upcast_internal(self.value))
     }
 
     /// Tests whether this value is an instance of the given type.
@@ -117,7 +130,7 @@ impl<'a, T: Value> Handle<'a, T> {
     /// # }
     /// ```
     pub fn is_a<U: Value>(&self) -> bool {
-        U::is_typeof(self.value)
+        U::is_typeof(self.env, self.value)
     }
 
     /// Attempts to downcast a handle to another type, which may fail. A failure
@@ -125,8 +138,8 @@ impl<'a, T: Value> Handle<'a, T> {
     /// continue interacting with the JS engine if this method produces an `Err`
     /// result.
     pub fn downcast<U: Value>(&self) -> DowncastResult<'a, T, U> {
-        match U::downcast(self.value) {
-            Some(v) => Ok(Handle::new_internal(v)),
+        match U::downcast(self.env, self.value) {
+            Some(v) => Ok(Handle::new_internal(self.env, v)),
             None => Err(DowncastError::new())
         }
     }
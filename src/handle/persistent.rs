@@ -0,0 +1,111 @@
+//! Handles that keep JS values alive beyond the scope that created them.
+
+use std::marker::PhantomData;
+use std::mem;
+use std::thread::{self, ThreadId};
+use neon_runtime;
+use neon_runtime::raw;
+use types::Value;
+use context::Context;
+use context::internal::Env;
+use event::{EventHandler, SharedHandler};
+use super::{Handle, Managed};
+
+/// A rooted handle to a JS value, which keeps the value alive until the handle is dropped.
+///
+/// Unlike a `Handle`, a `Persistent` isn't tied to a scope, so it can be stored in the
+/// internals of a class, moved into a `Task`, or kept anywhere else that outlives the
+/// current call, and turned back into a `Handle` in a later context.
+///
+/// Dropping a `Persistent` on the JavaScript thread that created it releases its value right
+/// away. Dropping it on any other thread queues the release to run on the JavaScript thread.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// struct CountTask {
+///     target: Persistent<JsObject>
+/// }
+///
+/// impl Task for CountTask {
+///     type Output = u32;
+///     type Error = String;
+///     type JsEvent = JsObject;
+///
+///     fn perform(&self) -> Result<u32, String> {
+///         Ok(42)
+///     }
+///
+///     fn complete(self, mut cx: TaskContext, result: Result<u32, String>) -> JsResult<JsObject> {
+///         let target = self.target.to_handle(&mut cx);
+///         let count = cx.number(result.unwrap());
+///         target.set(&mut cx, "count", count)?;
+///         Ok(target)
+///     }
+/// }
+/// ```
+pub struct Persistent<T: Value> {
+    internal: raw::Persistent,
+    env: Env,
+    thread: ThreadId,
+    handler: EventHandler,
+    phantom: PhantomData<T>
+}
+
+// The value can only be reached with a context of the environment that created the handle,
+// which is checked by `to_handle` and `drop`.
+unsafe impl<T: Value> Send for Persistent<T> { }
+
+impl<T: Value> Persistent<T> {
+    /// Creates a persistent handle to the value of `handle`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, handle: Handle<T>) -> Self {
+        let env = cx.env();
+        let internal = unsafe {
+            neon_runtime::mem::new_persistent(env.to_raw(), handle.to_raw())
+        };
+        Persistent {
+            internal,
+            env,
+            thread: thread::current().id(),
+            handler: SharedHandler::get(cx),
+            phantom: PhantomData
+        }
+    }
+
+    /// Produces a handle to the value, scoped to the given context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context belongs to a different JavaScript environment, such as another
+    /// worker thread, than the one that created the persistent handle.
+    pub fn to_handle<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, T> {
+        let env = cx.env();
+        assert!(env == self.env, "persistent handle used outside of the environment that created it");
+        unsafe {
+            let mut local: raw::Local = mem::zeroed();
+            neon_runtime::mem::get_persistent(&mut local, env.to_raw(), self.internal);
+            Handle::new_internal(env, T::from_raw(local))
+        }
+    }
+}
+
+impl<T: Value> Drop for Persistent<T> {
+    fn drop(&mut self) {
+        if thread::current().id() == self.thread {
+            unsafe {
+                neon_runtime::mem::drop_persistent(self.env.to_raw(), self.internal);
+            }
+            return;
+        }
+
+        let internal = RawPersistent(self.env, self.internal);
+        self.handler.schedule_with(move |_, _, _| unsafe {
+            neon_runtime::mem::drop_persistent((internal.0).to_raw(), internal.1);
+        });
+    }
+}
+
+struct RawPersistent(Env, raw::Persistent);
+
+// The persistent handle is only released on the JavaScript thread that created it.
+unsafe impl Send for RawPersistent { }
@@ -0,0 +1,97 @@
+//! Handles that refer to JS objects without keeping them alive.
+
+use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::c_void;
+use neon_runtime;
+use neon_runtime::raw;
+use object::Object;
+use context::Context;
+use context::internal::Env;
+use super::{Handle, Managed};
+
+/// A handle to a JS object that doesn't keep the object alive.
+///
+/// Once the object has been garbage collected, the handle no longer produces it. A `Weak`
+/// can't be sent to other threads, so it is always released on the JavaScript thread that
+/// created it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let weak = Weak::new(&mut cx, obj);
+///
+/// // ...later, possibly in another call:
+/// match weak.to_handle(&mut cx) {
+///     Some(obj) => { /* the object is still alive */ }
+///     None => { /* the object has been collected */ }
+/// }
+/// ```
+pub struct Weak<T: Object> {
+    internal: raw::Persistent,
+    env: Env,
+    phantom: PhantomData<T>
+}
+
+impl<T: Object> Weak<T> {
+    /// Creates a weak handle to the object of `handle`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, handle: Handle<T>) -> Self {
+        let env = cx.env();
+        let internal = unsafe {
+            neon_runtime::mem::new_weak(env.to_raw(), handle.to_raw())
+        };
+        Weak {
+            internal,
+            env,
+            phantom: PhantomData
+        }
+    }
+
+    /// Produces a handle to the object, scoped to the given context, or `None` if the object
+    /// has been garbage collected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context belongs to a different JavaScript environment, such as another
+    /// worker thread, than the one that created the weak handle.
+    pub fn to_handle<'a, C: Context<'a>>(&self, cx: &mut C) -> Option<Handle<'a, T>> {
+        let env = cx.env();
+        assert!(env == self.env, "weak handle used outside of the environment that created it");
+        unsafe {
+            let mut local: raw::Local = mem::zeroed();
+            if neon_runtime::mem::get_weak(&mut local, env.to_raw(), self.internal) {
+                Some(Handle::new_internal(env, T::from_raw(local)))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<T: Object> Drop for Weak<T> {
+    fn drop(&mut self) {
+        unsafe {
+            neon_runtime::mem::drop_persistent(self.env.to_raw(), self.internal);
+        }
+    }
+}
+
+extern "C" fn finalize<F: FnOnce()>(data: *mut c_void) {
+    let finalizer: Box<F> = unsafe { Box::from_raw(data as *mut F) };
+    finalizer();
+}
+
+impl<'a, T: Object> Handle<'a, T> {
+    /// Registers a callback that runs once the object has been garbage collected.
+    ///
+    /// The callback runs on the JavaScript thread during or shortly after garbage collection,
+    /// so it must not try to call back into JavaScript. An object can have any number of
+    /// finalizers.
+    pub fn add_finalizer<'b, C: Context<'b>, F: FnOnce() + 'static>(&self, cx: &mut C, finalizer: F) {
+        let env = cx.env();
+        let data = Box::into_raw(Box::new(finalizer)) as *mut c_void;
+        unsafe {
+            neon_runtime::mem::add_finalizer(env.to_raw(), self.to_raw(), data, finalize::<F>);
+        }
+    }
+}
@@ -4,6 +4,15 @@ extern crate neon_runtime;
 extern crate cslice;
 extern crate semver;
 
+#[cfg(feature = "macros")]
+extern crate neon_macros;
+#[cfg(feature = "macros")]
+extern crate linkme;
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde as serde_crate;
+
 #[cfg(test)]
 #[macro_use]
 extern crate lazy_static;
@@ -15,15 +24,43 @@ pub mod object;
 pub mod borrow;
 pub mod result;
 pub mod task;
+pub mod event;
+pub mod future;
+pub mod convert;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod meta;
 pub mod prelude;
 
 #[doc(hidden)]
 pub mod macro_internal;
 
+#[cfg(feature = "macros")]
+pub use neon_macros::{main, export};
+
 #[cfg(all(feature = "legacy-runtime", feature = "napi-runtime"))]
 compile_error!("Cannot enable both `legacy-runtime` and `napi-runtime` features.\n\nTo use `napi-runtime`, disable `legacy-runtime` by setting `default-features` to `false` in Cargo.toml\nor with cargo's --no-default-features flag.");
 
+// Records that the crate registers its own module, so that Neon doesn't register one for
+// the functions marked with `#[neon::export]`.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! register_main {
+    () => {
+        #[$crate::macro_internal::linkme::distributed_slice($crate::macro_internal::MAINS)]
+        #[linkme(crate = $crate::macro_internal::linkme)]
+        static __NEON_MAIN: &'static str = module_path!();
+    }
+}
+
+#[cfg(not(feature = "macros"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! register_main {
+    () => { }
+}
+
 #[cfg(all(feature = "napi-runtime", not(feature = "legacy-runtime")))]
 /// Register the current crate as a Node module, providing startup
 /// logic for initializing the module object at runtime.
@@ -32,6 +69,9 @@ compile_error!("Cannot enable both `legacy-runtime` and `napi-runtime` features.
 /// is usually bound to a mutable variable `mut cx`, which can then be used to
 /// pass to Neon APIs that require mutable access to an execution context.
 ///
+/// The initialization logic runs once for every environment that loads the module,
+/// such as the main thread and each worker thread.
+///
 /// Example:
 ///
 /// ```rust,ignore
@@ -45,16 +85,20 @@ compile_error!("Cannot enable both `legacy-runtime` and `napi-runtime` features.
 #[macro_export]
 macro_rules! register_module {
     ($module:pat, $init:block) => {
+        $crate::register_main!();
+
         #[no_mangle]
         pub unsafe extern "C" fn napi_register_module_v1(
-            _env: $crate::macro_internal::runtime::nodejs_sys::napi_env,
+            env: $crate::macro_internal::runtime::nodejs_sys::napi_env,
             exports: $crate::macro_internal::runtime::nodejs_sys::napi_value
         ) -> $crate::macro_internal::runtime::nodejs_sys::napi_value
         {
+            fn __init_neon_module($module: $crate::context::ModuleContext) -> $crate::result::NeonResult<()> $init
+
             // Suppress the default Rust panic hook, which prints diagnostics to stderr.
             ::std::panic::set_hook(::std::boxed::Box::new(|_| { }));
 
-            $init
+            $crate::macro_internal::initialize_module(env, exports, __init_neon_module);
 
             exports
         }
@@ -69,6 +113,9 @@ macro_rules! register_module {
 /// is usually bound to a mutable variable `mut cx`, which can then be used to
 /// pass to Neon APIs that require mutable access to an execution context.
 ///
+/// The initialization logic runs once for every environment that loads the module,
+/// such as the main thread and each worker thread.
+///
 /// Example:
 ///
 /// ```rust,ignore
@@ -82,6 +129,8 @@ macro_rules! register_module {
 #[macro_export]
 macro_rules! register_module {
     ($module:pat, $init:block) => {
+        $crate::register_main!();
+
         // Mark this function as a global constructor (like C++).
         #[allow(improper_ctypes)]
         #[cfg_attr(target_os = "linux", link_section = ".ctors")]
@@ -100,9 +149,9 @@ macro_rules! register_module {
                     dso_handle: *mut u8,
                     filename: *const u8,
                     register_func: Option<extern "C" fn(
-                        $crate::handle::Handle<$crate::types::JsObject>, *mut u8, *mut u8)>,
+                        $crate::macro_internal::runtime::raw::Local, *mut u8, *mut u8)>,
                     context_register_func: Option<extern "C" fn(
-                        $crate::handle::Handle<$crate::types::JsObject>, *mut u8, *mut u8, *mut u8)>,
+                        $crate::macro_internal::runtime::raw::Local, *mut u8, *mut u8, *mut u8)>,
                     modname: *const u8,
                     priv_data: *mut u8,
                     link: *mut __NodeModule
@@ -115,15 +164,17 @@ macro_rules! register_module {
                     flags: 0,
                     dso_handle: 0 as *mut _,
                     filename: b"neon_source.rs\0" as *const u8,
-                    register_func: Some(__register_neon_module),
-                    context_register_func: None,
+                    // Context-aware modules are initialized once for every environment that
+                    // loads them, which is what allows them to be loaded in worker threads.
+                    register_func: None,
+                    context_register_func: Some(__register_neon_module),
                     modname: b"neon_module\0" as *const u8,
                     priv_data: 0 as *mut _,
                     link: 0 as *mut _
                 };
 
                 extern "C" fn __register_neon_module(
-                        m: $crate::handle::Handle<$crate::types::JsObject>, _: *mut u8, _: *mut u8) {
+                        m: $crate::macro_internal::runtime::raw::Local, _: *mut u8, _: *mut u8, _: *mut u8) {
                     $crate::macro_internal::initialize_module(m, __init_neon_module);
                 }
 
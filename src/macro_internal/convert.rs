@@ -0,0 +1,53 @@
+//! Helpers used by the code generated for `#[derive(FromJs)]` and `#[derive(IntoJs)]`.
+
+use context::Context;
+use convert::{FromJs, IntoJs, ConversionError, ConversionResult};
+use handle::Handle;
+use object::Object;
+use result::NeonResult;
+use types::{JsValue, JsObject, JsArray, JsFunction, JsUndefined};
+
+/// Converts a value to a plain object, which excludes arrays and functions.
+pub fn object<'a>(value: Handle<'a, JsValue>) -> ConversionResult<Handle<'a, JsObject>> {
+    if value.is_a::<JsArray>() || value.is_a::<JsFunction>() {
+        return Err(ConversionError::expected("an object", value));
+    }
+    value.downcast::<JsObject>().map_err(|_| ConversionError::expected("an object", value))
+}
+
+/// Converts the property `key` of an object.
+pub fn get<'a, C: Context<'a>, T: FromJs>(cx: &mut C, object: Handle<'a, JsObject>, key: &str) -> ConversionResult<T> {
+    let value = object.get(cx, key)?;
+    T::from_js(cx, value).map_err(|e| e.context(format!("property {:?}", key)))
+}
+
+/// Converts the property `key` of an object, or produces the default value if the property
+/// is missing or `undefined`. Any other value, including `null`, is converted as usual.
+pub fn get_or_default<'a, C: Context<'a>, T: FromJs + Default>(cx: &mut C, object: Handle<'a, JsObject>, key: &str) -> ConversionResult<T> {
+    let value = object.get(cx, key)?;
+    if value.is_a::<JsUndefined>() {
+        return Ok(T::default());
+    }
+    T::from_js(cx, value).map_err(|e| e.context(format!("property {:?}", key)))
+}
+
+/// Sets the property `key` of an object.
+pub fn set<'a, C: Context<'a>, T: IntoJs>(cx: &mut C, object: Handle<'a, JsObject>, key: &str, value: T) -> NeonResult<()> {
+    let value = value.into_js(cx)?;
+    object.set(cx, key, value)?;
+    Ok(())
+}
+
+/// Sets the property `key` of an object, unless the value is `None`.
+pub fn set_optional<'a, C: Context<'a>, T: IntoJs>(cx: &mut C, object: Handle<'a, JsObject>, key: &str, value: Option<T>) -> NeonResult<()> {
+    match value {
+        Some(value) => set(cx, object, key, value),
+        None => Ok(())
+    }
+}
+
+/// An error for an enum variant name that does not match any of the expected names.
+pub fn unknown_variant(variant: &str, expected: &[&str]) -> ConversionError {
+    let expected = expected.iter().map(|name| format!("{:?}", name)).collect::<Vec<_>>().join(", ");
+    ConversionError::Invalid(format!("unknown variant {:?}, expected one of {}", variant, expected))
+}
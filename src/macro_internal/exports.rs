@@ -0,0 +1,22 @@
+//! Link-time registration of the functions marked with `#[neon::export]`, and of the
+//! module itself when the crate has no `register_module!`.
+
+pub use linkme;
+pub use context::internal::register_default_module;
+
+use linkme::distributed_slice;
+use context::ModuleContext;
+use result::NeonResult;
+
+/// A function that adds an item to the module's exports.
+pub type Export = for<'a, 'b> fn(&'b mut ModuleContext<'a>) -> NeonResult<()>;
+
+/// The items registered with `neon::export`, which are exported before the module's own
+/// initialization logic runs.
+#[distributed_slice]
+pub static EXPORTS: [Export] = [..];
+
+/// The module paths of the `register_module!` invocations in the crate. A crate without any
+/// has its module registered by `register_default_module` instead.
+#[distributed_slice]
+pub static MAINS: [&'static str] = [..];
@@ -5,6 +5,16 @@
 pub use object::class::internal::{AllocateCallback, ConstructCallback, ConstructorCallCallback, MethodCallback};
 pub use context::internal::initialize_module;
 
+// Used by the `FromJs` and `IntoJs` derives.
+pub mod convert;
+
+// Used by the `neon::export` attribute and `register_module!` to register the module's
+// contents at link time.
+#[cfg(feature = "macros")]
+mod exports;
+#[cfg(feature = "macros")]
+pub use self::exports::*;
+
 // An alias for neon_runtime so macros can refer to it.
 pub mod runtime {
     pub use neon_runtime::*;
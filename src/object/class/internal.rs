@@ -6,7 +6,7 @@ use neon_runtime::raw;
 use super::{Class, ClassInternal, Callback};
 use handle::{Handle, Managed};
 use context::{CallbackInfo, CallContext, Context};
-use context::internal::ContextInternal;
+use context::internal::{ContextInternal, Env};
 use result::{NeonResult, JsResult, Throw};
 use types::{JsValue, JsObject, JsFunction, JsUndefined, build};
 use types::error::convert_panics;
@@ -19,16 +19,17 @@ impl<T: Class> Callback<()> for MethodCallback<T> {
         unsafe {
             info.with_cx::<T, _, _>(|mut cx| {
                 let data = info.data();
-                let this: Handle<JsValue> = Handle::new_internal(JsValue::from_raw(info.this(&mut cx)));
+                let env = cx.env();
+                let this: Handle<JsValue> = Handle::new_internal(env, JsValue::from_raw(info.this(&mut cx)));
                 if !this.is_a::<T>() {
                     if let Ok(metadata) = T::metadata(&mut cx) {
-                        neon_runtime::class::throw_this_error(mem::transmute(cx.isolate()), metadata.pointer);
+                        neon_runtime::class::throw_this_error(env.to_raw(), metadata.pointer);
                     }
                     return;
                 };
                 let dynamic_callback: fn(CallContext<T>) -> JsResult<JsValue> =
                     mem::transmute(neon_runtime::fun::get_dynamic_callback(data.to_raw()));
-                if let Ok(value) = convert_panics(|| { dynamic_callback(cx) }) {
+                if let Ok(value) = convert_panics(env, || { dynamic_callback(cx) }) {
                     info.set_return(value);
                 }
             })
@@ -48,7 +49,7 @@ impl ConstructorCallCallback {
         fn callback<T: Class>(mut cx: CallContext<JsValue>) -> JsResult<JsValue> {
             unsafe {
                 if let Ok(metadata) = T::metadata(&mut cx) {
-                    neon_runtime::class::throw_call_error(mem::transmute(cx.isolate()), metadata.pointer);
+                    neon_runtime::class::throw_call_error(cx.env().to_raw(), metadata.pointer);
                 }
             }
             Err(Throw)
@@ -63,9 +64,10 @@ impl Callback<()> for ConstructorCallCallback {
         unsafe {
             info.with_cx(|cx| {
                 let data = info.data();
+                let env = cx.env();
                 let kernel: fn(CallContext<JsValue>) -> JsResult<JsValue> =
                     mem::transmute(neon_runtime::class::get_call_kernel(data.to_raw()));
-                if let Ok(value) = convert_panics(|| { kernel(cx) }) {
+                if let Ok(value) = convert_panics(env, || { kernel(cx) }) {
                     info.set_return(value);
                 }
             })
@@ -85,9 +87,10 @@ impl<T: Class> Callback<*mut c_void> for AllocateCallback<T> {
         unsafe {
             info.with_cx(|cx| {
                 let data = info.data();
+                let env = cx.env();
                 let kernel: fn(CallContext<JsUndefined>) -> NeonResult<T::Internals> =
                     mem::transmute(neon_runtime::class::get_allocate_kernel(data.to_raw()));
-                if let Ok(value) = convert_panics(|| { kernel(cx) }) {
+                if let Ok(value) = convert_panics(env, || { kernel(cx) }) {
                     let p = Box::into_raw(Box::new(value));
                     mem::transmute(p)
                 } else {
@@ -110,9 +113,10 @@ impl<T: Class> Callback<bool> for ConstructCallback<T> {
         unsafe {
             info.with_cx(|cx| {
                 let data = info.data();
+                let env = cx.env();
                 let kernel: fn(CallContext<T>) -> NeonResult<Option<Handle<JsObject>>> =
                     mem::transmute(neon_runtime::class::get_construct_kernel(data.to_raw()));
-                match convert_panics(|| { kernel(cx) }) {
+                match convert_panics(env, || { kernel(cx) }) {
                     Ok(None) => true,
                     Ok(Some(obj)) => {
                         info.set_return(obj);
@@ -137,12 +141,13 @@ pub struct ClassMetadata {
 
 impl ClassMetadata {
     pub unsafe fn constructor<'a, T: Class, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsFunction<T>> {
-        build(|out| {
-            neon_runtime::class::metadata_to_constructor(out, mem::transmute(cx.isolate()), self.pointer)
+        let env = cx.env();
+        build(env, |out| {
+            neon_runtime::class::metadata_to_constructor(out, env.to_raw(), self.pointer)
         })
     }
 
-    pub unsafe fn has_instance(&self, value: raw::Local) -> bool {
-        neon_runtime::class::has_instance(self.pointer, value)
+    pub unsafe fn has_instance(&self, env: Env, value: raw::Local) -> bool {
+        neon_runtime::class::has_instance(env.to_raw(), self.pointer, value)
     }
 }
@@ -1,10 +1,11 @@
 //! A convenience module that re-exports the most commonly-used Neon APIs.
 
 pub use handle::Handle;
-pub use types::{JsBuffer, JsArrayBuffer, BinaryData, JsError, Value, JsValue, JsUndefined, JsNull, JsBoolean, JsString, JsNumber, JsObject, JsArray, JsFunction};
+pub use types::{JsBuffer, JsArrayBuffer, JsTypedArray, JsDataView, BinaryData, JsError, JsPromise, Value, JsValue, JsUndefined, JsNull, JsBoolean, JsString, JsNumber, JsBigInt, JsSymbol, JsObject, JsDate, JsArray, JsFunction};
 pub use object::{Object, Class};
 pub use borrow::{Borrow, BorrowMut};
 pub use context::{CallKind, Context, ModuleContext, ExecuteContext, ComputeContext, CallContext, FunctionContext, MethodContext, TaskContext};
 pub use result::{NeonResult, JsResult, JsResultExt};
+pub use convert::{FromJs, IntoJs};
 pub use task::Task;
 pub use crate::{register_module, declare_types};
@@ -0,0 +1,244 @@
+//! Deserialization of Rust values from JavaScript values.
+
+use std::vec;
+
+use serde_crate::de::{self, Visitor, DeserializeSeed, IntoDeserializer};
+
+use context::Context;
+use handle::Handle;
+use object::Object;
+use types::{JsValue, JsArray, JsObject, JsBuffer, JsArrayBuffer, JsBoolean, JsNumber, JsString, JsNull, JsUndefined};
+use super::Error;
+
+type Result<T> = ::std::result::Result<T, Error>;
+
+// The range of integers that a JavaScript number can represent exactly.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+// How deeply arrays and objects can be nested, which also stops the deserializer from
+// recursing forever into an object that refers to itself.
+const MAX_DEPTH: usize = 128;
+
+/// Deserializes a Rust value from a JavaScript value.
+pub(crate) struct Deserializer<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    input: Handle<'a, JsValue>,
+    depth: usize
+}
+
+impl<'a, 'b, C: Context<'a>> Deserializer<'a, 'b, C> {
+    pub(crate) fn new(cx: &'b mut C, input: Handle<'a, JsValue>) -> Self {
+        Deserializer { cx, input, depth: 0 }
+    }
+
+    /// Produces the depth of the values inside of the input, which is an array or an object.
+    fn nested_depth(&self) -> Result<usize> {
+        if self.depth >= MAX_DEPTH {
+            return Err(Error::Message(format!(
+                "JavaScript value is nested more than {} levels deep, or refers to itself", MAX_DEPTH)));
+        }
+        Ok(self.depth + 1)
+    }
+
+    fn is_nullish(&self) -> bool {
+        self.input.is_a::<JsNull>() || self.input.is_a::<JsUndefined>()
+    }
+
+    fn bytes(&mut self) -> Option<Vec<u8>> {
+        if let Ok(buffer) = self.input.downcast::<JsBuffer>() {
+            Some(self.cx.borrow(&buffer, |data| data.as_slice::<u8>().to_vec()))
+        } else if let Ok(buffer) = self.input.downcast::<JsArrayBuffer>() {
+            Some(self.cx.borrow(&buffer, |data| data.as_slice::<u8>().to_vec()))
+        } else {
+            None
+        }
+    }
+}
+
+fn unexpected(input: Handle<JsValue>) -> Error {
+    let kind = if input.is_a::<JsArray>() {
+        "array"
+    } else if input.is_a::<JsObject>() {
+        "object"
+    } else {
+        "value"
+    };
+    Error::Message(format!("unsupported JavaScript {}", kind))
+}
+
+impl<'de, 'a, 'b, C: Context<'a>> de::Deserializer<'de> for Deserializer<'a, 'b, C> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
+        if self.is_nullish() {
+            visitor.visit_unit()
+        } else if let Ok(b) = self.input.downcast::<JsBoolean>() {
+            visitor.visit_bool(b.value())
+        } else if let Ok(n) = self.input.downcast::<JsNumber>() {
+            let n = n.value();
+            if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER {
+                if n < 0.0 { visitor.visit_i64(n as i64) } else { visitor.visit_u64(n as u64) }
+            } else {
+                visitor.visit_f64(n)
+            }
+        } else if let Ok(s) = self.input.downcast::<JsString>() {
+            visitor.visit_string(s.value())
+        } else if let Ok(array) = self.input.downcast::<JsArray>() {
+            let depth = self.nested_depth()?;
+            let elements = array.to_vec(self.cx)?;
+            visitor.visit_seq(SeqAccess { cx: self.cx, elements: elements.into_iter(), depth })
+        } else if let Some(bytes) = self.bytes() {
+            visitor.visit_byte_buf(bytes)
+        } else if let Ok(object) = self.input.downcast::<JsObject>() {
+            let depth = self.nested_depth()?;
+            // As with `JSON.stringify`, only own enumerable string keys become map entries.
+            let keys = object.get_own_property_names(self.cx)?.to_vec(self.cx)?;
+            visitor.visit_map(MapAccess { cx: self.cx, object, keys: keys.into_iter(), value: None, depth })
+        } else {
+            Err(unexpected(self.input))
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_nullish() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
+        match self.bytes() {
+            Some(bytes) => visitor.visit_byte_buf(bytes),
+            None => self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        if let Ok(variant) = self.input.downcast::<JsString>() {
+            return visitor.visit_enum(variant.value().into_deserializer());
+        }
+
+        // Any other variant is an object with the variant name as its only key.
+        let object = match self.input.downcast::<JsObject>() {
+            Ok(object) => object,
+            Err(_) => return Err(Error::Message("expected a string or an object for an enum".to_string()))
+        };
+        let depth = self.nested_depth()?;
+        let mut keys = object.get_own_property_names(self.cx)?.to_vec(self.cx)?;
+        if keys.len() != 1 {
+            return Err(Error::Message("expected an object with a single key for an enum".to_string()));
+        }
+        let variant = keys.pop().unwrap();
+        let value = object.get(self.cx, variant)?;
+        visitor.visit_enum(EnumAccess { cx: self.cx, variant, value, depth })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Deserializes the elements of an array.
+struct SeqAccess<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    elements: vec::IntoIter<Handle<'a, JsValue>>,
+    depth: usize
+}
+
+impl<'de, 'a, 'b, C: Context<'a>> de::SeqAccess<'de> for SeqAccess<'a, 'b, C> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.elements.next() {
+            Some(element) => seed.deserialize(Deserializer { cx: self.cx, input: element, depth: self.depth }).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+/// Deserializes the own properties of an object.
+struct MapAccess<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    object: Handle<'a, JsObject>,
+    keys: vec::IntoIter<Handle<'a, JsValue>>,
+    value: Option<Handle<'a, JsValue>>,
+    depth: usize
+}
+
+impl<'de, 'a, 'b, C: Context<'a>> de::MapAccess<'de> for MapAccess<'a, 'b, C> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.keys.next() {
+            Some(key) => {
+                self.value = Some(self.object.get(self.cx, key)?);
+                seed.deserialize(Deserializer { cx: self.cx, input: key, depth: self.depth }).map(Some)
+            }
+            None => Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(Deserializer { cx: self.cx, input: value, depth: self.depth }),
+            None => Err(Error::Message("map value deserialized before its key".to_string()))
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len())
+    }
+}
+
+/// Deserializes an enum variant represented as an object with a single key.
+struct EnumAccess<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    variant: Handle<'a, JsValue>,
+    value: Handle<'a, JsValue>,
+    depth: usize
+}
+
+impl<'de, 'a, 'b, C: Context<'a>> de::EnumAccess<'de> for EnumAccess<'a, 'b, C> {
+    type Error = Error;
+    type Variant = Deserializer<'a, 'b, C>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let EnumAccess { cx, variant, value, depth } = self;
+        let variant = seed.deserialize(Deserializer { cx: &mut *cx, input: variant, depth })?;
+        Ok((variant, Deserializer { cx, input: value, depth }))
+    }
+}
+
+impl<'de, 'a, 'b, C: Context<'a>> de::VariantAccess<'de> for Deserializer<'a, 'b, C> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,108 @@
+//! Conversions between Rust values and JavaScript values using [Serde](https://serde.rs).
+//!
+//! This module is only available with the `serde` feature.
+//!
+//! Values are represented the same way `serde_json` represents them:
+//!
+//! - numbers, strings and booleans become the corresponding primitives, with all integer
+//!   types becoming JavaScript numbers;
+//! - `None` and `()` become `null`;
+//! - sequences and tuples become arrays, and byte arrays become `Buffer`s;
+//! - structs and maps become plain objects, with map keys converted to strings;
+//! - enum variants are externally tagged, so a unit variant becomes its name as a string and
+//!   any other variant becomes an object with the variant name as its only key.
+//!
+//! # Example:
+//!
+//! ```no_run
+//! # extern crate neon;
+//! # extern crate serde_derive;
+//! # use neon::prelude::*;
+//! # use serde_derive::{Serialize, Deserialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64
+//! }
+//!
+//! fn translate(mut cx: FunctionContext) -> JsResult<JsValue> {
+//!     let arg = cx.argument::<JsValue>(0)?;
+//!     let mut point: Point = neon::serde::from_value(&mut cx, arg)?;
+//!     point.x += 1.0;
+//!     neon::serde::to_value(&mut cx, &point)
+//! }
+//! # fn main() { }
+//! ```
+
+mod de;
+mod ser;
+
+use std::error;
+use std::fmt;
+
+use serde_crate::{de as serde_de, ser as serde_ser};
+use serde_crate::de::DeserializeOwned;
+use serde_crate::ser::Serialize;
+
+use context::Context;
+use handle::{Handle, Managed};
+use result::{NeonResult, JsResult, Throw};
+use types::{Value, JsValue};
+
+/// Converts a Rust value to a JavaScript value. Throws an `Error` if the value cannot be
+/// represented in JavaScript.
+pub fn to_value<'a, C: Context<'a>, T: Serialize + ?Sized>(cx: &mut C, value: &T) -> JsResult<'a, JsValue> {
+    match value.serialize(ser::Serializer::new(cx)) {
+        Ok(v) => Ok(v),
+        Err(Error::Throw) => Err(Throw),
+        Err(Error::Message(message)) => cx.throw_error(message)
+    }
+}
+
+/// Converts a JavaScript value to a Rust value. Throws a `TypeError` if the value does not
+/// have the shape the Rust type expects.
+pub fn from_value<'a, C: Context<'a>, T: DeserializeOwned, V: Value>(cx: &mut C, value: Handle<V>) -> NeonResult<T> {
+    let value = JsValue::new_internal(cx.env(), value.to_raw());
+    match T::deserialize(de::Deserializer::new(cx, value)) {
+        Ok(v) => Ok(v),
+        Err(Error::Throw) => Err(Throw),
+        Err(Error::Message(message)) => cx.throw_type_error(message)
+    }
+}
+
+/// An error produced during a conversion, which is either a JavaScript exception that is
+/// already pending or a message for an exception that has yet to be thrown.
+#[derive(Debug)]
+pub(crate) enum Error {
+    Throw,
+    Message(String)
+}
+
+impl From<Throw> for Error {
+    fn from(_: Throw) -> Self {
+        Error::Throw
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Throw => fmt::Display::fmt(&Throw, f),
+            Error::Message(ref message) => f.write_str(message)
+        }
+    }
+}
+
+impl error::Error for Error { }
+
+impl serde_ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl serde_de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
@@ -0,0 +1,302 @@
+//! Serialization of Rust values to JavaScript values.
+
+use std::marker::PhantomData;
+
+use serde_crate::ser::{self, Serialize};
+
+use context::Context;
+use handle::Handle;
+use object::Object;
+use types::{Value, JsValue, JsArray, JsObject, JsBuffer, JsBoolean, JsNumber, JsString, JsNull};
+use super::Error;
+
+type Result<'a> = ::std::result::Result<Handle<'a, JsValue>, Error>;
+
+/// Serializes a Rust value into a new JavaScript value.
+pub(crate) struct Serializer<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    phantom: PhantomData<&'a ()>
+}
+
+impl<'a, 'b, C: Context<'a>> Serializer<'a, 'b, C> {
+    pub(crate) fn new(cx: &'b mut C) -> Self {
+        Serializer { cx, phantom: PhantomData }
+    }
+}
+
+fn serialize<'a, C: Context<'a>, T: Serialize + ?Sized>(cx: &mut C, value: &T) -> Result<'a> {
+    value.serialize(Serializer::new(cx))
+}
+
+/// Wraps `value` in an object with `variant` as its only key, the representation of an
+/// externally tagged enum variant.
+fn tagged<'a, C: Context<'a>>(cx: &mut C, variant: &str, value: Handle<'a, JsValue>) -> Result<'a> {
+    let object = JsObject::new(cx);
+    object.set(cx, variant, value)?;
+    Ok(object.upcast())
+}
+
+impl<'a, 'b, C: Context<'a>> ser::Serializer for Serializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer<'a, 'b, C>;
+    type SerializeTuple = ArraySerializer<'a, 'b, C>;
+    type SerializeTupleStruct = ArraySerializer<'a, 'b, C>;
+    type SerializeTupleVariant = ArraySerializer<'a, 'b, C>;
+    type SerializeMap = ObjectSerializer<'a, 'b, C>;
+    type SerializeStruct = ObjectSerializer<'a, 'b, C>;
+    type SerializeStructVariant = ObjectSerializer<'a, 'b, C>;
+
+    fn serialize_bool(self, v: bool) -> Result<'a> {
+        Ok(JsBoolean::new(self.cx, v).upcast())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_i16(self, v: i16) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_i32(self, v: i32) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_i64(self, v: i64) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_u8(self, v: u8) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_u16(self, v: u16) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_u32(self, v: u32) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_u64(self, v: u64) -> Result<'a> { self.serialize_f64(v as f64) }
+    fn serialize_f32(self, v: f32) -> Result<'a> { self.serialize_f64(v as f64) }
+
+    fn serialize_f64(self, v: f64) -> Result<'a> {
+        Ok(JsNumber::new(self.cx, v).upcast())
+    }
+
+    fn serialize_char(self, v: char) -> Result<'a> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<'a> {
+        match JsString::new_internal(self.cx.env(), v) {
+            Some(s) => Ok(s.upcast()),
+            None => Err(Error::Message(format!("string size out of range: {}", v.len())))
+        }
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<'a> {
+        let mut buffer = JsBuffer::new(self.cx, v.len() as u32)?;
+        self.cx.borrow_mut(&mut buffer, |data| {
+            data.as_mut_slice::<u8>().copy_from_slice(v);
+        });
+        Ok(buffer.upcast())
+    }
+
+    fn serialize_none(self) -> Result<'a> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<'a> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<'a> {
+        Ok(JsNull::new_internal(self.cx.env()).upcast())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<'a> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<'a> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<'a> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<'a> {
+        let value = serialize(self.cx, value)?;
+        tagged(self.cx, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> ::std::result::Result<Self::SerializeSeq, Error> {
+        Ok(ArraySerializer::new(self.cx, len.unwrap_or(0), None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> ::std::result::Result<Self::SerializeTuple, Error> {
+        Ok(ArraySerializer::new(self.cx, len, None))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> ::std::result::Result<Self::SerializeTupleStruct, Error> {
+        Ok(ArraySerializer::new(self.cx, len, None))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, len: usize) -> ::std::result::Result<Self::SerializeTupleVariant, Error> {
+        Ok(ArraySerializer::new(self.cx, len, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> ::std::result::Result<Self::SerializeMap, Error> {
+        Ok(ObjectSerializer::new(self.cx, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> ::std::result::Result<Self::SerializeStruct, Error> {
+        Ok(ObjectSerializer::new(self.cx, None))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, _len: usize) -> ::std::result::Result<Self::SerializeStructVariant, Error> {
+        Ok(ObjectSerializer::new(self.cx, Some(variant)))
+    }
+}
+
+/// Serializes sequences and tuples into an array.
+pub(crate) struct ArraySerializer<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    array: Handle<'a, JsArray>,
+    len: u32,
+    variant: Option<&'static str>
+}
+
+impl<'a, 'b, C: Context<'a>> ArraySerializer<'a, 'b, C> {
+    fn new(cx: &'b mut C, capacity: usize, variant: Option<&'static str>) -> Self {
+        let array = JsArray::new(cx, capacity as u32);
+        ArraySerializer { cx, array, len: 0, variant }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> ::std::result::Result<(), Error> {
+        let value = serialize(self.cx, value)?;
+        self.array.set(self.cx, self.len, value)?;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<'a> {
+        match self.variant {
+            Some(variant) => tagged(self.cx, variant, self.array.upcast()),
+            None => Ok(self.array.upcast())
+        }
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeSeq for ArraySerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> ::std::result::Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeTuple for ArraySerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> ::std::result::Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeTupleStruct for ArraySerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> ::std::result::Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeTupleVariant for ArraySerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> ::std::result::Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
+
+/// Serializes maps and structs into a plain object.
+pub(crate) struct ObjectSerializer<'a, 'b, C: Context<'a> + 'b> {
+    cx: &'b mut C,
+    object: Handle<'a, JsObject>,
+    key: Option<Handle<'a, JsString>>,
+    variant: Option<&'static str>
+}
+
+impl<'a, 'b, C: Context<'a>> ObjectSerializer<'a, 'b, C> {
+    fn new(cx: &'b mut C, variant: Option<&'static str>) -> Self {
+        let object = JsObject::new(cx);
+        ObjectSerializer { cx, object, key: None, variant }
+    }
+
+    fn finish(self) -> Result<'a> {
+        match self.variant {
+            Some(variant) => tagged(self.cx, variant, self.object.upcast()),
+            None => Ok(self.object.upcast())
+        }
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeMap for ObjectSerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> ::std::result::Result<(), Error> {
+        // Like JSON, objects only have string keys, so convert keys such as numbers up front.
+        let key = serialize(self.cx, key)?;
+        self.key = Some(key.to_string(self.cx)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> ::std::result::Result<(), Error> {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(Error::Message("map value serialized before its key".to_string()))
+        };
+        let value = serialize(self.cx, value)?;
+        self.object.set(self.cx, key, value)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeStruct for ObjectSerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> ::std::result::Result<(), Error> {
+        let value = serialize(self.cx, value)?;
+        self.object.set(self.cx, key, value)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> ser::SerializeStructVariant for ObjectSerializer<'a, 'b, C> {
+    type Ok = Handle<'a, JsValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> ::std::result::Result<(), Error> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<'a> {
+        self.finish()
+    }
+}
@@ -4,10 +4,11 @@ use std::marker::{Send, Sized};
 use std::mem;
 use std::os::raw::c_void;
 
-use types::{Value, JsFunction};
+use types::{Value, JsFunction, JsPromise};
 use result::JsResult;
 use handle::{Handle, Managed};
-use context::TaskContext;
+use context::{Context, TaskContext};
+use context::internal::Env;
 use neon_runtime;
 use neon_runtime::raw;
 
@@ -40,12 +41,34 @@ pub trait Task: Send + Sized + 'static {
         let self_raw = Box::into_raw(boxed_self);
         let callback_raw = callback.to_raw();
         unsafe {
-            neon_runtime::task::schedule(mem::transmute(self_raw),
+            neon_runtime::task::schedule(callback.env().to_raw(),
+                                         mem::transmute(self_raw),
                                          perform_task::<Self>,
                                          complete_task::<Self>,
                                          callback_raw);
         }
     }
+
+    /// Schedule a task to be executed on a background thread, producing a promise for its
+    /// result.
+    ///
+    /// The promise resolves with the value produced by `complete`, or rejects with the
+    /// exception thrown by `complete`.
+    fn promise<'a, C: Context<'a>>(self, cx: &mut C) -> Handle<'a, JsPromise> {
+        let env = cx.env();
+        let boxed_self = Box::new(self);
+        let self_raw = Box::into_raw(boxed_self);
+        unsafe {
+            let mut promise: raw::Local = mem::zeroed();
+            let deferred = neon_runtime::promise::new(&mut promise, env.to_raw());
+            neon_runtime::task::schedule_promise(env.to_raw(),
+                                                 mem::transmute(self_raw),
+                                                 perform_task::<Self>,
+                                                 complete_task::<Self>,
+                                                 deferred);
+            Handle::new_internal(env, JsPromise::from_raw(promise))
+        }
+    }
 }
 
 unsafe extern "C" fn perform_task<T: Task>(task: *mut c_void) -> *mut c_void {
@@ -58,7 +81,7 @@ unsafe extern "C" fn perform_task<T: Task>(task: *mut c_void) -> *mut c_void {
 unsafe extern "C" fn complete_task<T: Task>(task: *mut c_void, result: *mut c_void, out: &mut raw::Local) {
     let result: Result<T::Output, T::Error> = *Box::from_raw(mem::transmute(result));
     let task: Box<T> = Box::from_raw(mem::transmute(task));
-    TaskContext::with(|cx| {
+    TaskContext::with(Env::current(), |cx| {
         if let Ok(result) = task.complete(cx, result) {
             *out = result.to_raw();
         }
@@ -0,0 +1,153 @@
+//! Types and traits representing JavaScript `BigInt` values.
+
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use neon_runtime;
+use neon_runtime::raw;
+
+use context::Context;
+use context::internal::Env;
+use handle::Handle;
+use result::JsResult;
+use types::{Value, Managed, build};
+use types::internal::ValueInternal;
+
+/// A JavaScript `BigInt` primitive value.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct JsBigInt(raw::Local);
+
+/// An error produced when a `JsBigInt` does not fit in the requested integer type. The
+/// error carries the value truncated to the requested type, the way JavaScript's
+/// `BigInt.asIntN` and `BigInt.asUintN` truncate.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct BigIntOverflow<T>(T);
+
+impl<T: Copy> BigIntOverflow<T> {
+    /// The truncated value.
+    pub fn truncated(&self) -> T {
+        self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for BigIntOverflow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BigInt out of range (truncated to {})", self.0)
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> Error for BigIntOverflow<T> { }
+
+impl Value for JsBigInt { }
+
+impl Managed for JsBigInt {
+    fn to_raw(self) -> raw::Local { self.0 }
+
+    fn from_raw(h: raw::Local) -> Self { JsBigInt(h) }
+}
+
+impl ValueInternal for JsBigInt {
+    fn name() -> String { "BigInt".to_string() }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_bigint(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl JsBigInt {
+    /// Creates a `BigInt` from an `i64`.
+    pub fn from_i64<'a, C: Context<'a>>(cx: &mut C, v: i64) -> JsResult<'a, JsBigInt> {
+        let env = cx.env();
+        build(env, |out| { unsafe { neon_runtime::bigint::new_i64(out, env.to_raw(), v) } })
+    }
+
+    /// Creates a `BigInt` from a `u64`.
+    pub fn from_u64<'a, C: Context<'a>>(cx: &mut C, v: u64) -> JsResult<'a, JsBigInt> {
+        let env = cx.env();
+        build(env, |out| { unsafe { neon_runtime::bigint::new_u64(out, env.to_raw(), v) } })
+    }
+
+    /// Creates a `BigInt` from an `i128`.
+    pub fn from_i128<'a, C: Context<'a>>(cx: &mut C, v: i128) -> JsResult<'a, JsBigInt> {
+        let magnitude = if v < 0 { (v as u128).wrapping_neg() } else { v as u128 };
+        JsBigInt::from_magnitude(cx.env(), v < 0, magnitude)
+    }
+
+    /// Creates a `BigInt` from a `u128`.
+    pub fn from_u128<'a, C: Context<'a>>(cx: &mut C, v: u128) -> JsResult<'a, JsBigInt> {
+        JsBigInt::from_magnitude(cx.env(), false, v)
+    }
+
+    fn from_magnitude<'a>(env: Env, negative: bool, magnitude: u128) -> JsResult<'a, JsBigInt> {
+        let words = [magnitude as u64, (magnitude >> 64) as u64];
+        build(env, |out| { unsafe {
+            neon_runtime::bigint::new_words(out, env.to_raw(), negative, words.len(), words.as_ptr())
+        } })
+    }
+}
+
+impl<'a> Handle<'a, JsBigInt> {
+    /// Converts the `BigInt` to an `i64`, failing if the conversion would lose information.
+    pub fn to_i64(self) -> Result<i64, BigIntOverflow<i64>> {
+        unsafe {
+            let mut v: i64 = 0;
+            if neon_runtime::bigint::to_i64(self.env().to_raw(), &mut v, self.to_raw()) {
+                Ok(v)
+            } else {
+                Err(BigIntOverflow(v))
+            }
+        }
+    }
+
+    /// Converts the `BigInt` to a `u64`, failing if the conversion would lose information.
+    pub fn to_u64(self) -> Result<u64, BigIntOverflow<u64>> {
+        unsafe {
+            let mut v: u64 = 0;
+            if neon_runtime::bigint::to_u64(self.env().to_raw(), &mut v, self.to_raw()) {
+                Ok(v)
+            } else {
+                Err(BigIntOverflow(v))
+            }
+        }
+    }
+
+    /// Converts the `BigInt` to an `i128`, failing if the conversion would lose information.
+    pub fn to_i128(self) -> Result<i128, BigIntOverflow<i128>> {
+        let (negative, magnitude, fits) = self.magnitude();
+        let v = if negative { (magnitude as i128).wrapping_neg() } else { magnitude as i128 };
+        let limit = if negative { 1u128 << 127 } else { i128::max_value() as u128 };
+        if fits && magnitude <= limit {
+            Ok(v)
+        } else {
+            Err(BigIntOverflow(v))
+        }
+    }
+
+    /// Converts the `BigInt` to a `u128`, failing if the conversion would lose information.
+    pub fn to_u128(self) -> Result<u128, BigIntOverflow<u128>> {
+        let (negative, magnitude, fits) = self.magnitude();
+        let v = if negative { magnitude.wrapping_neg() } else { magnitude };
+        if fits && (!negative || magnitude == 0) {
+            Ok(v)
+        } else {
+            Err(BigIntOverflow(v))
+        }
+    }
+
+    /// Reads the sign and the low 128 bits of the magnitude, along with whether the
+    /// magnitude fits entirely in those bits.
+    fn magnitude(self) -> (bool, u128, bool) {
+        unsafe {
+            let mut negative = false;
+            let mut words: [u64; 2] = mem::zeroed();
+            let needed = neon_runtime::bigint::to_words(self.env().to_raw(),
+                                                        &mut negative,
+                                                        words.as_mut_ptr(),
+                                                        words.len(),
+                                                        self.to_raw());
+            let magnitude = (words[0] as u128) | ((words[1] as u128) << 64);
+            (negative, magnitude, needed <= words.len())
+        }
+    }
+}
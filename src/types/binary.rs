@@ -3,16 +3,20 @@
 use std::marker::PhantomData;
 use std::mem::{self, MaybeUninit};
 use std::os::raw::c_void;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
 use std::slice;
 use context::{Context, Lock};
+use context::internal::Env;
 use borrow::{Borrow, BorrowMut, Ref, RefMut, LoanError};
 use borrow::internal::Pointer;
-use handle::Managed;
+use handle::{Handle, Managed};
 use types::{Value, Object, build};
 use types::internal::ValueInternal;
-use result::JsResult;
+use result::{NeonResult, JsResult};
 use neon_runtime;
 use neon_runtime::raw;
+use neon_runtime::raw::TypedArrayType;
 
 /// The Node [`Buffer`](https://nodejs.org/api/buffer.html) type.
 #[repr(C)]
@@ -22,13 +26,36 @@ pub struct JsBuffer(raw::Local);
 impl JsBuffer {
 
     /// Constructs a new `Buffer` object, safely zero-filled.
-    pub fn new<'a, C: Context<'a>>(_: &mut C, size: u32) -> JsResult<'a, JsBuffer> {
-        build(|out| { unsafe { neon_runtime::buffer::new(out, size) } })
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, size: u32) -> JsResult<'a, JsBuffer> {
+        let env = cx.env();
+        build(env, |out| { unsafe { neon_runtime::buffer::new(out, env.to_raw(), size) } })
     }
 
-    /// Constructs a new `Buffer` object, safely zero-filled.
-    pub unsafe fn uninitialized<'a, C: Context<'a>>(_: &mut C, size: u32) -> JsResult<'a, JsBuffer> {
-        build(|out| { neon_runtime::buffer::uninitialized(out, size) })
+    /// Constructs a new `Buffer` object whose contents are left uninitialized.
+    pub unsafe fn uninitialized<'a, C: Context<'a>>(cx: &mut C, size: u32) -> JsResult<'a, JsBuffer> {
+        let env = cx.env();
+        build(env, |out| { neon_runtime::buffer::uninitialized(out, env.to_raw(), size) })
+    }
+
+    /// Constructs a new `Buffer` object that takes ownership of `data` and exposes its bytes
+    /// directly, without copying. The data is dropped once the `Buffer` is garbage collected,
+    /// which may happen on another thread.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// # use neon::prelude::*;
+    /// # fn decode_frame(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    /// let frame: Vec<u8> = vec![0; 1024 * 1024];
+    /// JsBuffer::external(&mut cx, frame)
+    /// # }
+    /// ```
+    pub fn external<'a, C: Context<'a>, T: AsMut<[u8]> + Send + 'static>(cx: &mut C, data: T) -> JsResult<'a, JsBuffer> {
+        let env = cx.env();
+        let (base, length, data) = external_data(data);
+        build(env, |out| { unsafe {
+            neon_runtime::buffer::new_external(out, env.to_raw(), base, length, data, drop_external::<T>)
+        } })
     }
 
 }
@@ -42,8 +69,8 @@ impl Managed for JsBuffer {
 impl ValueInternal for JsBuffer {
     fn name() -> String { "Buffer".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_buffer(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_buffer(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -51,6 +78,21 @@ impl Value for JsBuffer { }
 
 impl Object for JsBuffer { }
 
+/// Moves the owner of external memory to the heap, producing the address and length of the
+/// memory along with the pointer that `drop_external` later frees.
+fn external_data<T: AsMut<[u8]>>(data: T) -> (*mut c_void, usize, *mut c_void) {
+    let mut data = Box::new(data);
+    let (base, length) = {
+        let bytes = (*data).as_mut();
+        (bytes.as_mut_ptr() as *mut c_void, bytes.len())
+    };
+    (base, length, Box::into_raw(data) as *mut c_void)
+}
+
+extern "C" fn drop_external<T>(data: *mut c_void) {
+    unsafe { Box::from_raw(data as *mut T); }
+}
+
 /// The standard JS [`ArrayBuffer`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/ArrayBuffer) type.
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -60,7 +102,19 @@ impl JsArrayBuffer {
 
     /// Constructs a new `ArrayBuffer` object with the given size, in bytes.
     pub fn new<'a, C: Context<'a>>(cx: &mut C, size: u32) -> JsResult<'a, JsArrayBuffer> {
-        build(|out| { unsafe { neon_runtime::arraybuffer::new(out, mem::transmute(cx.isolate()), size) } })
+        let env = cx.env();
+        build(env, |out| { unsafe { neon_runtime::arraybuffer::new(out, env.to_raw(), size) } })
+    }
+
+    /// Constructs a new `ArrayBuffer` object that takes ownership of `data` and exposes its
+    /// bytes directly, without copying. The data is dropped once the `ArrayBuffer` is garbage
+    /// collected, which may happen on another thread.
+    pub fn external<'a, C: Context<'a>, T: AsMut<[u8]> + Send + 'static>(cx: &mut C, data: T) -> JsResult<'a, JsArrayBuffer> {
+        let env = cx.env();
+        let (base, length, data) = external_data(data);
+        build(env, |out| { unsafe {
+            neon_runtime::arraybuffer::new_external(out, env.to_raw(), base, length, data, drop_external::<T>)
+        } })
     }
 
 }
@@ -74,8 +128,8 @@ impl Managed for JsArrayBuffer {
 impl ValueInternal for JsArrayBuffer {
     fn name() -> String { "ArrayBuffer".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_arraybuffer(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_arraybuffer(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -100,21 +154,29 @@ unsafe impl<'a> Pointer for BinaryData<'a> {
     unsafe fn as_mut(&mut self) -> *mut c_void {
         self.base
     }
+
+    fn size(&self) -> usize {
+        self.size
+    }
 }
 
 /// The trait for element types by which a buffer's binary data can be indexed.
-pub trait BinaryViewType: Sized { }
-
-impl BinaryViewType for u8 { }
-impl BinaryViewType for i8 { }
-impl BinaryViewType for u16 { }
-impl BinaryViewType for i16 { }
-impl BinaryViewType for u32 { }
-impl BinaryViewType for i32 { }
-impl BinaryViewType for u64 { }
-impl BinaryViewType for i64 { }
-impl BinaryViewType for f32 { }
-impl BinaryViewType for f64 { }
+pub trait BinaryViewType: Sized + 'static {
+    /// The kind of typed array whose elements have this type.
+    #[doc(hidden)]
+    const TYPED_ARRAY_TYPE: TypedArrayType;
+}
+
+impl BinaryViewType for u8 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Uint8; }
+impl BinaryViewType for i8 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Int8; }
+impl BinaryViewType for u16 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Uint16; }
+impl BinaryViewType for i16 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Int16; }
+impl BinaryViewType for u32 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Uint32; }
+impl BinaryViewType for i32 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Int32; }
+impl BinaryViewType for u64 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::BigUint64; }
+impl BinaryViewType for i64 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::BigInt64; }
+impl BinaryViewType for f32 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Float32; }
+impl BinaryViewType for f64 { const TYPED_ARRAY_TYPE: TypedArrayType = TypedArrayType::Float64; }
 
 impl<'a> BinaryData<'a> {
 
@@ -177,7 +239,7 @@ impl<'a> Borrow for &'a JsBuffer {
         // Initialize pointer
         unsafe {
             let pointer = data.as_mut_ptr();
-            (*pointer).size = neon_runtime::buffer::data(&mut (*pointer).base, self.to_raw());
+            (*pointer).size = neon_runtime::buffer::data(guard.env.to_raw(), &mut (*pointer).base, self.to_raw());
         }
 
         // UB if pointer is not initialized!
@@ -202,7 +264,7 @@ impl<'a> BorrowMut for &'a mut JsBuffer {
         // Initialize pointer
         unsafe {
             let pointer = data.as_mut_ptr();
-            (*pointer).size = neon_runtime::buffer::data(&mut (*pointer).base, self.to_raw());
+            (*pointer).size = neon_runtime::buffer::data(guard.env.to_raw(), &mut (*pointer).base, self.to_raw());
         }
 
         // UB if pointer is not initialized!
@@ -221,7 +283,7 @@ impl<'a> Borrow for &'a JsArrayBuffer {
         // Initialize pointer
         unsafe {
             let pointer = data.as_mut_ptr();
-            (*pointer).size = neon_runtime::arraybuffer::data(&mut (*pointer).base, self.to_raw());
+            (*pointer).size = neon_runtime::arraybuffer::data(guard.env.to_raw(), &mut (*pointer).base, self.to_raw());
         }
 
         // UB if pointer is not initialized!
@@ -246,7 +308,7 @@ impl<'a> BorrowMut for &'a mut JsArrayBuffer {
         // Initialize pointer
         unsafe {
             let pointer = data.as_mut_ptr();
-            (*pointer).size = neon_runtime::arraybuffer::data(&mut (*pointer).base, self.to_raw());
+            (*pointer).size = neon_runtime::arraybuffer::data(guard.env.to_raw(), &mut (*pointer).base, self.to_raw());
         }
 
         // UB if pointer is not initialized!
@@ -255,3 +317,300 @@ impl<'a> BorrowMut for &'a mut JsArrayBuffer {
         }
     }
 }
+
+/// Checks that a view of `byte_length` bytes at `byte_offset` fits in `buffer`, throwing a
+/// `RangeError` otherwise, since the engine does not check this for us.
+fn check_view<'a, C: Context<'a>>(cx: &mut C, buffer: Handle<JsArrayBuffer>, byte_offset: usize, byte_length: Option<usize>) -> NeonResult<()> {
+    let size = unsafe {
+        let mut base: *mut c_void = ptr::null_mut();
+        neon_runtime::arraybuffer::data(cx.env().to_raw(), &mut base, buffer.to_raw())
+    };
+    match byte_length.and_then(|len| byte_offset.checked_add(len)) {
+        Some(end) if end <= size => Ok(()),
+        _ => cx.throw_range_error("view does not fit in the ArrayBuffer")
+    }
+}
+
+/// A JavaScript typed array, such as `Uint8Array` or `Float64Array`, whose elements have
+/// the Rust type `T`.
+///
+/// The contents can be accessed as a `&[T]` or `&mut [T]` via the `Borrow` and `BorrowMut`
+/// traits.
+#[repr(C)]
+pub struct JsTypedArray<T: BinaryViewType> {
+    raw: raw::Local,
+    marker: PhantomData<T>
+}
+
+impl<T: BinaryViewType> Clone for JsTypedArray<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T: BinaryViewType> Copy for JsTypedArray<T> { }
+
+impl<T: BinaryViewType> JsTypedArray<T> {
+
+    /// Constructs a new typed array viewing `len` elements of `buffer`, starting `byte_offset`
+    /// bytes into it. Throws a `RangeError` if the offset is not a multiple of the element
+    /// size or the view does not fit in the buffer.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, buffer: Handle<JsArrayBuffer>, byte_offset: usize, len: usize) -> JsResult<'a, JsTypedArray<T>> {
+        if byte_offset % mem::size_of::<T>() != 0 {
+            return cx.throw_range_error(format!("start offset of {} should be a multiple of {}", Self::name(), mem::size_of::<T>()));
+        }
+        check_view(cx, buffer, byte_offset, len.checked_mul(mem::size_of::<T>()))?;
+        let env = cx.env();
+        build(env, |out| { unsafe {
+            neon_runtime::typedarray::new(out, env.to_raw(), T::TYPED_ARRAY_TYPE, buffer.to_raw(), byte_offset, len)
+        } })
+    }
+
+    /// Produces the `ArrayBuffer` this typed array is a view of.
+    pub fn buffer<'a, C: Context<'a>>(self, cx: &mut C) -> Handle<'a, JsArrayBuffer> {
+        let (_, buffer, _, _) = self.info(cx.env());
+        Handle::new_internal(cx.env(), JsArrayBuffer(buffer))
+    }
+
+    fn info(self, env: Env) -> (TypedArrayType, raw::Local, usize, usize) {
+        unsafe {
+            let mut ty = T::TYPED_ARRAY_TYPE;
+            let mut buffer: raw::Local = mem::zeroed();
+            let mut byte_offset = 0;
+            let mut len = 0;
+            neon_runtime::typedarray::info(env.to_raw(), self.to_raw(), &mut ty, &mut buffer, &mut byte_offset, &mut len);
+            (ty, buffer, byte_offset, len)
+        }
+    }
+
+    fn data<'a>(self, env: Env) -> TypedArrayData<'a, T> {
+        let (_, buffer, byte_offset, len) = self.info(env);
+        let base = if len == 0 {
+            NonNull::dangling().as_ptr()
+        } else {
+            unsafe {
+                let mut base: *mut c_void = ptr::null_mut();
+                neon_runtime::arraybuffer::data(env.to_raw(), &mut base, buffer);
+                (base as *mut u8).add(byte_offset) as *mut T
+            }
+        };
+        TypedArrayData { base, len, phantom: PhantomData }
+    }
+
+}
+
+impl<'a, T: BinaryViewType> Handle<'a, JsTypedArray<T>> {
+    /// Produces the offset of this view into its `ArrayBuffer`, in bytes.
+    pub fn byte_offset(self) -> usize {
+        let (_, _, byte_offset, _) = self.info(self.env());
+        byte_offset
+    }
+
+    /// Produces the length of this typed array, in elements.
+    pub fn len(self) -> usize {
+        let (_, _, _, len) = self.info(self.env());
+        len
+    }
+}
+
+impl<T: BinaryViewType> Managed for JsTypedArray<T> {
+    fn to_raw(self) -> raw::Local { self.raw }
+
+    fn from_raw(h: raw::Local) -> Self { JsTypedArray { raw: h, marker: PhantomData } }
+}
+
+impl<T: BinaryViewType> ValueInternal for JsTypedArray<T> {
+    fn name() -> String {
+        match T::TYPED_ARRAY_TYPE {
+            TypedArrayType::Int8 => "Int8Array",
+            TypedArrayType::Uint8 => "Uint8Array",
+            TypedArrayType::Uint8Clamped => "Uint8ClampedArray",
+            TypedArrayType::Int16 => "Int16Array",
+            TypedArrayType::Uint16 => "Uint16Array",
+            TypedArrayType::Int32 => "Int32Array",
+            TypedArrayType::Uint32 => "Uint32Array",
+            TypedArrayType::Float32 => "Float32Array",
+            TypedArrayType::Float64 => "Float64Array",
+            TypedArrayType::BigInt64 => "BigInt64Array",
+            TypedArrayType::BigUint64 => "BigUint64Array"
+        }.to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe {
+            neon_runtime::tag::is_typedarray(env.to_raw(), other.to_raw()) &&
+                JsTypedArray::<T>::from_raw(other.to_raw()).info(env).0 == T::TYPED_ARRAY_TYPE
+        }
+    }
+}
+
+impl<T: BinaryViewType> Value for JsTypedArray<T> { }
+
+impl<T: BinaryViewType> Object for JsTypedArray<T> { }
+
+/// A reference to the elements of a typed array, which can be accessed via the `Borrow` and
+/// `BorrowMut` traits and dereferences to a slice.
+pub struct TypedArrayData<'a, T: BinaryViewType> {
+    base: *mut T,
+    len: usize,
+    phantom: PhantomData<&'a ()>
+}
+
+unsafe impl<'a, T: BinaryViewType> Pointer for TypedArrayData<'a, T> {
+    unsafe fn as_ptr(&self) -> *const c_void {
+        self.base as *const c_void
+    }
+
+    unsafe fn as_mut(&mut self) -> *mut c_void {
+        self.base as *mut c_void
+    }
+
+    fn size(&self) -> usize {
+        self.len * mem::size_of::<T>()
+    }
+}
+
+impl<'a, T: BinaryViewType> Deref for TypedArrayData<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.base, self.len) }
+    }
+}
+
+impl<'a, T: BinaryViewType> DerefMut for TypedArrayData<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.base, self.len) }
+    }
+}
+
+impl<'a, T: BinaryViewType> Borrow for &'a JsTypedArray<T> {
+    type Target = TypedArrayData<'a, T>;
+
+    fn try_borrow<'b>(self, guard: &'b Lock<'b>) -> Result<Ref<'b, Self::Target>, LoanError> {
+        unsafe {
+            Ref::new(guard, self.data(guard.env))
+        }
+    }
+}
+
+impl<'a, T: BinaryViewType> Borrow for &'a mut JsTypedArray<T> {
+    type Target = TypedArrayData<'a, T>;
+
+    fn try_borrow<'b>(self, guard: &'b Lock<'b>) -> Result<Ref<'b, Self::Target>, LoanError> {
+        (self as &'a JsTypedArray<T>).try_borrow(guard)
+    }
+}
+
+impl<'a, T: BinaryViewType> BorrowMut for &'a mut JsTypedArray<T> {
+    fn try_borrow_mut<'b>(self, guard: &'b Lock<'b>) -> Result<RefMut<'b, Self::Target>, LoanError> {
+        unsafe {
+            RefMut::new(guard, self.data(guard.env))
+        }
+    }
+}
+
+/// The standard JS [`DataView`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/DataView) type.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct JsDataView(raw::Local);
+
+impl JsDataView {
+
+    /// Constructs a new `DataView` of `byte_length` bytes of `buffer`, starting `byte_offset`
+    /// bytes into it. Throws a `RangeError` if the view does not fit in the buffer.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, buffer: Handle<JsArrayBuffer>, byte_offset: usize, byte_length: usize) -> JsResult<'a, JsDataView> {
+        check_view(cx, buffer, byte_offset, Some(byte_length))?;
+        let env = cx.env();
+        build(env, |out| { unsafe {
+            neon_runtime::dataview::new(out, env.to_raw(), buffer.to_raw(), byte_offset, byte_length)
+        } })
+    }
+
+    /// Produces the `ArrayBuffer` this `DataView` is a view of.
+    pub fn buffer<'a, C: Context<'a>>(self, cx: &mut C) -> Handle<'a, JsArrayBuffer> {
+        let (buffer, _, _) = self.info(cx.env());
+        Handle::new_internal(cx.env(), JsArrayBuffer(buffer))
+    }
+
+    fn info(self, env: Env) -> (raw::Local, usize, usize) {
+        unsafe {
+            let mut buffer: raw::Local = mem::zeroed();
+            let mut byte_offset = 0;
+            let mut byte_length = 0;
+            neon_runtime::dataview::info(env.to_raw(), self.to_raw(), &mut buffer, &mut byte_offset, &mut byte_length);
+            (buffer, byte_offset, byte_length)
+        }
+    }
+
+    fn data<'a>(self, env: Env) -> BinaryData<'a> {
+        let (buffer, byte_offset, size) = self.info(env);
+        let base = if size == 0 {
+            NonNull::dangling().as_ptr()
+        } else {
+            unsafe {
+                let mut base: *mut c_void = ptr::null_mut();
+                neon_runtime::arraybuffer::data(env.to_raw(), &mut base, buffer);
+                (base as *mut u8).add(byte_offset) as *mut c_void
+            }
+        };
+        BinaryData { base, size, phantom: PhantomData }
+    }
+
+}
+
+impl<'a> Handle<'a, JsDataView> {
+    /// Produces the offset of this view into its `ArrayBuffer`, in bytes.
+    pub fn byte_offset(self) -> usize {
+        let (_, byte_offset, _) = self.info(self.env());
+        byte_offset
+    }
+
+    /// Produces the length of this view, in bytes.
+    pub fn byte_length(self) -> usize {
+        let (_, _, byte_length) = self.info(self.env());
+        byte_length
+    }
+}
+
+impl Managed for JsDataView {
+    fn to_raw(self) -> raw::Local { self.0 }
+
+    fn from_raw(h: raw::Local) -> Self { JsDataView(h) }
+}
+
+impl ValueInternal for JsDataView {
+    fn name() -> String { "DataView".to_string() }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_dataview(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Value for JsDataView { }
+
+impl Object for JsDataView { }
+
+impl<'a> Borrow for &'a JsDataView {
+    type Target = BinaryData<'a>;
+
+    fn try_borrow<'b>(self, guard: &'b Lock<'b>) -> Result<Ref<'b, Self::Target>, LoanError> {
+        unsafe {
+            Ref::new(guard, self.data(guard.env))
+        }
+    }
+}
+
+impl<'a> Borrow for &'a mut JsDataView {
+    type Target = BinaryData<'a>;
+
+    fn try_borrow<'b>(self, guard: &'b Lock<'b>) -> Result<Ref<'b, Self::Target>, LoanError> {
+        (self as &'a JsDataView).try_borrow(guard)
+    }
+}
+
+impl<'a> BorrowMut for &'a mut JsDataView {
+    fn try_borrow_mut<'b>(self, guard: &'b Lock<'b>) -> Result<RefMut<'b, Self::Target>, LoanError> {
+        unsafe {
+            RefMut::new(guard, self.data(guard.env))
+        }
+    }
+}
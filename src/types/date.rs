@@ -0,0 +1,70 @@
+//! Types and traits representing JavaScript dates.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use neon_runtime;
+use neon_runtime::raw;
+
+use context::Context;
+use context::internal::Env;
+use handle::Handle;
+use result::JsResult;
+use types::{Value, Object, Managed, build};
+use types::internal::ValueInternal;
+
+/// A JavaScript `Date` object.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct JsDate(raw::Local);
+
+impl Managed for JsDate {
+    fn to_raw(self) -> raw::Local { self.0 }
+
+    fn from_raw(h: raw::Local) -> Self { JsDate(h) }
+}
+
+impl ValueInternal for JsDate {
+    fn name() -> String { "Date".to_string() }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_date(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Value for JsDate { }
+
+impl Object for JsDate { }
+
+impl JsDate {
+    /// Creates a date from a number of milliseconds since the Unix epoch. As with
+    /// JavaScript's `new Date(ms)`, a value that is out of range produces an invalid date.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, ms: f64) -> JsResult<'a, JsDate> {
+        let env = cx.env();
+        build(env, |out| { unsafe { neon_runtime::date::new(out, env.to_raw(), ms) } })
+    }
+
+    /// Creates a date from a `SystemTime`, truncated to millisecond precision.
+    pub fn from_system_time<'a, C: Context<'a>>(cx: &mut C, time: SystemTime) -> JsResult<'a, JsDate> {
+        let ms = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as f64) * 1000.0 + (d.subsec_millis() as f64),
+            Err(e) => {
+                let d = e.duration();
+                -((d.as_secs() as f64) * 1000.0 + (d.subsec_millis() as f64))
+            }
+        };
+        JsDate::new(cx, ms)
+    }
+}
+
+impl<'a> Handle<'a, JsDate> {
+    /// The number of milliseconds since the Unix epoch, or `NaN` for an invalid date.
+    pub fn value(self) -> f64 {
+        unsafe {
+            neon_runtime::date::value(self.env().to_raw(), self.to_raw())
+        }
+    }
+
+    /// Indicates whether the date is valid, i.e. whether its value is not `NaN`.
+    pub fn is_valid(self) -> bool {
+        !self.value().is_nan()
+    }
+}
@@ -6,6 +6,7 @@ use neon_runtime;
 use neon_runtime::raw;
 
 use context::Context;
+use context::internal::Env;
 use result::{NeonResult, Throw};
 use types::{Value, Object, Handle, Managed, build};
 use types::internal::ValueInternal;
@@ -25,8 +26,8 @@ impl Managed for JsError {
 impl ValueInternal for JsError {
     fn name() -> String { "Error".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_error(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_error(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -38,8 +39,9 @@ impl JsError {
     /// Creates a direct instance of the [`Error`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/Error) class.
     pub fn error<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, msg: S) -> NeonResult<Handle<'a, JsError>> {
         let msg = cx.string(msg.as_ref());
-        build(|out| unsafe {
-            neon_runtime::error::new_error(out, msg.to_raw());
+        let env = cx.env();
+        build(env, |out| unsafe {
+            neon_runtime::error::new_error(out, env.to_raw(), msg.to_raw());
             true
         })
     }
@@ -47,8 +49,9 @@ impl JsError {
     /// Creates an instance of the [`TypeError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/TypeError) class.
     pub fn type_error<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, msg: S) -> NeonResult<Handle<'a, JsError>> {
         let msg = cx.string(msg.as_ref());
-        build(|out| unsafe {
-            neon_runtime::error::new_type_error(out, msg.to_raw());
+        let env = cx.env();
+        build(env, |out| unsafe {
+            neon_runtime::error::new_type_error(out, env.to_raw(), msg.to_raw());
             true
         })
     }
@@ -56,14 +59,15 @@ impl JsError {
     /// Creates an instance of the [`RangeError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/RangeError) class.
     pub fn range_error<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, msg: S) -> NeonResult<Handle<'a, JsError>> {
         let msg = cx.string(msg.as_ref());
-        build(|out| unsafe {
-            neon_runtime::error::new_range_error(out, msg.to_raw());
+        let env = cx.env();
+        build(env, |out| unsafe {
+            neon_runtime::error::new_range_error(out, env.to_raw(), msg.to_raw());
             true
         })
     }
 }
 
-pub(crate) fn convert_panics<T, F: UnwindSafe + FnOnce() -> NeonResult<T>>(f: F) -> NeonResult<T> {
+pub(crate) fn convert_panics<T, F: UnwindSafe + FnOnce() -> NeonResult<T>>(env: Env, f: F) -> NeonResult<T> {
     match catch_unwind(|| { f() }) {
         Ok(result) => result,
         Err(panic) => {
@@ -76,7 +80,7 @@ pub(crate) fn convert_panics<T, F: UnwindSafe + FnOnce() -> NeonResult<T>>(f: F)
             };
             let (data, len) = Utf8::from(&msg[..]).truncate().lower();
             unsafe {
-                neon_runtime::error::throw_error_from_utf8(data, len);
+                neon_runtime::error::throw_error_from_utf8(env.to_raw(), data, len);
                 Err(Throw)
             }
         }
@@ -1,49 +1,89 @@
 use std::mem;
+use std::marker::PhantomData;
 use std::os::raw::c_void;
+use std::panic::AssertUnwindSafe;
 use neon_runtime;
 use neon_runtime::raw;
 use context::{CallbackInfo, FunctionContext};
+use context::internal::{ContextInternal, Env};
 use types::error::convert_panics;
 use types::{JsObject, Handle, Managed};
-use result::JsResult;
+use result::{NeonResult, JsResult};
+use convert::IntoJs;
 use object::class::Callback;
 use super::Value;
 
 pub trait ValueInternal: Managed + 'static {
     fn name() -> String;
 
-    fn is_typeof<Other: Value>(other: Other) -> bool;
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool;
 
-    fn downcast<Other: Value>(other: Other) -> Option<Self> {
-        if Self::is_typeof(other) {
+    fn downcast<Other: Value>(env: Env, other: Other) -> Option<Self> {
+        if Self::is_typeof(env, other) {
             Some(Self::from_raw(other.to_raw()))
         } else {
             None
         }
     }
 
-    fn cast<'a, T: Value, F: FnOnce(raw::Local) -> T>(self, f: F) -> Handle<'a, T> {
-        Handle::new_internal(f(self.to_raw()))
+    fn cast<'a, T: Value, F: FnOnce(raw::Local) -> T>(self, env: Env, f: F) -> Handle<'a, T> {
+        Handle::new_internal(env, f(self.to_raw()))
     }
 }
 
-#[repr(C)]
-pub struct FunctionCallback<T: Value>(pub fn(FunctionContext) -> JsResult<T>);
+/// The callback of a function created from a Rust closure. The closure is boxed and passed
+/// as the dynamic callback.
+pub struct FunctionCallback<F, T>(pub *mut F, pub PhantomData<T>);
 
-impl<T: Value> Callback<()> for FunctionCallback<T> {
+impl<F, T> Callback<()> for FunctionCallback<F, T>
+    where F: Fn(FunctionContext) -> JsResult<T> + 'static,
+          T: Value
+{
     extern "C" fn invoke(info: &CallbackInfo) {
         unsafe {
             info.with_cx::<JsObject, _, _>(|cx| {
                 let data = info.data();
-                let dynamic_callback: fn(FunctionContext) -> JsResult<T> =
-                    mem::transmute(neon_runtime::fun::get_dynamic_callback(data.to_raw()));
-                if let Ok(value) = convert_panics(|| { dynamic_callback(cx) }) {
+                let env = cx.env();
+                let dynamic_callback = &*(neon_runtime::fun::get_dynamic_callback(data.to_raw()) as *const F);
+                // Closures may capture state that isn't unwind safe, such as a `RefCell`. A panic
+                // is turned into a JavaScript exception, just like in a plain function.
+                if let Ok(value) = convert_panics(env, AssertUnwindSafe(|| { dynamic_callback(cx) })) {
                     info.set_return(value);
                 }
             })
         }
     }
 
+    fn as_ptr(self) -> *mut c_void {
+        self.0 as *mut c_void
+    }
+}
+
+#[repr(C)]
+pub struct TypedFunctionCallback<T: IntoJs>(pub fn(FunctionContext) -> NeonResult<T>);
+
+impl<T: IntoJs> Callback<()> for TypedFunctionCallback<T> {
+    extern "C" fn invoke(info: &CallbackInfo) {
+        unsafe {
+            info.with_cx::<JsObject, _, _>(|cx| {
+                let data = info.data();
+                let env = cx.env();
+                let dynamic_callback: fn(FunctionContext) -> NeonResult<T> =
+                    mem::transmute(neon_runtime::fun::get_dynamic_callback(data.to_raw()));
+                let result = match convert_panics(env, || { dynamic_callback(cx) }) {
+                    Ok(result) => result,
+                    Err(_) => return
+                };
+                // The function consumed its context, so the result is converted in a fresh one.
+                info.with_cx::<JsObject, _, _>(|mut cx| {
+                    if let Ok(value) = convert_panics(env, AssertUnwindSafe(|| { result.into_js(&mut cx) })) {
+                        info.set_return(value);
+                    }
+                })
+            })
+        }
+    }
+
     fn as_ptr(self) -> *mut c_void {
         unsafe { mem::transmute(self.0) }
     }
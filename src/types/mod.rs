@@ -1,7 +1,11 @@
 //! Representations of JavaScript's core builtin types.
 
 pub(crate) mod binary;
+pub(crate) mod bigint;
+pub(crate) mod date;
 pub(crate) mod error;
+pub(crate) mod promise;
+pub(crate) mod symbol;
 
 pub(crate) mod internal;
 pub(crate) mod utf8;
@@ -13,23 +17,30 @@ use std::marker::PhantomData;
 use neon_runtime;
 use neon_runtime::raw;
 use context::{Context, FunctionContext};
-use context::internal::Isolate;
+use context::internal::Env;
 use result::{NeonResult, JsResult, Throw, JsResultExt};
 use object::{Object, This};
 use object::class::Callback;
 use handle::{Handle, Managed};
 use handle::internal::SuperType;
-use self::internal::{ValueInternal, FunctionCallback};
+use convert::IntoJs;
+use self::internal::{ValueInternal, FunctionCallback, TypedFunctionCallback};
 use self::utf8::Utf8;
 
-pub use self::binary::{JsBuffer, JsArrayBuffer, BinaryData, BinaryViewType};
+pub use self::binary::{JsBuffer, JsArrayBuffer, BinaryData, BinaryViewType, JsTypedArray, TypedArrayData, JsDataView};
+pub use self::bigint::{JsBigInt, BigIntOverflow};
+pub use self::date::JsDate;
 pub use self::error::JsError;
+pub use self::promise::{JsPromise, Deferred};
+#[cfg(feature = "legacy-runtime")]
+pub use self::promise::PromiseState;
+pub use self::symbol::JsSymbol;
 
-pub(crate) fn build<'a, T: Managed, F: FnOnce(&mut raw::Local) -> bool>(init: F) -> JsResult<'a, T> {
+pub(crate) fn build<'a, T: Managed, F: FnOnce(&mut raw::Local) -> bool>(env: Env, init: F) -> JsResult<'a, T> {
     unsafe {
         let mut local: raw::Local = std::mem::zeroed();
         if init(&mut local) {
-            Ok(Handle::new_internal(T::from_raw(local)))
+            Ok(Handle::new_internal(env, T::from_raw(local)))
         } else {
             Err(Throw)
         }
@@ -50,12 +61,13 @@ impl<T: Object> SuperType<T> for JsObject {
 
 /// The trait shared by all JavaScript values.
 pub trait Value: ValueInternal {
-    fn to_string<'a, C: Context<'a>>(self, _: &mut C) -> JsResult<'a, JsString> {
-        build(|out| { unsafe { neon_runtime::convert::to_string(out, self.to_raw()) } })
+    fn to_string<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsString> {
+        let env = cx.env();
+        build(env, |out| { unsafe { neon_runtime::convert::to_string(out, env.to_raw(), self.to_raw()) } })
     }
 
-    fn as_value<'a, C: Context<'a>>(self, _: &mut C) -> Handle<'a, JsValue> {
-        JsValue::new_internal(self.to_raw())
+    fn as_value<'a, C: Context<'a>>(self, cx: &mut C) -> Handle<'a, JsValue> {
+        JsValue::new_internal(cx.env(), self.to_raw())
     }
 }
 
@@ -75,20 +87,20 @@ impl Managed for JsValue {
 impl ValueInternal for JsValue {
     fn name() -> String { "any".to_string() }
 
-    fn is_typeof<Other: Value>(_: Other) -> bool {
+    fn is_typeof<Other: Value>(_: Env, _: Other) -> bool {
         true
     }
 }
 
 unsafe impl This for JsValue {
-    fn as_this(h: raw::Local) -> Self {
+    fn as_this(_: Env, h: raw::Local) -> Self {
         JsValue(h)
     }
 }
 
 impl JsValue {
-    pub(crate) fn new_internal<'a>(value: raw::Local) -> Handle<'a, JsValue> {
-        Handle::new_internal(JsValue(value))
+    pub(crate) fn new_internal<'a>(env: Env, value: raw::Local) -> Handle<'a, JsValue> {
+        Handle::new_internal(env, JsValue(value))
     }
 }
 
@@ -98,15 +110,23 @@ impl JsValue {
 pub struct JsUndefined(raw::Local);
 
 impl JsUndefined {
+    #[cfg(feature = "legacy-runtime")]
     pub fn new<'a>() -> Handle<'a, JsUndefined> {
-        JsUndefined::new_internal()
+        JsUndefined::new_internal(Env::current())
     }
 
-    pub(crate) fn new_internal<'a>() -> Handle<'a, JsUndefined> {
+    /// Creates `undefined` in the context's environment. Unlike the legacy runtime, N-API has no
+    /// environment to fall back on without a context.
+    #[cfg(feature = "napi-runtime")]
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Handle<'a, JsUndefined> {
+        JsUndefined::new_internal(cx.env())
+    }
+
+    pub(crate) fn new_internal<'a>(env: Env) -> Handle<'a, JsUndefined> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            neon_runtime::primitive::undefined(&mut local);
-            Handle::new_internal(JsUndefined(local))
+            neon_runtime::primitive::undefined(&mut local, env.to_raw());
+            Handle::new_internal(env, JsUndefined(local))
         }
     }
 }
@@ -120,10 +140,10 @@ impl Managed for JsUndefined {
 }
 
 unsafe impl This for JsUndefined {
-    fn as_this(_: raw::Local) -> Self {
+    fn as_this(env: Env, _: raw::Local) -> Self {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            neon_runtime::primitive::undefined(&mut local);
+            neon_runtime::primitive::undefined(&mut local, env.to_raw());
             JsUndefined(local)
         }
     }
@@ -132,8 +152,8 @@ unsafe impl This for JsUndefined {
 impl ValueInternal for JsUndefined {
     fn name() -> String { "undefined".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_undefined(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_undefined(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -143,15 +163,23 @@ impl ValueInternal for JsUndefined {
 pub struct JsNull(raw::Local);
 
 impl JsNull {
+    #[cfg(feature = "legacy-runtime")]
     pub fn new<'a>() -> Handle<'a, JsNull> {
-        JsNull::new_internal()
+        JsNull::new_internal(Env::current())
+    }
+
+    /// Creates `null` in the context's environment. Unlike the legacy runtime, N-API has no
+    /// environment to fall back on without a context.
+    #[cfg(feature = "napi-runtime")]
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Handle<'a, JsNull> {
+        JsNull::new_internal(cx.env())
     }
 
-    pub(crate) fn new_internal<'a>() -> Handle<'a, JsNull> {
+    pub(crate) fn new_internal<'a>(env: Env) -> Handle<'a, JsNull> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            neon_runtime::primitive::null(&mut local);
-            Handle::new_internal(JsNull(local))
+            neon_runtime::primitive::null(&mut local, env.to_raw());
+            Handle::new_internal(env, JsNull(local))
         }
     }
 }
@@ -167,8 +195,8 @@ impl Managed for JsNull {
 impl ValueInternal for JsNull {
     fn name() -> String { "null".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_null(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_null(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -178,21 +206,24 @@ impl ValueInternal for JsNull {
 pub struct JsBoolean(raw::Local);
 
 impl JsBoolean {
-    pub fn new<'a, C: Context<'a>>(_: &mut C, b: bool) -> Handle<'a, JsBoolean> {
-        JsBoolean::new_internal(b)
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, b: bool) -> Handle<'a, JsBoolean> {
+        JsBoolean::new_internal(cx.env(), b)
     }
 
-    pub(crate) fn new_internal<'a>(b: bool) -> Handle<'a, JsBoolean> {
+    pub(crate) fn new_internal<'a>(env: Env, b: bool) -> Handle<'a, JsBoolean> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            neon_runtime::primitive::boolean(&mut local, b);
-            Handle::new_internal(JsBoolean(local))
+            neon_runtime::primitive::boolean(&mut local, env.to_raw(), b);
+            Handle::new_internal(env, JsBoolean(local))
         }
     }
 
+}
+
+impl<'a> Handle<'a, JsBoolean> {
     pub fn value(self) -> bool {
         unsafe {
-            neon_runtime::primitive::boolean_value(self.to_raw())
+            neon_runtime::primitive::boolean_value(self.env().to_raw(), self.to_raw())
         }
     }
 }
@@ -208,8 +239,8 @@ impl Managed for JsBoolean {
 impl ValueInternal for JsBoolean {
     fn name() -> String { "boolean".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_boolean(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_boolean(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -251,42 +282,46 @@ impl Managed for JsString {
 impl ValueInternal for JsString {
     fn name() -> String { "string".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_string(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_string(env.to_raw(), other.to_raw()) }
     }
 }
 
-impl JsString {
+impl<'a> Handle<'a, JsString> {
     pub fn size(self) -> isize {
         unsafe {
-            neon_runtime::string::utf8_len(self.to_raw())
+            neon_runtime::string::utf8_len(self.env().to_raw(), self.to_raw())
         }
     }
 
     pub fn value(self) -> String {
         unsafe {
-            let capacity = neon_runtime::string::utf8_len(self.to_raw());
+            let env = self.env().to_raw();
+            // Leave room for a NUL terminator, which N-API always writes.
+            let capacity = neon_runtime::string::utf8_len(env, self.to_raw()) + 1;
             let mut buffer: Vec<u8> = Vec::with_capacity(capacity as usize);
             let p = buffer.as_mut_ptr();
             std::mem::forget(buffer);
-            let len = neon_runtime::string::data(p, capacity, self.to_raw());
+            let len = neon_runtime::string::data(env, p, capacity, self.to_raw());
             String::from_raw_parts(p, len as usize, capacity as usize)
         }
     }
+}
 
+impl JsString {
     pub fn new<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, val: S) -> Handle<'a, JsString> {
         JsString::try_new(cx, val).unwrap()
     }
 
     pub fn try_new<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, val: S) -> StringResult<'a> {
         let val = val.as_ref();
-        match JsString::new_internal(cx.isolate(), val) {
+        match JsString::new_internal(cx.env(), val) {
             Some(s) => Ok(s),
             None => Err(StringOverflow(val.len()))
         }
     }
 
-    pub(crate) fn new_internal<'a>(isolate: Isolate, val: &str) -> Option<Handle<'a, JsString>> {
+    pub(crate) fn new_internal<'a>(env: Env, val: &str) -> Option<Handle<'a, JsString>> {
         let (ptr, len) = if let Some(small) = Utf8::from(val).into_small() {
             small.lower()
         } else {
@@ -295,8 +330,8 @@ impl JsString {
 
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            if neon_runtime::string::new(&mut local, isolate.to_raw(), ptr, len) {
-                Some(Handle::new_internal(JsString(local)))
+            if neon_runtime::string::new(&mut local, env.to_raw(), ptr, len) {
+                Some(Handle::new_internal(env, JsString(local)))
             } else {
                 None
             }
@@ -311,20 +346,23 @@ pub struct JsNumber(raw::Local);
 
 impl JsNumber {
     pub fn new<'a, C: Context<'a>, T: Into<f64>>(cx: &mut C, x: T) -> Handle<'a, JsNumber> {
-        JsNumber::new_internal(cx.isolate(), x.into())
+        JsNumber::new_internal(cx.env(), x.into())
     }
 
-    pub(crate) fn new_internal<'a>(isolate: Isolate, v: f64) -> Handle<'a, JsNumber> {
+    pub(crate) fn new_internal<'a>(env: Env, v: f64) -> Handle<'a, JsNumber> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            neon_runtime::primitive::number(&mut local, isolate.to_raw(), v);
-            Handle::new_internal(JsNumber(local))
+            neon_runtime::primitive::number(&mut local, env.to_raw(), v);
+            Handle::new_internal(env, JsNumber(local))
         }
     }
 
+}
+
+impl<'a> Handle<'a, JsNumber> {
     pub fn value(self) -> f64 {
         unsafe {
-            neon_runtime::primitive::number_value(self.to_raw())
+            neon_runtime::primitive::number_value(self.env().to_raw(), self.to_raw())
         }
     }
 }
@@ -340,8 +378,8 @@ impl Managed for JsNumber {
 impl ValueInternal for JsNumber {
     fn name() -> String { "number".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_number(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_number(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -359,33 +397,33 @@ impl Managed for JsObject {
 }
 
 unsafe impl This for JsObject {
-    fn as_this(h: raw::Local) -> Self { JsObject(h) }
+    fn as_this(_: Env, h: raw::Local) -> Self { JsObject(h) }
 }
 
 impl ValueInternal for JsObject {
     fn name() -> String { "object".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_object(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_object(env.to_raw(), other.to_raw()) }
     }
 }
 
 impl Object for JsObject { }
 
 impl JsObject {
-    pub fn new<'a, C: Context<'a>>(_: &mut C) -> Handle<'a, JsObject> {
-        JsObject::new_internal()
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Handle<'a, JsObject> {
+        JsObject::new_internal(cx.env())
     }
 
-    pub(crate) fn new_internal<'a>() -> Handle<'a, JsObject> {
-        JsObject::build(|out| { unsafe { neon_runtime::object::new(out) } })
+    pub(crate) fn new_internal<'a>(env: Env) -> Handle<'a, JsObject> {
+        JsObject::build(env, |out| { unsafe { neon_runtime::object::new(out, env.to_raw()) } })
     }
 
-    pub(crate) fn build<'a, F: FnOnce(&mut raw::Local)>(init: F) -> Handle<'a, JsObject> {
+    pub(crate) fn build<'a, F: FnOnce(&mut raw::Local)>(env: Env, init: F) -> Handle<'a, JsObject> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
             init(&mut local);
-            Handle::new_internal(JsObject(local))
+            Handle::new_internal(env, JsObject(local))
         }
     }
 }
@@ -398,24 +436,25 @@ pub struct JsArray(raw::Local);
 
 impl JsArray {
     pub fn new<'a, C: Context<'a>>(cx: &mut C, len: u32) -> Handle<'a, JsArray> {
-        JsArray::new_internal(cx.isolate(), len)
+        JsArray::new_internal(cx.env(), len)
     }
 
-    pub(crate) fn new_internal<'a>(isolate: Isolate, len: u32) -> Handle<'a, JsArray> {
+    pub(crate) fn new_internal<'a>(env: Env, len: u32) -> Handle<'a, JsArray> {
         unsafe {
             let mut local: raw::Local = std::mem::zeroed();
-            neon_runtime::array::new(&mut local, isolate.to_raw(), len);
-            Handle::new_internal(JsArray(local))
+            neon_runtime::array::new(&mut local, env.to_raw(), len);
+            Handle::new_internal(env, JsArray(local))
         }
     }
 
     pub fn to_vec<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<Vec<Handle<'a, JsValue>>> {
-        let mut result = Vec::with_capacity(self.len() as usize);
+        let env = cx.env();
+        let mut result = Vec::with_capacity(self.len_internal(env) as usize);
         let mut i = 0;
         loop {
             // Since getting a property can trigger arbitrary code,
             // we have to re-check the length on every iteration.
-            if i >= self.len() {
+            if i >= self.len_internal(env) {
                 return Ok(result);
             }
             result.push(self.get(cx, i)?);
@@ -423,13 +462,19 @@ impl JsArray {
         }
     }
 
-    pub fn len(self) -> u32 {
+    fn len_internal(self, env: Env) -> u32 {
         unsafe {
-            neon_runtime::array::len(self.to_raw())
+            neon_runtime::array::len(env.to_raw(), self.to_raw())
         }
     }
 }
 
+impl<'a> Handle<'a, JsArray> {
+    pub fn len(self) -> u32 {
+        self.len_internal(self.env())
+    }
+}
+
 impl Value for JsArray { }
 
 impl Managed for JsArray {
@@ -441,8 +486,8 @@ impl Managed for JsArray {
 impl ValueInternal for JsArray {
     fn name() -> String { "Array".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_array(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_array(env.to_raw(), other.to_raw()) }
     }
 }
 
@@ -461,28 +506,69 @@ impl<T: Object> Object for JsFunction<T> { }
 // Maximum number of function arguments in V8.
 const V8_ARGC_LIMIT: usize = 65535;
 
-unsafe fn prepare_call<'a, 'b, C: Context<'a>, A>(cx: &mut C, args: &mut [Handle<'b, A>]) -> NeonResult<(*mut c_void, i32, *mut c_void)>
-    where A: Value + 'b
-{
+unsafe fn prepare_call<'a, C: Context<'a>>(cx: &mut C, args: &mut [raw::Local]) -> NeonResult<(i32, *mut c_void)> {
     let argv = args.as_mut_ptr();
     let argc = args.len();
     if argc > V8_ARGC_LIMIT {
         return cx.throw_range_error("too many arguments");
     }
-    let isolate: *mut c_void = std::mem::transmute(cx.isolate().to_raw());
-    Ok((isolate, argc as i32, argv as *mut c_void))
+    Ok((argc as i32, argv as *mut c_void))
 }
 
 impl JsFunction {
-    pub fn new<'a, C, U>(cx: &mut C, f: fn(FunctionContext) -> JsResult<U>) -> JsResult<'a, JsFunction>
+    /// Creates a function from a Rust function or closure. A closure can capture Rust state,
+    /// which is dropped when the function is garbage collected.
+    ///
+    /// ```no_run
+    /// # use neon::prelude::*;
+    /// # use std::cell::Cell;
+    /// fn counter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    ///     let count = Cell::new(0);
+    ///     JsFunction::new(&mut cx, move |mut cx| {
+    ///         count.set(count.get() + 1);
+    ///         Ok(cx.number(count.get()))
+    ///     })
+    /// }
+    /// ```
+    pub fn new<'a, C, F, U>(cx: &mut C, f: F) -> JsResult<'a, JsFunction>
         where C: Context<'a>,
+              F: Fn(FunctionContext) -> JsResult<U> + 'static,
               U: Value
     {
-        build(|out| {
+        let env = cx.env();
+        let data = Box::into_raw(Box::new(f));
+        let function = build(env, |out| {
+            unsafe {
+                let callback = FunctionCallback(data, PhantomData).into_c_callback();
+                neon_runtime::fun::new(out, env.to_raw(), callback)
+            }
+        });
+        match function {
+            Ok(function) => {
+                // Plain functions are zero-sized, so there is nothing to free.
+                if std::mem::size_of::<F>() != 0 || std::mem::needs_drop::<F>() {
+                    function.add_finalizer(cx, move || unsafe { drop(Box::from_raw(data)); });
+                }
+                Ok(function)
+            }
+            Err(throw) => {
+                unsafe { drop(Box::from_raw(data)); }
+                Err(throw)
+            }
+        }
+    }
+
+    /// Creates a function from a Rust function that returns a Rust value, which is converted
+    /// to a JavaScript value with `IntoJs` when the function returns.
+    pub fn new_typed<'a, C, U>(cx: &mut C, f: fn(FunctionContext) -> NeonResult<U>) -> JsResult<'a, JsFunction>
+        where C: Context<'a>,
+              U: IntoJs
+    {
+        let env = cx.env();
+        build(env, |out| {
             unsafe {
-                let isolate: *mut c_void = std::mem::transmute(cx.isolate().to_raw());
-                let callback = FunctionCallback(f).into_c_callback();
-                neon_runtime::fun::new(out, isolate, callback)
+                let callback = TypedFunctionCallback(f).into_c_callback();
+                neon_runtime::fun::new(out, env.to_raw(), callback)
             }
         })
     }
@@ -494,11 +580,12 @@ impl<CL: Object> JsFunction<CL> {
               A: Value + 'b,
               AS: IntoIterator<Item=Handle<'b, A>>
     {
-        let mut args = args.into_iter().collect::<Vec<_>>();
-        let (isolate, argc, argv) = unsafe { prepare_call(cx, &mut args) }?;
-        build(|out| {
+        let mut args = args.into_iter().map(|arg| arg.to_raw()).collect::<Vec<_>>();
+        let (argc, argv) = unsafe { prepare_call(cx, &mut args) }?;
+        let env = cx.env();
+        build(env, |out| {
             unsafe {
-                neon_runtime::fun::call(out, isolate, self.to_raw(), this.to_raw(), argc, argv)
+                neon_runtime::fun::call(out, env.to_raw(), self.to_raw(), this.to_raw(), argc, argv)
             }
         })
     }
@@ -507,11 +594,12 @@ impl<CL: Object> JsFunction<CL> {
         where A: Value + 'b,
               AS: IntoIterator<Item=Handle<'b, A>>
     {
-        let mut args = args.into_iter().collect::<Vec<_>>();
-        let (isolate, argc, argv) = unsafe { prepare_call(cx, &mut args) }?;
-        build(|out| {
+        let mut args = args.into_iter().map(|arg| arg.to_raw()).collect::<Vec<_>>();
+        let (argc, argv) = unsafe { prepare_call(cx, &mut args) }?;
+        let env = cx.env();
+        build(env, |out| {
             unsafe {
-                neon_runtime::fun::construct(out, isolate, self.to_raw(), argc, argv)
+                neon_runtime::fun::construct(out, env.to_raw(), self.to_raw(), argc, argv)
             }
         })
     }
@@ -533,7 +621,7 @@ impl<T: Object> Managed for JsFunction<T> {
 impl<T: Object> ValueInternal for JsFunction<T> {
     fn name() -> String { "function".to_string() }
 
-    fn is_typeof<Other: Value>(other: Other) -> bool {
-        unsafe { neon_runtime::tag::is_function(other.to_raw()) }
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_function(env.to_raw(), other.to_raw()) }
     }
 }
@@ -0,0 +1,215 @@
+//! Types and traits representing JavaScript promises.
+//!
+//! Promises can be created, settled and chained with either runtime. Inspecting the state of
+//! an existing promise with `JsPromise::state` is only supported by the legacy runtime, since
+//! N-API has no way to read it; with N-API, register callbacks with `JsPromise::then` instead.
+
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use neon_runtime;
+use neon_runtime::raw;
+
+use context::{Context, TaskContext};
+use context::internal::Env;
+use event::{EventHandler, SharedHandler};
+use result::{NeonResult, JsResult, Throw};
+use types::{Value, Object, Managed, Handle, JsValue, JsFunction, JsError};
+use types::internal::ValueInternal;
+
+/// A JS `Promise` object.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct JsPromise(raw::Local);
+
+impl Managed for JsPromise {
+    fn to_raw(self) -> raw::Local { self.0 }
+
+    fn from_raw(h: raw::Local) -> Self { JsPromise(h) }
+}
+
+impl ValueInternal for JsPromise {
+    fn name() -> String { "Promise".to_string() }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_promise(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Value for JsPromise { }
+
+impl Object for JsPromise { }
+
+/// The state of a `JsPromise`, as produced by `JsPromise::state`. Only available with the
+/// legacy runtime.
+#[cfg(feature = "legacy-runtime")]
+pub enum PromiseState<'a> {
+    /// The promise has not settled yet.
+    Pending,
+    /// The promise has been fulfilled with a value.
+    Fulfilled(Handle<'a, JsValue>),
+    /// The promise has been rejected with a reason.
+    Rejected(Handle<'a, JsValue>)
+}
+
+impl JsPromise {
+    /// Creates a pending promise, along with the `Deferred` that settles it.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> (Handle<'a, JsPromise>, Deferred) {
+        let env = cx.env();
+        let handler = SharedHandler::get(cx);
+        unsafe {
+            let mut local: raw::Local = mem::zeroed();
+            let internal = neon_runtime::promise::new(&mut local, env.to_raw());
+            (Handle::new_internal(env, JsPromise(local)), Deferred { internal, env, handler })
+        }
+    }
+
+    /// Gets the state of the promise, along with its result once it has settled.
+    ///
+    /// This is only available with the legacy runtime, since N-API has no way to inspect
+    /// the state of a promise. With N-API, use `then` to be notified when the promise
+    /// settles instead.
+    #[cfg(feature = "legacy-runtime")]
+    pub fn state<'a, C: Context<'a>>(self, cx: &mut C) -> PromiseState<'a> {
+        let env = cx.env();
+        unsafe {
+            let mut result: raw::Local = mem::zeroed();
+            match neon_runtime::promise::state(&mut result, env.to_raw(), self.to_raw()) {
+                0 => PromiseState::Pending,
+                1 => PromiseState::Fulfilled(Handle::new_internal(env, JsValue::from_raw(result))),
+                _ => PromiseState::Rejected(Handle::new_internal(env, JsValue::from_raw(result)))
+            }
+        }
+    }
+
+    /// Calls the promise's `then` method with the given callbacks, producing the promise it
+    /// returns.
+    pub fn then<'a, C: Context<'a>>(self,
+                                    cx: &mut C,
+                                    on_fulfilled: Handle<JsFunction>,
+                                    on_rejected: Option<Handle<JsFunction>>) -> JsResult<'a, JsPromise> {
+        let then = self.get(cx, "then")?.downcast_or_throw::<JsFunction, _>(cx)?;
+        let this = Handle::new_internal(cx.env(), self);
+        let mut args: Vec<Handle<JsValue>> = vec![on_fulfilled.upcast()];
+        if let Some(on_rejected) = on_rejected {
+            args.push(on_rejected.upcast());
+        }
+        then.call(cx, this, args)?.downcast_or_throw(cx)
+    }
+}
+
+/// The means of settling a `JsPromise` created by `JsPromise::new`.
+///
+/// A `Deferred` can be sent to other threads, and kept to settle its promise in a later
+/// context on the JavaScript thread that created it. Settling consumes the `Deferred`, so a
+/// promise can only be settled once. Dropping it without settling it, on any thread, rejects
+/// the promise with an `Error` once the JavaScript thread gets to it.
+pub struct Deferred {
+    internal: raw::Deferred,
+    env: Env,
+    handler: EventHandler
+}
+
+// The promise can only be settled with a context of the environment that created it.
+unsafe impl Send for Deferred { }
+
+impl Deferred {
+    /// Resolves the promise with a value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context belongs to a different JavaScript environment, such as another
+    /// worker thread, than the one that created the promise.
+    pub fn resolve<'a, C: Context<'a>, V: Value>(mut self, cx: &mut C, value: Handle<V>) -> NeonResult<()> {
+        let env = self.check_env(cx);
+        if unsafe { neon_runtime::promise::resolve(env.to_raw(), self.take(), value.to_raw()) } {
+            Ok(())
+        } else {
+            Err(Throw)
+        }
+    }
+
+    /// Rejects the promise with a reason, which is usually an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context belongs to a different JavaScript environment, such as another
+    /// worker thread, than the one that created the promise.
+    pub fn reject<'a, C: Context<'a>, V: Value>(mut self, cx: &mut C, reason: Handle<V>) -> NeonResult<()> {
+        let env = self.check_env(cx);
+        if unsafe { neon_runtime::promise::reject(env.to_raw(), self.take(), reason.to_raw()) } {
+            Ok(())
+        } else {
+            Err(Throw)
+        }
+    }
+
+    /// Settles the promise with the value produced by `complete`, which runs in a context of
+    /// its own. If `complete` throws an exception, the promise is rejected with it instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context belongs to a different JavaScript environment, such as another
+    /// worker thread, than the one that created the promise.
+    pub fn settle_with<'a, C, V, F>(mut self, cx: &mut C, complete: F)
+        where C: Context<'a>,
+              V: Value,
+              F: for<'b> FnOnce(&mut TaskContext<'b>) -> JsResult<'b, V>
+    {
+        let env = self.check_env(cx);
+        let data = Box::into_raw(Box::new((env, complete))) as *mut c_void;
+        unsafe {
+            neon_runtime::promise::settle(env.to_raw(), self.take(), settle_deferred::<V, F>, data);
+        }
+    }
+
+    fn check_env<'a, C: Context<'a>>(&self, cx: &mut C) -> Env {
+        let env = cx.env();
+        assert!(env == self.env, "promise settled outside of the environment that created it");
+        env
+    }
+
+    /// Takes the runtime's deferred out of `self` to settle it, so that dropping `self` doesn't
+    /// reject the promise.
+    fn take(&mut self) -> raw::Deferred {
+        mem::replace(&mut self.internal, ptr::null_mut())
+    }
+}
+
+impl Drop for Deferred {
+    fn drop(&mut self) {
+        if self.internal.is_null() {
+            return;
+        }
+
+        // There may not be a handle scope to create the error in, even on the JavaScript
+        // thread, so the promise is always rejected from a context of its own.
+        let internal = RawDeferred(self.env, self.take());
+        self.handler.schedule_with(move |cx, _, _| {
+            let reason = match JsError::error(cx, "Deferred dropped without settling its promise") {
+                Ok(error) => error.upcast::<JsValue>(),
+                Err(_) => cx.undefined().upcast()
+            };
+            unsafe {
+                neon_runtime::promise::reject((internal.0).to_raw(), internal.1, reason.to_raw());
+            }
+        });
+    }
+}
+
+struct RawDeferred(Env, raw::Deferred);
+
+// The deferred is only settled on the JavaScript thread that created it.
+unsafe impl Send for RawDeferred { }
+
+unsafe extern "C" fn settle_deferred<V, F>(data: *mut c_void, out: &mut raw::Local)
+    where V: Value,
+          F: for<'b> FnOnce(&mut TaskContext<'b>) -> JsResult<'b, V>
+{
+    let (env, complete): (Env, F) = *Box::from_raw(data as *mut (Env, F));
+    TaskContext::with(env, |mut cx| {
+        if let Ok(value) = complete(&mut cx) {
+            *out = value.to_raw();
+        }
+    })
+}
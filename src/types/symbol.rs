@@ -0,0 +1,125 @@
+//! Types and traits representing JavaScript symbols.
+
+use std::mem;
+use neon_runtime;
+use neon_runtime::raw;
+
+use context::Context;
+use context::internal::Env;
+use object::Object;
+use result::JsResult;
+use types::{Value, Managed, Handle, JsString, JsFunction, JsObject};
+use types::internal::ValueInternal;
+
+/// A JavaScript `Symbol` primitive value.
+///
+/// A `Handle<JsSymbol>` can be used as a property key, which is how Rust code can implement
+/// JavaScript protocols, such as making an object iterable with `JsSymbol::iterator`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct JsSymbol(raw::Local);
+
+impl Value for JsSymbol { }
+
+impl Managed for JsSymbol {
+    fn to_raw(self) -> raw::Local { self.0 }
+
+    fn from_raw(h: raw::Local) -> Self { JsSymbol(h) }
+}
+
+impl ValueInternal for JsSymbol {
+    fn name() -> String { "symbol".to_string() }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_symbol(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl JsSymbol {
+    /// Creates a new unique symbol with no description, like `Symbol()`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Handle<'a, JsSymbol> {
+        let description: raw::Local = unsafe { mem::zeroed() };
+        JsSymbol::new_internal(cx.env(), description)
+    }
+
+    /// Creates a new unique symbol with a description, like `Symbol(description)`.
+    pub fn with_description<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, description: S) -> Handle<'a, JsSymbol> {
+        let description = JsString::new(cx, description);
+        JsSymbol::new_internal(cx.env(), description.to_raw())
+    }
+
+    fn new_internal<'a>(env: Env, description: raw::Local) -> Handle<'a, JsSymbol> {
+        unsafe {
+            let mut local: raw::Local = mem::zeroed();
+            neon_runtime::symbol::new(&mut local, env.to_raw(), description);
+            Handle::new_internal(env, JsSymbol(local))
+        }
+    }
+
+    /// Looks up a symbol in the global symbol registry, creating it if it does not exist yet,
+    /// like `Symbol.for(key)`.
+    pub fn for_key<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, key: S) -> JsResult<'a, JsSymbol> {
+        let constructor = JsSymbol::constructor(cx)?;
+        let lookup = constructor.get(cx, "for")?.downcast_or_throw::<JsFunction, _>(cx)?;
+        let key = JsString::new(cx, key);
+        lookup.call(cx, constructor, vec![key])?.downcast_or_throw(cx)
+    }
+
+    /// The symbol's description, or `None` if it was created without one.
+    pub fn description<'a, C: Context<'a>>(self, cx: &mut C) -> Option<Handle<'a, JsString>> {
+        let env = cx.env();
+        let description = unsafe {
+            let mut local: raw::Local = mem::zeroed();
+            neon_runtime::symbol::description(&mut local, env.to_raw(), self.to_raw());
+            local
+        };
+        if unsafe { neon_runtime::tag::is_string(env.to_raw(), description) } {
+            Some(Handle::new_internal(env, JsString::from_raw(description)))
+        } else {
+            None
+        }
+    }
+
+    /// The well-known `Symbol.iterator`, which makes an object iterable with `for...of`.
+    pub fn iterator<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsSymbol> {
+        JsSymbol::well_known(cx, "iterator")
+    }
+
+    /// The well-known `Symbol.asyncIterator`, which makes an object iterable with
+    /// `for await...of`.
+    pub fn async_iterator<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsSymbol> {
+        JsSymbol::well_known(cx, "asyncIterator")
+    }
+
+    /// The well-known `Symbol.hasInstance`, which customizes `instanceof`.
+    pub fn has_instance<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsSymbol> {
+        JsSymbol::well_known(cx, "hasInstance")
+    }
+
+    /// The well-known `Symbol.toPrimitive`, which customizes conversion to a primitive value.
+    pub fn to_primitive<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsSymbol> {
+        JsSymbol::well_known(cx, "toPrimitive")
+    }
+
+    /// The well-known `Symbol.toStringTag`, which customizes `Object.prototype.toString`.
+    pub fn to_string_tag<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsSymbol> {
+        JsSymbol::well_known(cx, "toStringTag")
+    }
+
+    /// Node's `util.inspect.custom`, which customizes how `console.log` and `util.inspect`
+    /// display an object. Node registers it as `Symbol.for('nodejs.util.inspect.custom')`.
+    pub fn inspect_custom<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsSymbol> {
+        JsSymbol::for_key(cx, "nodejs.util.inspect.custom")
+    }
+
+    fn well_known<'a, C: Context<'a>>(cx: &mut C, name: &str) -> JsResult<'a, JsSymbol> {
+        let constructor = JsSymbol::constructor(cx)?;
+        constructor.get(cx, name)?.downcast_or_throw(cx)
+    }
+
+    fn constructor<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsObject> {
+        let global = cx.global();
+        global.get(cx, "Symbol")?.downcast_or_throw(cx)
+    }
+}
+
@@ -0,0 +1,69 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+describe('JsPromise', function() {
+  it('resolves a promise in the same call', async function () {
+    assert.equal(await addon.resolve_promise(42), 42);
+  });
+
+  it('resolves a promise in a later call', async function () {
+    var promise = addon.defer_promise();
+    addon.settle_deferred(true, "done");
+    assert.equal(await promise, "done");
+  });
+
+  it('rejects a promise in a later call', async function () {
+    var promise = addon.defer_promise();
+    addon.settle_deferred(false, new Error("failed"));
+    try {
+      await promise;
+    } catch (err) {
+      assert.equal(err.message, "failed");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+
+  it('rejects a promise whose Deferred is dropped', async function () {
+    try {
+      await addon.drop_deferred();
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.equal(err.message, "Deferred dropped without settling its promise");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+
+  it('rejects a promise whose Deferred is dropped on another thread', async function () {
+    try {
+      await addon.drop_deferred_on_thread();
+    } catch (err) {
+      assert.equal(err.message, "Deferred dropped without settling its promise");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+
+  it('chains a fulfillment callback from Rust', async function () {
+    var result = await addon.then_from_rust(Promise.resolve(2), n => n * 2);
+    assert.equal(result, 4);
+  });
+
+  it('chains a rejection callback from Rust', async function () {
+    var result = await addon.then_from_rust(Promise.reject(new Error("oops")), () => "fulfilled", err => err.message);
+    assert.equal(result, "oops");
+  });
+
+  it('inspects the state of a promise', async function () {
+    var pending = new Promise(() => {});
+    var fulfilled = Promise.resolve(17);
+    var rejected = Promise.reject(new Error("nope"));
+    rejected.catch(() => {});
+
+    assert.deepEqual(addon.promise_state(pending), { state: "pending" });
+    assert.deepEqual(addon.promise_state(fulfilled), { state: "fulfilled", result: 17 });
+    assert.equal(addon.promise_state(rejected).state, "rejected");
+    assert.equal(addon.promise_state(rejected).result.message, "nope");
+  });
+});
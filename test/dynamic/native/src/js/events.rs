@@ -0,0 +1,32 @@
+use std::thread;
+use neon::prelude::*;
+use neon::event::EventHandler;
+
+pub fn emit_events(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let count = cx.argument::<JsNumber>(0)?.value() as u32;
+    let callback = cx.argument::<JsFunction>(1)?;
+    let handler = EventHandler::new(&mut cx, callback);
+
+    thread::spawn(move || {
+        for i in 0..count {
+            handler.schedule(move |cx| vec![cx.number(i)]);
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn emit_event_with_this(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let this = cx.argument::<JsObject>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?;
+    let handler = EventHandler::bind(this, callback);
+
+    thread::spawn(move || {
+        handler.schedule_with(|cx, this, callback| {
+            let args = vec![cx.string("from another thread")];
+            let _ = callback.call(cx, this, args);
+        });
+    });
+
+    Ok(cx.undefined())
+}
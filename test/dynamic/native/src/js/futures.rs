@@ -0,0 +1,60 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+use neon::prelude::*;
+
+/// A future that completes after a delay, timed by a thread of its own.
+struct Delay {
+    state: Arc<Mutex<(bool, Option<Waker>)>>
+}
+
+fn delay(ms: u64) -> Delay {
+    let state = Arc::new(Mutex::new((false, None::<Waker>)));
+    let timer = state.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(ms));
+        let mut state = timer.lock().unwrap();
+        state.0 = true;
+        if let Some(waker) = state.1.take() {
+            waker.wake();
+        }
+    });
+    Delay { state }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.0 {
+            Poll::Ready(())
+        } else {
+            state.1 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+pub fn add_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let a = cx.argument::<JsNumber>(0)?.value();
+    let b = cx.argument::<JsNumber>(1)?.value();
+    neon::future::promise(&mut cx, async move {
+        delay(10).await;
+        a + b
+    }, |cx, sum| Ok(cx.number(sum)))
+}
+
+pub fn fail_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let message = cx.argument::<JsString>(0)?.value();
+    neon::future::promise(&mut cx, async move {
+        delay(10).await;
+        message
+    }, |cx, message| {
+        let result: JsResult<JsUndefined> = cx.throw_error(message);
+        result
+    })
+}
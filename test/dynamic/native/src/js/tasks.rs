@@ -1,4 +1,5 @@
 use neon::prelude::*;
+use neon::handle::Persistent;
 
 struct SuccessTask;
 
@@ -22,6 +23,10 @@ pub fn perform_async_task(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     Ok(cx.undefined())
 }
 
+pub fn perform_async_task_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    Ok(SuccessTask.promise(&mut cx))
+}
+
 struct FailureTask;
 
 impl Task for FailureTask {
@@ -43,3 +48,43 @@ pub fn perform_failing_task(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     FailureTask.schedule(f);
     Ok(cx.undefined())
 }
+
+pub fn perform_failing_task_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    Ok(FailureTask.promise(&mut cx))
+}
+
+struct CountTask {
+    target: Persistent<JsObject>
+}
+
+impl Task for CountTask {
+    type Output = u32;
+    type Error = String;
+    type JsEvent = JsObject;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        Ok(42)
+    }
+
+    fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+        let target = self.target.to_handle(&mut cx);
+        let count = cx.number(result.unwrap());
+        target.set(&mut cx, "count", count)?;
+        Ok(target)
+    }
+}
+
+pub fn perform_task_with_persistent(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let target = cx.argument::<JsObject>(0)?;
+    let f = cx.argument::<JsFunction>(1)?;
+    let target = Persistent::new(&mut cx, target);
+    CountTask { target }.schedule(f);
+    Ok(cx.undefined())
+}
+
+pub fn release_persistent_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let target = cx.argument::<JsObject>(0)?;
+    let target = Persistent::new(&mut cx, target);
+    std::thread::spawn(move || drop(target)).join().unwrap();
+    Ok(cx.undefined())
+}
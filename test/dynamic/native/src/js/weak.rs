@@ -0,0 +1,39 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use neon::prelude::*;
+use neon::handle::Weak;
+
+thread_local! {
+    static TRACKED: RefCell<Option<Weak<JsObject>>> = RefCell::new(None);
+}
+
+pub fn track_object(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let weak = Weak::new(&mut cx, obj);
+    TRACKED.with(|tracked| *tracked.borrow_mut() = Some(weak));
+    Ok(cx.undefined())
+}
+
+pub fn tracked_object(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let obj = TRACKED.with(|tracked| {
+        tracked.borrow().as_ref().and_then(|weak| weak.to_handle(&mut cx))
+    });
+    match obj {
+        Some(obj) => Ok(obj.upcast()),
+        None => Ok(cx.undefined().upcast())
+    }
+}
+
+static COLLECTED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn count_when_collected(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let obj = cx.argument::<JsObject>(0)?;
+    obj.add_finalizer(&mut cx, || {
+        COLLECTED.fetch_add(1, Ordering::SeqCst);
+    });
+    Ok(cx.undefined())
+}
+
+pub fn collected_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(COLLECTED.load(Ordering::SeqCst) as f64))
+}
@@ -0,0 +1,24 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+describe('EventHandler', function() {
+  it('calls a function from another thread', function (done) {
+    var events = [];
+    addon.emit_events(5, function (n) {
+      events.push(n);
+      if (events.length === 5) {
+        assert.deepEqual(events, [0, 1, 2, 3, 4]);
+        done();
+      }
+    });
+  });
+
+  it('calls a function with the bound receiver', function (done) {
+    var obj = {};
+    addon.emit_event_with_this(obj, function (message) {
+      assert.strictEqual(this, obj);
+      assert.equal(message, "from another thread");
+      done();
+    });
+  });
+});
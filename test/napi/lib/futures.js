@@ -0,0 +1,23 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+describe('Futures', function() {
+  it('resolves the promise of a future', async function () {
+    assert.equal(await addon.add_async(1, 2), 3);
+  });
+
+  it('resolves the promises of concurrent futures', async function () {
+    var sums = await Promise.all([addon.add_async(1, 2), addon.add_async(3, 4), addon.add_async(5, 6)]);
+    assert.deepEqual(sums, [3, 7, 11]);
+  });
+
+  it('rejects the promise when completion throws', async function () {
+    try {
+      await addon.fail_async("failed in the background");
+    } catch (err) {
+      assert.equal(err.message, "failed in the background");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+});
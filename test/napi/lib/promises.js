@@ -0,0 +1,57 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+describe('JsPromise', function() {
+  it('resolves a promise in the same call', async function () {
+    assert.equal(await addon.resolve_promise(42), 42);
+  });
+
+  it('resolves a promise in a later call', async function () {
+    var promise = addon.defer_promise();
+    addon.settle_deferred(true, "done");
+    assert.equal(await promise, "done");
+  });
+
+  it('rejects a promise in a later call', async function () {
+    var promise = addon.defer_promise();
+    addon.settle_deferred(false, new Error("failed"));
+    try {
+      await promise;
+    } catch (err) {
+      assert.equal(err.message, "failed");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+
+  it('rejects a promise whose Deferred is dropped', async function () {
+    try {
+      await addon.drop_deferred();
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.equal(err.message, "Deferred dropped without settling its promise");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+
+  it('rejects a promise whose Deferred is dropped on another thread', async function () {
+    try {
+      await addon.drop_deferred_on_thread();
+    } catch (err) {
+      assert.equal(err.message, "Deferred dropped without settling its promise");
+      return;
+    }
+    throw new Error("expected promise to be rejected");
+  });
+
+  it('chains a fulfillment callback from Rust', async function () {
+    var result = await addon.then_from_rust(Promise.resolve(2), n => n * 2);
+    assert.equal(result, 4);
+  });
+
+  it('chains a rejection callback from Rust', async function () {
+    var result = await addon.then_from_rust(Promise.reject(new Error("oops")), () => "fulfilled", err => err.message);
+    assert.equal(result, "oops");
+  });
+});
@@ -0,0 +1,88 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+async function collectUntil(done) {
+  for (var i = 0; i < 10 && !done(); i++) {
+    global.gc();
+    await new Promise(resolve => setImmediate(resolve));
+  }
+}
+
+describe('Task', function() {
+  it('completes a successful task', function (done) {
+    addon.perform_async_task((err, n) => {
+      if (err) {
+        done(err);
+      } else if (n === 17) {
+        done();
+      } else {
+        done(new Error("not 17 but: " + n));
+      }
+    });
+  });
+
+  it('completes a failing task', function (done) {
+    addon.perform_failing_task((err, n) => {
+      if (err) {
+        if (err.message === 'I am a failing task') {
+          done();
+        } else {
+          done(new Error("expected error message 'I am a failing task', got: " + err.message));
+        }
+      } else {
+        done(new Error("expected task to fail, got: " + n));
+      }
+    });
+  });
+
+  it('executes microtasks after callback', function () {
+    return new Promise((resolve, reject) => {
+      addon.perform_async_task((err, res) => {
+        if (err) {
+          reject(err);
+        } else {
+          resolve(res);
+        }
+      });
+    });
+  });
+
+  it('resolves the promise of a successful task', async function () {
+    const n = await addon.perform_async_task_promise();
+    assert.equal(n, 17);
+  });
+
+  it('rejects the promise of a failing task', async function () {
+    try {
+      await addon.perform_failing_task_promise();
+    } catch (err) {
+      assert.equal(err.message, 'I am a failing task');
+      return;
+    }
+    throw new Error("expected task to fail");
+  });
+
+  it('keeps a value alive in a task with a persistent handle', function (done) {
+    var target = {};
+    addon.perform_task_with_persistent(target, (err, result) => {
+      if (err) {
+        done(err);
+        return;
+      }
+      assert.strictEqual(result, target);
+      assert.equal(target.count, 42);
+      done();
+    });
+  });
+
+  (global.gc ? it : it.skip)('releases a persistent handle dropped on another thread', async function () {
+    var before = addon.collected_count();
+    (function () {
+      var target = {};
+      addon.count_when_collected(target);
+      addon.release_persistent_on_thread(target);
+    })();
+    await collectUntil(() => addon.collected_count() > before);
+    assert.isAbove(addon.collected_count(), before);
+  });
+});
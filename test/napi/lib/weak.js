@@ -0,0 +1,31 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+// Collects garbage until `done()` is true, giving deferred finalizers a chance to run.
+async function collectUntil(done) {
+  for (var i = 0; i < 10 && !done(); i++) {
+    global.gc();
+    await new Promise(resolve => setImmediate(resolve));
+  }
+}
+
+describe('Weak', function() {
+  it('produces the object of a weak handle while it is alive', function () {
+    var obj = {};
+    addon.track_object(obj);
+    assert.strictEqual(addon.tracked_object(), obj);
+  });
+
+  (global.gc ? it : it.skip)('clears a weak handle once its object is collected', async function () {
+    (function () { addon.track_object({}); })();
+    await collectUntil(() => addon.tracked_object() === undefined);
+    assert.isUndefined(addon.tracked_object());
+  });
+
+  (global.gc ? it : it.skip)('runs a finalizer once its object is collected', async function () {
+    var before = addon.collected_count();
+    (function () { addon.count_when_collected({}); })();
+    await collectUntil(() => addon.collected_count() > before);
+    assert.equal(addon.collected_count(), before + 1);
+  });
+});
@@ -0,0 +1,83 @@
+var addon = require('../native');
+var assert = require('chai').assert;
+
+var worker_threads;
+try {
+  worker_threads = require('worker_threads');
+} catch (e) {
+  // Worker threads aren't available in this version of Node.
+}
+
+// Runs `source` in a new worker thread, where `addon` is the module loaded from the
+// same path as in the main thread, and resolves to the messages the worker posted.
+function runWorker(source) {
+  return new Promise((resolve, reject) => {
+    var messages = [];
+    var worker = new worker_threads.Worker(`
+      const { parentPort, workerData } = require('worker_threads');
+      const addon = require(workerData);
+      ${source}
+    `, { eval: true, workerData: require.resolve('../native') });
+
+    worker.on('message', message => messages.push(message));
+    worker.on('error', reject);
+    worker.on('exit', code => {
+      if (code === 0) {
+        resolve(messages);
+      } else {
+        reject(new Error("worker exited with code " + code));
+      }
+    });
+  });
+}
+
+(worker_threads ? describe : describe.skip)('Worker threads', function() {
+  it('loads the module in a worker', function () {
+    return runWorker(`parentPort.postMessage(addon.return_js_string());`).then(messages => {
+      assert.deepEqual(messages, ["hello node"]);
+    });
+  });
+
+  it('loads the module in several workers at once', function () {
+    var source = `parentPort.postMessage(addon.return_js_string());`;
+    return Promise.all([runWorker(source), runWorker(source), runWorker(source)]).then(results => {
+      assert.deepEqual(results, [["hello node"], ["hello node"], ["hello node"]]);
+    });
+  });
+
+  it('constructs classes in a worker', function () {
+    return runWorker(`
+      const u = new addon.User(1, "some", "thing", "else");
+      parentPort.postMessage(u instanceof addon.User);
+      parentPort.postMessage(u.get('first_name'));
+    `).then(messages => {
+      assert.deepEqual(messages, [true, "some"]);
+    });
+  });
+
+  it('completes a task in a worker', function () {
+    return runWorker(`
+      addon.perform_async_task((err, n) => parentPort.postMessage(err ? err.message : n));
+    `).then(messages => {
+      assert.deepEqual(messages, [17]);
+    });
+  });
+
+  it('keeps separate instance data for each worker', function () {
+    var before = addon.count_calls();
+    return runWorker(`
+      parentPort.postMessage(addon.count_calls());
+      parentPort.postMessage(addon.count_calls());
+    `).then(messages => {
+      assert.deepEqual(messages, [1, 2]);
+      assert.equal(addon.count_calls(), before + 1);
+    });
+  });
+
+  it('runs cleanup hooks when a worker exits', function () {
+    var before = addon.cleanup_hook_runs();
+    return runWorker(`addon.add_cleanup_hook();`).then(() => {
+      assert.equal(addon.cleanup_hook_runs(), before + 1);
+    });
+  });
+});
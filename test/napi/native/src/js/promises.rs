@@ -0,0 +1,55 @@
+use std::cell::RefCell;
+use neon::prelude::*;
+use neon::types::Deferred;
+
+pub fn resolve_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let value = cx.argument::<JsValue>(0)?;
+    let (promise, deferred) = JsPromise::new(&mut cx);
+    deferred.resolve(&mut cx, value)?;
+    Ok(promise)
+}
+
+pub fn drop_deferred(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let (promise, _) = JsPromise::new(&mut cx);
+    Ok(promise)
+}
+
+pub fn drop_deferred_on_thread(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let (promise, deferred) = JsPromise::new(&mut cx);
+    std::thread::spawn(move || drop(deferred));
+    Ok(promise)
+}
+
+thread_local! {
+    static PENDING: RefCell<Option<Deferred>> = RefCell::new(None);
+}
+
+pub fn defer_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let (promise, deferred) = JsPromise::new(&mut cx);
+    PENDING.with(|pending| *pending.borrow_mut() = Some(deferred));
+    Ok(promise)
+}
+
+pub fn settle_deferred(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let fulfill = cx.argument::<JsBoolean>(0)?.value();
+    let value = cx.argument::<JsValue>(1)?;
+    let deferred = match PENDING.with(|pending| pending.borrow_mut().take()) {
+        Some(deferred) => deferred,
+        None => return cx.throw_error("no promise is pending")
+    };
+    if fulfill {
+        deferred.resolve(&mut cx, value)?;
+    } else {
+        deferred.reject(&mut cx, value)?;
+    }
+    Ok(cx.undefined())
+}
+
+pub fn then_from_rust(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let promise = cx.argument::<JsPromise>(0)?;
+    let on_fulfilled = cx.argument::<JsFunction>(1)?;
+    let on_rejected = cx.argument_opt(2)
+        .map(|f| f.downcast_or_throw::<JsFunction, _>(&mut cx))
+        .transpose()?;
+    promise.then(&mut cx, on_fulfilled, on_rejected)
+}
@@ -0,0 +1,90 @@
+use neon::prelude::*;
+use neon::handle::Persistent;
+
+struct SuccessTask;
+
+impl Task for SuccessTask {
+    type Output = i32;
+    type Error = String;
+    type JsEvent = JsNumber;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        Ok(17)
+    }
+
+    fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+        Ok(cx.number(result.unwrap()))
+    }
+}
+
+pub fn perform_async_task(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    SuccessTask.schedule(f);
+    Ok(cx.undefined())
+}
+
+pub fn perform_async_task_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    Ok(SuccessTask.promise(&mut cx))
+}
+
+struct FailureTask;
+
+impl Task for FailureTask {
+    type Output = i32;
+    type Error = String;
+    type JsEvent = JsNumber;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        Err(format!("I am a failing task"))
+    }
+
+    fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+        cx.throw_error(&result.unwrap_err())
+    }
+}
+
+pub fn perform_failing_task(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    FailureTask.schedule(f);
+    Ok(cx.undefined())
+}
+
+pub fn perform_failing_task_promise(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    Ok(FailureTask.promise(&mut cx))
+}
+
+struct CountTask {
+    target: Persistent<JsObject>
+}
+
+impl Task for CountTask {
+    type Output = u32;
+    type Error = String;
+    type JsEvent = JsObject;
+
+    fn perform(&self) -> Result<Self::Output, Self::Error> {
+        Ok(42)
+    }
+
+    fn complete(self, mut cx: TaskContext, result: Result<Self::Output, Self::Error>) -> JsResult<Self::JsEvent> {
+        let target = self.target.to_handle(&mut cx);
+        let count = cx.number(result.unwrap());
+        target.set(&mut cx, "count", count)?;
+        Ok(target)
+    }
+}
+
+pub fn perform_task_with_persistent(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let target = cx.argument::<JsObject>(0)?;
+    let f = cx.argument::<JsFunction>(1)?;
+    let target = Persistent::new(&mut cx, target);
+    CountTask { target }.schedule(f);
+    Ok(cx.undefined())
+}
+
+pub fn release_persistent_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let target = cx.argument::<JsObject>(0)?;
+    let target = Persistent::new(&mut cx, target);
+    std::thread::spawn(move || drop(target)).join().unwrap();
+    Ok(cx.undefined())
+}
@@ -0,0 +1,32 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use neon::prelude::*;
+
+struct CallCount(u32);
+
+pub fn count_calls(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let count = cx.instance_data::<CallCount>().map(|count| {
+        count.0 += 1;
+        count.0
+    });
+    let count = match count {
+        Some(count) => count,
+        None => {
+            cx.set_instance_data(CallCount(1));
+            1
+        }
+    };
+    Ok(cx.number(count))
+}
+
+static CLEANUP_HOOK_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn add_cleanup_hook(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    cx.add_cleanup_hook(|| {
+        CLEANUP_HOOK_RUNS.fetch_add(1, Ordering::SeqCst);
+    });
+    Ok(cx.undefined())
+}
+
+pub fn cleanup_hook_runs(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(CLEANUP_HOOK_RUNS.load(Ordering::SeqCst) as f64))
+}
@@ -1,3 +1,224 @@
 use neon::prelude::*;
 
-register_module!(_, { });
+mod js {
+    pub mod strings;
+    pub mod numbers;
+    pub mod arrays;
+    pub mod objects;
+    #[path = "../../../../shared/src/js/functions.rs"]
+    pub mod functions;
+    pub mod classes;
+    pub mod tasks;
+    pub mod events;
+    pub mod weak;
+    pub mod promises;
+    pub mod futures;
+    #[path = "../../../../shared/src/js/bigint.rs"]
+    pub mod bigint;
+    #[path = "../../../../shared/src/js/dates.rs"]
+    pub mod dates;
+    #[path = "../../../../shared/src/js/symbols.rs"]
+    pub mod symbols;
+    #[path = "../../../../shared/src/js/typedarrays.rs"]
+    pub mod typedarrays;
+    #[path = "../../../../shared/src/js/external.rs"]
+    pub mod external;
+    #[path = "../../../../shared/src/js/borrow.rs"]
+    pub mod borrow;
+    #[path = "../../../../shared/src/js/serde.rs"]
+    pub mod serde;
+    #[path = "../../../../shared/src/js/convert.rs"]
+    pub mod convert;
+    #[path = "../../../../shared/src/js/derive.rs"]
+    pub mod derive;
+    #[path = "../../../../shared/src/js/export.rs"]
+    pub mod export;
+    pub mod workers;
+    #[path = "../../../../shared/src/js/properties.rs"]
+    pub mod properties;
+}
+
+use js::strings::return_js_string;
+use js::numbers::*;
+use js::arrays::*;
+use js::objects::*;
+use js::functions::*;
+use js::classes::*;
+use js::tasks::*;
+use js::events::*;
+use js::weak::*;
+use js::promises::*;
+use js::futures::*;
+use js::bigint::*;
+use js::dates::*;
+use js::symbols::*;
+use js::typedarrays::*;
+use js::external::*;
+use js::borrow::*;
+use js::serde::*;
+use js::convert::*;
+use js::derive::*;
+use js::workers::*;
+use js::properties::*;
+
+#[neon::main]
+fn main(mut cx: ModuleContext) -> NeonResult<()> {
+    cx.export_function("return_js_string", return_js_string)?;
+
+    cx.export_function("return_js_number", return_js_number)?;
+    cx.export_function("return_large_js_number", return_large_js_number)?;
+    cx.export_function("return_negative_js_number", return_negative_js_number)?;
+    cx.export_function("return_float_js_number", return_float_js_number)?;
+    cx.export_function("return_negative_float_js_number", return_negative_float_js_number)?;
+    cx.export_function("accept_and_return_js_number", accept_and_return_js_number)?;
+    cx.export_function("accept_and_return_large_js_number", accept_and_return_large_js_number)?;
+    cx.export_function("accept_and_return_float_js_number", accept_and_return_float_js_number)?;
+    cx.export_function("accept_and_return_negative_js_number", accept_and_return_negative_js_number)?;
+
+    cx.export_function("return_js_array", return_js_array)?;
+    cx.export_function("return_js_array_with_number", return_js_array_with_number)?;
+    cx.export_function("return_js_array_with_string", return_js_array_with_string)?;
+
+    cx.export_function("return_js_global_object", return_js_global_object)?;
+    cx.export_function("return_js_object", return_js_object)?;
+    cx.export_function("return_js_object_with_number", return_js_object_with_number)?;
+    cx.export_function("return_js_object_with_string", return_js_object_with_string)?;
+    cx.export_function("return_js_object_with_mixed_content", return_js_object_with_mixed_content)?;
+    cx.export_function("return_array_buffer", return_array_buffer)?;
+    cx.export_function("return_buffer", return_buffer)?;
+    cx.export_function("read_array_buffer_with_lock", read_array_buffer_with_lock)?;
+    cx.export_function("read_array_buffer_with_borrow", read_array_buffer_with_borrow)?;
+    cx.export_function("write_array_buffer_with_lock", write_array_buffer_with_lock)?;
+    cx.export_function("write_array_buffer_with_borrow_mut", write_array_buffer_with_borrow_mut)?;
+    cx.export_function("read_buffer_with_lock", read_buffer_with_lock)?;
+    cx.export_function("read_buffer_with_borrow", read_buffer_with_borrow)?;
+    cx.export_function("write_buffer_with_lock", write_buffer_with_lock)?;
+    cx.export_function("write_buffer_with_borrow_mut", write_buffer_with_borrow_mut)?;
+    cx.export_function("own_property_names", own_property_names)?;
+
+    cx.export_function("return_js_function", return_js_function)?;
+    cx.export_function("call_js_function", call_js_function)?;
+    cx.export_function("make_counter", make_counter)?;
+    cx.export_function("make_tracked_closure", make_tracked_closure)?;
+    cx.export_function("dropped_closures", dropped_closures)?;
+
+    let greeting = "hello from a closure".to_string();
+    cx.export_function("closure_greeting", move |mut cx| Ok(cx.string(&greeting)))?;
+    cx.export_function("construct_js_function", construct_js_function)?;
+    cx.export_function("num_arguments", num_arguments)?;
+    cx.export_function("return_this", return_this)?;
+    cx.export_function("require_object_this", require_object_this)?;
+    cx.export_function("is_argument_zero_some", is_argument_zero_some)?;
+    cx.export_function("require_argument_zero_string", require_argument_zero_string)?;
+    cx.export_function("execute_scoped", execute_scoped)?;
+    cx.export_function("compute_scoped", compute_scoped)?;
+
+    cx.export_function("check_string_and_number", check_string_and_number)?;
+
+    cx.export_function("perform_async_task", perform_async_task)?;
+    cx.export_function("perform_failing_task", perform_failing_task)?;
+    cx.export_function("perform_async_task_promise", perform_async_task_promise)?;
+    cx.export_function("perform_failing_task_promise", perform_failing_task_promise)?;
+    cx.export_function("perform_task_with_persistent", perform_task_with_persistent)?;
+    cx.export_function("release_persistent_on_thread", release_persistent_on_thread)?;
+
+    cx.export_function("emit_events", emit_events)?;
+    cx.export_function("emit_event_with_this", emit_event_with_this)?;
+
+    cx.export_function("track_object", track_object)?;
+    cx.export_function("tracked_object", tracked_object)?;
+    cx.export_function("count_when_collected", count_when_collected)?;
+    cx.export_function("collected_count", collected_count)?;
+
+    cx.export_function("resolve_promise", resolve_promise)?;
+    cx.export_function("defer_promise", defer_promise)?;
+    cx.export_function("drop_deferred", drop_deferred)?;
+    cx.export_function("drop_deferred_on_thread", drop_deferred_on_thread)?;
+    cx.export_function("settle_deferred", settle_deferred)?;
+    cx.export_function("then_from_rust", then_from_rust)?;
+
+    cx.export_function("count_calls", count_calls)?;
+    cx.export_function("add_cleanup_hook", add_cleanup_hook)?;
+    cx.export_function("cleanup_hook_runs", cleanup_hook_runs)?;
+
+    cx.export_function("add_async", add_async)?;
+    cx.export_function("fail_async", fail_async)?;
+
+    cx.export_function("panic", panic)?;
+    cx.export_function("panic_after_throw", panic_after_throw)?;
+
+    cx.export_class::<JsUser>("User")?;
+    cx.export_class::<JsPanickyAllocator>("PanickyAllocator")?;
+    cx.export_class::<JsPanickyConstructor>("PanickyConstructor")?;
+
+    cx.export_function("return_bigint_i64_min", return_bigint_i64_min)?;
+    cx.export_function("return_bigint_u64_max", return_bigint_u64_max)?;
+    cx.export_function("return_bigint_i128_min", return_bigint_i128_min)?;
+    cx.export_function("return_bigint_u128_max", return_bigint_u128_max)?;
+    cx.export_function("round_trip_bigint_i64", round_trip_bigint_i64)?;
+    cx.export_function("round_trip_bigint_u64", round_trip_bigint_u64)?;
+    cx.export_function("round_trip_bigint_i128", round_trip_bigint_i128)?;
+    cx.export_function("round_trip_bigint_u128", round_trip_bigint_u128)?;
+    cx.export_function("truncate_bigint_i64", truncate_bigint_i64)?;
+    cx.export_function("is_bigint", is_bigint)?;
+
+    cx.export_function("create_date", create_date)?;
+    cx.export_function("create_date_from_system_time", create_date_from_system_time)?;
+    cx.export_function("create_date_before_epoch", create_date_before_epoch)?;
+    cx.export_function("get_date_value", get_date_value)?;
+    cx.export_function("is_valid_date", is_valid_date)?;
+    cx.export_function("is_date", is_date)?;
+
+    cx.export_function("create_symbol", create_symbol)?;
+    cx.export_function("create_symbol_with_description", create_symbol_with_description)?;
+    cx.export_function("symbol_for", symbol_for)?;
+    cx.export_function("get_symbol_description", get_symbol_description)?;
+    cx.export_function("get_well_known_symbols", get_well_known_symbols)?;
+    cx.export_function("create_tagged_object", create_tagged_object)?;
+    cx.export_function("is_symbol", is_symbol)?;
+
+    cx.export_function("create_float32_array", create_float32_array)?;
+    cx.export_function("sum_float32_array", sum_float32_array)?;
+    cx.export_function("double_float64_array", double_float64_array)?;
+    cx.export_function("get_typed_array_info", get_typed_array_info)?;
+    cx.export_function("is_uint8_array", is_uint8_array)?;
+    cx.export_function("create_data_view", create_data_view)?;
+    cx.export_function("fill_data_view", fill_data_view)?;
+    cx.export_function("is_data_view", is_data_view)?;
+
+    cx.export_function("external_buffer", external_buffer)?;
+    cx.export_function("external_array_buffer", external_array_buffer)?;
+    cx.export_function("tracked_external_buffer", tracked_external_buffer)?;
+    cx.export_function("tracked_external_array_buffer", tracked_external_array_buffer)?;
+    cx.export_function("dropped_externals", dropped_externals)?;
+
+    cx.export_function("borrow_mut_overlaps_borrow", borrow_mut_overlaps_borrow)?;
+    cx.export_function("borrow_overlaps_borrow", borrow_overlaps_borrow)?;
+    cx.export_function("buffer_borrow_mut_overlaps_array_buffer", buffer_borrow_mut_overlaps_array_buffer)?;
+    cx.export_function("borrow_again_after_release", borrow_again_after_release)?;
+
+    cx.export_function("make_user", make_user)?;
+    cx.export_function("roundtrip_user", roundtrip_user)?;
+    cx.export_function("describe_role", describe_role)?;
+    cx.export_function("point_length", point_length)?;
+    cx.export_function("count_words", count_words)?;
+    cx.export_function("ignore_value", ignore_value)?;
+    cx.export_function("reverse_bytes", reverse_bytes)?;
+
+    cx.export_typed_function("add_numbers", add_numbers)?;
+    cx.export_typed_function("describe_args", describe_args)?;
+    cx.export_typed_function("sum_list", sum_list)?;
+    cx.export_typed_function("swap_pair", swap_pair)?;
+    cx.export_typed_function("double_values", double_values)?;
+    cx.export_typed_function("halve_float", halve_float)?;
+    cx.export_typed_function("maybe_string", maybe_string)?;
+    cx.export_typed_function("large_integer", large_integer)?;
+    cx.export_function("convert_with_handles", convert_with_handles)?;
+
+    cx.export_typed_function("make_profile", make_profile)?;
+    cx.export_typed_function("touch_profile", touch_profile)?;
+    cx.export_typed_function("shape_area", shape_area)?;
+    cx.export_typed_function("scale_shape", scale_shape)?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('JsBigInt', function() {
+  it('returns 64-bit extremes built in Rust', function () {
+    assert.strictEqual(addon.return_bigint_i64_min(), -(2n ** 63n));
+    assert.strictEqual(addon.return_bigint_u64_max(), 2n ** 64n - 1n);
+  });
+
+  it('returns 128-bit extremes built in Rust', function () {
+    assert.strictEqual(addon.return_bigint_i128_min(), -(2n ** 127n));
+    assert.strictEqual(addon.return_bigint_u128_max(), 2n ** 128n - 1n);
+  });
+
+  it('round trips values that fit', function () {
+    assert.strictEqual(addon.round_trip_bigint_i64(-42n), -42n);
+    assert.strictEqual(addon.round_trip_bigint_i64(2n ** 63n - 1n), 2n ** 63n - 1n);
+    assert.strictEqual(addon.round_trip_bigint_u64(2n ** 64n - 1n), 2n ** 64n - 1n);
+    assert.strictEqual(addon.round_trip_bigint_i128(-(2n ** 127n)), -(2n ** 127n));
+    assert.strictEqual(addon.round_trip_bigint_i128(2n ** 127n - 1n), 2n ** 127n - 1n);
+    assert.strictEqual(addon.round_trip_bigint_u128(0n), 0n);
+    assert.strictEqual(addon.round_trip_bigint_u128(2n ** 128n - 1n), 2n ** 128n - 1n);
+  });
+
+  it('reports lossy conversions', function () {
+    assert.throws(function () { addon.round_trip_bigint_i64(2n ** 63n); }, RangeError);
+    assert.throws(function () { addon.round_trip_bigint_u64(-1n); }, RangeError);
+    assert.throws(function () { addon.round_trip_bigint_u64(2n ** 64n); }, RangeError);
+    assert.throws(function () { addon.round_trip_bigint_i128(2n ** 127n); }, RangeError);
+    assert.throws(function () { addon.round_trip_bigint_i128(-(2n ** 127n) - 1n); }, RangeError);
+    assert.throws(function () { addon.round_trip_bigint_u128(-1n); }, RangeError);
+    assert.throws(function () { addon.round_trip_bigint_u128(2n ** 128n); }, RangeError);
+  });
+
+  it('truncates lossy conversions', function () {
+    assert.strictEqual(addon.truncate_bigint_i64(2n ** 64n + 5n), 5n);
+    assert.strictEqual(addon.truncate_bigint_i64(2n ** 63n), -(2n ** 63n));
+  });
+
+  it('correctly identifies BigInt values', function () {
+    assert.isTrue(addon.is_bigint(1n));
+    assert.isFalse(addon.is_bigint(1));
+    assert.isFalse(addon.is_bigint('1'));
+    assert.isFalse(addon.is_bigint(Object(1n)));
+  });
+});
@@ -0,0 +1,29 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('Borrowing', function() {
+  it('rejects a mutable borrow of memory that is already borrowed through another view', function () {
+    var buffer = new ArrayBuffer(16);
+    assert.isTrue(addon.borrow_mut_overlaps_borrow(new Uint8Array(buffer, 0, 8), new Uint8Array(buffer, 4, 8)));
+    assert.isTrue(addon.borrow_mut_overlaps_borrow(new Uint8Array(buffer), new Uint8Array(buffer)));
+  });
+
+  it('allows a mutable borrow of disjoint views of the same memory', function () {
+    var buffer = new ArrayBuffer(16);
+    assert.isFalse(addon.borrow_mut_overlaps_borrow(new Uint8Array(buffer, 0, 8), new Uint8Array(buffer, 8, 8)));
+  });
+
+  it('allows overlapping immutable borrows', function () {
+    var buffer = new ArrayBuffer(16);
+    assert.isFalse(addon.borrow_overlaps_borrow(new Uint8Array(buffer, 0, 8), new Uint8Array(buffer, 4, 8)));
+  });
+
+  it('rejects a mutable borrow of a Buffer while its ArrayBuffer is borrowed', function () {
+    assert.isTrue(addon.buffer_borrow_mut_overlaps_array_buffer(Buffer.from('hello')));
+  });
+
+  it('releases every loan once the borrows end', function () {
+    var buffer = new ArrayBuffer(16);
+    assert.isTrue(addon.borrow_again_after_release(new Uint8Array(buffer), new Uint8Array(buffer)));
+  });
+});
@@ -0,0 +1,58 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('FromJs and IntoJs', function() {
+  it('converts arguments and return values', function () {
+    assert.strictEqual(addon.add_numbers(1.5, 2), 3.5);
+    assert.strictEqual(addon.describe_args(7, 'seven', true), '7 seven Some(true)');
+  });
+
+  it('treats missing optional arguments as None', function () {
+    assert.strictEqual(addon.describe_args(7, 'seven'), '7 seven None');
+    assert.strictEqual(addon.describe_args(7, 'seven', null), '7 seven None');
+  });
+
+  it('converts arrays, tuples and maps', function () {
+    assert.strictEqual(addon.sum_list([1, 2, 3]), 6);
+    assert.deepEqual(addon.swap_pair(['a', true]), [true, 'a']);
+    assert.deepEqual(addon.double_values({ a: 1, b: 2.5 }), { a: 2, b: 5 });
+    assert.strictEqual(addon.convert_with_handles(['x', 'y']), 2);
+  });
+
+  it('converts None to null', function () {
+    assert.strictEqual(addon.maybe_string(true), 'here');
+    assert.isNull(addon.maybe_string(false));
+  });
+
+  it('throws a TypeError naming the argument that fails to convert', function () {
+    assert.throws(function () { addon.add_numbers(1, 'two'); }, TypeError, /argument 1: expected a number, found string/);
+    assert.throws(function () { addon.add_numbers(1); }, TypeError, /argument 1: expected a number, found undefined/);
+    assert.throws(function () { addon.sum_list([1, null]); }, TypeError, /argument 0: element 1: expected a number, found null/);
+    assert.throws(function () { addon.double_values({ a: 'x' }); }, TypeError, /argument 0: property "a"/);
+    assert.throws(function () { addon.swap_pair(['a']); }, TypeError, /expected an array of length 2/);
+  });
+
+  it('checks the range of integers', function () {
+    assert.throws(function () { addon.describe_args(256, 'x'); }, TypeError, /argument 0: 256 is out of range for u8/);
+    assert.throws(function () { addon.describe_args(-1, 'x'); }, TypeError, /out of range/);
+    assert.throws(function () { addon.describe_args(1.5, 'x'); }, TypeError, /expected an integer/);
+    assert.throws(function () { addon.sum_list([2147483648]); }, TypeError, /out of range for i32/);
+  });
+
+  it('checks the range of f32 values', function () {
+    assert.strictEqual(addon.halve_float(3), 1.5);
+    assert.strictEqual(addon.halve_float(Infinity), Infinity);
+    assert.throws(function () { addon.halve_float(1e40); }, TypeError, /argument 0: .* is out of range for f32/);
+  });
+
+  it('converts only own enumerable properties to a map', function () {
+    var o = { a: 1 };
+    Object.defineProperty(o, 'x', { value: 'hidden', enumerable: false });
+    assert.deepEqual(addon.double_values(o), { a: 2 });
+  });
+
+  it('throws a RangeError for integers that cannot be represented exactly', function () {
+    assert.strictEqual(addon.large_integer(true), Number.MAX_SAFE_INTEGER);
+    assert.throws(function () { addon.large_integer(false); }, RangeError);
+  });
+});
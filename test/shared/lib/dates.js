@@ -0,0 +1,38 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('JsDate', function() {
+  it('creates a date from milliseconds since the epoch', function () {
+    var date = addon.create_date(1234567890123);
+    assert.instanceOf(date, Date);
+    assert.strictEqual(date.getTime(), 1234567890123);
+  });
+
+  it('creates a date from a SystemTime', function () {
+    assert.strictEqual(addon.create_date_from_system_time().getTime(), 1000000000123);
+    assert.strictEqual(addon.create_date_before_epoch().getTime(), -86400000);
+  });
+
+  it('creates an invalid date from an out of range value', function () {
+    var date = addon.create_date(8.64e15 + 1);
+    assert.instanceOf(date, Date);
+    assert.isNaN(date.getTime());
+  });
+
+  it('reads the value of a date', function () {
+    assert.strictEqual(addon.get_date_value(new Date(0)), 0);
+    assert.strictEqual(addon.get_date_value(new Date(-1000)), -1000);
+    assert.isNaN(addon.get_date_value(new Date(NaN)));
+  });
+
+  it('checks the validity of a date', function () {
+    assert.isTrue(addon.is_valid_date(new Date()));
+    assert.isFalse(addon.is_valid_date(new Date('not a date')));
+  });
+
+  it('correctly identifies dates', function () {
+    assert.isTrue(addon.is_date(new Date()));
+    assert.isFalse(addon.is_date(Date.now()));
+    assert.isFalse(addon.is_date({}));
+  });
+});
@@ -0,0 +1,68 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('derive(FromJs, IntoJs)', function() {
+  it('converts a struct to an object with renamed fields', function () {
+    assert.deepEqual(addon.make_profile(), {
+      userName: 'ada',
+      loginCount: 3,
+      isAdmin: true,
+      status: 'on-leave',
+      home: [51.5, -0.1]
+    });
+  });
+
+  it('converts an object to a struct with optional and default fields', function () {
+    var profile = addon.touch_profile({
+      userName: 'grace',
+      displayName: 'Grace',
+      isAdmin: false,
+      status: 'active',
+      home: [40.7, -74]
+    });
+    assert.deepEqual(profile, {
+      userName: 'grace',
+      displayName: 'GRACE',
+      loginCount: 1,
+      isAdmin: false,
+      status: 'active',
+      home: [40.7, -74]
+    });
+  });
+
+  it('converts tagged enums', function () {
+    assert.closeTo(addon.shape_area({ kind: 'circle', radius: 1 }), Math.PI, 1e-9);
+    assert.strictEqual(addon.shape_area({ kind: 'rectangle', sideA: 2, sideB: 3 }), 6);
+    assert.strictEqual(addon.shape_area({ kind: 'point' }), 0);
+    assert.strictEqual(addon.shape_area({ kind: 'polygon', value: [[0, 0], [4, 0], [0, 3]] }), 6);
+    assert.deepEqual(addon.scale_shape({ kind: 'rectangle', sideA: 2, sideB: 3 }, 2), { kind: 'rectangle', sideA: 4, sideB: 6 });
+    assert.deepEqual(addon.scale_shape({ kind: 'polygon', value: [[1, 2]] }, 3), { kind: 'polygon', value: [[3, 6]] });
+    assert.deepEqual(addon.scale_shape({ kind: 'point' }, 3), { kind: 'point' });
+  });
+
+  it('throws a TypeError naming the property that fails to convert', function () {
+    assert.throws(function () {
+      addon.touch_profile({ userName: 'x', isAdmin: false, status: 'active' });
+    }, TypeError, /argument 0: property "home": expected an array of length 2, found undefined/);
+    assert.throws(function () {
+      addon.touch_profile({ userName: 'x', isAdmin: false, status: 'retired', home: [0, 0] });
+    }, TypeError, /property "status": unknown variant "retired", expected one of "active", "on-leave"/);
+    assert.throws(function () {
+      addon.shape_area({ kind: 'hexagon' });
+    }, TypeError, /property "kind": unknown variant "hexagon"/);
+    assert.throws(function () {
+      addon.shape_area([]);
+    }, TypeError, /expected an object, found array/);
+  });
+
+  it('only uses the default for a missing or undefined property', function () {
+    var profile = { userName: 'x', isAdmin: false, status: 'active', home: [0, 0] };
+    assert.strictEqual(addon.touch_profile(profile).loginCount, 1);
+    profile.loginCount = undefined;
+    assert.strictEqual(addon.touch_profile(profile).loginCount, 1);
+    profile.loginCount = null;
+    assert.throws(function () {
+      addon.touch_profile(profile);
+    }, TypeError, /property "loginCount": expected a number, found null/);
+  });
+});
@@ -0,0 +1,36 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('neon::export', function() {
+  it('exports functions without explicit registration', function () {
+    assert.typeOf(addon.exported_add, 'function');
+    assert.strictEqual(addon.exported_add(1, 2), 3);
+  });
+
+  it('overrides the name of an export', function () {
+    assert.strictEqual(addon.exportedGreeting('Ada'), 'Hello, Ada.');
+    assert.strictEqual(addon.exportedGreeting('Ada', true), 'Hello, Ada!');
+    assert.isUndefined(addon.exported_greeting);
+  });
+
+  it('converts arguments and throws a TypeError naming the bad argument', function () {
+    assert.throws(function () { addon.exported_add(1, '2'); }, TypeError, /argument 1/);
+  });
+
+  it('throws the error of a Result', function () {
+    assert.strictEqual(addon.exported_divide(7, 2), 3);
+    assert.throws(function () { addon.exported_divide(1, 0); }, Error, /division by zero/);
+  });
+
+  it('returns undefined from a function without a return value', function () {
+    assert.isUndefined(addon.exported_nothing());
+  });
+
+  it('passes the context to functions that take one', function () {
+    assert.deepEqual(addon.exported_with_context(3), [0, 1, 2]);
+    assert.throws(function () { addon.exported_with_context(101); }, RangeError, /too long/);
+    assert.strictEqual(addon.exported_neon_function(4), 'got 4');
+    assert.isTrue(addon.exported_typed_neon_function(1));
+    assert.isFalse(addon.exported_typed_neon_function());
+  });
+});
@@ -0,0 +1,45 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+// Collects garbage until `done()` is true, giving deferred finalizers a chance to run.
+async function collectUntil(done) {
+  for (var i = 0; i < 10 && !done(); i++) {
+    global.gc();
+    await new Promise(resolve => setImmediate(resolve));
+  }
+}
+
+describe('External buffers', function() {
+  it('exposes a Vec<u8> as a Buffer', function () {
+    var buffer = addon.external_buffer();
+    assert.instanceOf(buffer, Buffer);
+    assert.deepEqual(Array.from(buffer), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+  });
+
+  it('exposes a Box<[u8]> as an ArrayBuffer', function () {
+    var buffer = addon.external_array_buffer();
+    assert.instanceOf(buffer, ArrayBuffer);
+    assert.deepEqual(Array.from(new Uint8Array(buffer)), [1, 2, 3, 4]);
+  });
+
+  it('shares memory with the Rust value', function () {
+    var buffer = addon.tracked_external_buffer();
+    buffer[0] = 7;
+    assert.strictEqual(buffer[0], 7);
+    assert.strictEqual(buffer[1], 42);
+  });
+
+  (global.gc ? it : it.skip)('drops the Rust value once a Buffer is collected', async function () {
+    var before = addon.dropped_externals();
+    (function () { addon.tracked_external_buffer(); })();
+    await collectUntil(() => addon.dropped_externals() > before);
+    assert.isAbove(addon.dropped_externals(), before);
+  });
+
+  (global.gc ? it : it.skip)('drops the Rust value once an ArrayBuffer is collected', async function () {
+    var before = addon.dropped_externals();
+    (function () { addon.tracked_external_array_buffer(); })();
+    await collectUntil(() => addon.dropped_externals() > before);
+    assert.isAbove(addon.dropped_externals(), before);
+  });
+});
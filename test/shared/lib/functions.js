@@ -1,5 +1,12 @@
-var addon = require('../native');
-var assert = require('chai').assert;
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+async function collectUntil(done) {
+  for (var i = 0; i < 10 && !done(); i++) {
+    global.gc();
+    await new Promise(resolve => setImmediate(resolve));
+  }
+}
 
 describe('JsFunction', function() {
   it('return a JsFunction built in Rust', function () {
@@ -10,6 +17,24 @@ describe('JsFunction', function() {
     assert.equal(addon.return_js_function()(41), 42);
   });
 
+  it('return a JsFunction built from a closure with captured state', function () {
+    var counter = addon.make_counter(10);
+    assert.equal(counter(), 11);
+    assert.equal(counter(), 12);
+    assert.equal(addon.make_counter(0)(), 1);
+  });
+
+  it('export a closure from a module', function () {
+    assert.equal(addon.closure_greeting(), 'hello from a closure');
+  });
+
+  (global.gc ? it : it.skip)('drops the captured state once the function is collected', async function () {
+    var before = addon.dropped_closures();
+    (function () { addon.make_tracked_closure()(); })();
+    await collectUntil(() => addon.dropped_closures() > before);
+    assert.isAbove(addon.dropped_closures(), before);
+  });
+
   it('call a JsFunction built in JS that implements x => x + 1', function () {
     assert.equal(addon.call_js_function(function(x) { return x + 1 }), 17);
   });
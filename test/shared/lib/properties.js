@@ -0,0 +1,30 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+// V8 can report index keys as numbers, so compare the names as strings.
+function names(o) {
+  return addon.own_property_names(o).map(String);
+}
+
+describe('Object::get_own_property_names', function() {
+  it('lists own enumerable string keys', function () {
+    assert.deepEqual(names({ a: 1, b: 'two', 3: null }), ['3', 'a', 'b']);
+  });
+
+  it('skips non-enumerable properties', function () {
+    var o = { visible: true };
+    Object.defineProperty(o, 'hidden', { value: true, enumerable: false });
+    assert.deepEqual(names(o), ['visible']);
+  });
+
+  it('skips an array\'s length', function () {
+    assert.deepEqual(names(['x', 'y']), ['0', '1']);
+  });
+
+  it('skips symbol keys and inherited properties', function () {
+    var o = Object.create({ inherited: true });
+    o[Symbol('sym')] = true;
+    o.own = true;
+    assert.deepEqual(names(o), ['own']);
+  });
+});
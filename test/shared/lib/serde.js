@@ -0,0 +1,91 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('serde', function() {
+  it('converts a Rust struct to a JavaScript object', function () {
+    assert.deepEqual(addon.make_user(), {
+      name: 'Ada',
+      age: 36,
+      email: null,
+      tags: ['math', 'engines'],
+      role: { Member: { since: 1843 } }
+    });
+  });
+
+  it('roundtrips a JavaScript object through a Rust struct', function () {
+    var user = addon.roundtrip_user({
+      name: 'Grace',
+      age: 85,
+      email: 'grace@example.com',
+      tags: [],
+      role: 'Guest'
+    });
+    assert.deepEqual(user, {
+      name: 'Grace',
+      age: 86,
+      email: 'grace@example.com',
+      tags: [],
+      role: 'Guest'
+    });
+  });
+
+  it('converts externally tagged enums', function () {
+    assert.strictEqual(addon.describe_role('Guest'), 'guest');
+    assert.strictEqual(addon.describe_role({ Member: { since: 2020 } }), 'member since 2020');
+    assert.strictEqual(addon.describe_role({ Admin: 'wiki' }), 'admin of wiki');
+  });
+
+  it('ignores non-enumerable properties', function () {
+    class Point {
+      constructor(x, y) {
+        this.x = x;
+        this.y = y;
+        Object.defineProperty(this, 'cached', { value: null, writable: true, enumerable: false });
+      }
+
+      get length() {
+        return Math.hypot(this.x, this.y);
+      }
+    }
+
+    assert.strictEqual(addon.point_length(new Point(3, 4)), 5);
+    assert.throws(function () { addon.point_length({ x: 3, y: 4, z: 0 }); }, TypeError);
+
+    var role = { Admin: 'wiki' };
+    Object.defineProperty(role, 'note', { value: 'hidden', enumerable: false });
+    assert.strictEqual(addon.describe_role(role), 'admin of wiki');
+  });
+
+  it('converts sequences and maps', function () {
+    assert.deepEqual(addon.count_words(['a', 'b', 'a']), { a: 2, b: 1 });
+  });
+
+  it('converts bytes to and from Buffers', function () {
+    var reversed = addon.reverse_bytes(Buffer.from([1, 2, 3]));
+    assert.instanceOf(reversed, Buffer);
+    assert.deepEqual(Array.from(reversed), [3, 2, 1]);
+  });
+
+  it('throws a TypeError for a value with the wrong shape', function () {
+    assert.throws(function () { addon.roundtrip_user({ name: 'Ada' }); }, TypeError);
+    assert.throws(function () { addon.count_words([1, 2]); }, TypeError);
+    assert.throws(function () { addon.describe_role('Owner'); }, TypeError);
+  });
+
+  it('deserializes deeply nested values', function () {
+    var value = [];
+    for (var i = 0; i < 50; i++) {
+      value = { inner: [value] };
+    }
+    addon.ignore_value(value);
+  });
+
+  it('throws a TypeError for a value that refers to itself', function () {
+    var obj = {};
+    obj.self = obj;
+    assert.throws(function () { addon.ignore_value(obj); }, TypeError, /refers to itself/);
+    var arr = [];
+    arr.push(arr);
+    assert.throws(function () { addon.ignore_value(arr); }, TypeError, /refers to itself/);
+  });
+});
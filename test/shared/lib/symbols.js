@@ -0,0 +1,46 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+var util = require('util');
+
+describe('JsSymbol', function() {
+  it('creates a unique symbol', function () {
+    var symbol = addon.create_symbol();
+    assert.typeOf(symbol, 'symbol');
+    assert.notStrictEqual(symbol, addon.create_symbol());
+    assert.isUndefined(addon.get_symbol_description(symbol));
+  });
+
+  it('creates a symbol with a description', function () {
+    var symbol = addon.create_symbol_with_description('neon');
+    assert.strictEqual(symbol.toString(), 'Symbol(neon)');
+    assert.notStrictEqual(symbol, Symbol.for('neon'));
+    assert.strictEqual(addon.get_symbol_description(symbol), 'neon');
+  });
+
+  it('looks up symbols in the global registry', function () {
+    assert.strictEqual(addon.symbol_for('neon.registry'), Symbol.for('neon.registry'));
+  });
+
+  it('gets well-known symbols', function () {
+    assert.deepEqual(addon.get_well_known_symbols(), [
+      Symbol.iterator,
+      Symbol.asyncIterator,
+      Symbol.hasInstance,
+      Symbol.toPrimitive,
+      Symbol.toStringTag,
+      util.inspect.custom
+    ]);
+  });
+
+  it('uses symbols as property keys', function () {
+    var object = addon.create_tagged_object();
+    assert.strictEqual(Object.prototype.toString.call(object), '[object Rusty]');
+    assert.strictEqual(util.inspect(object), '<rusty>');
+  });
+
+  it('correctly identifies symbols', function () {
+    assert.isTrue(addon.is_symbol(Symbol()));
+    assert.isFalse(addon.is_symbol('symbol'));
+    assert.isFalse(addon.is_symbol(Object(Symbol())));
+  });
+});
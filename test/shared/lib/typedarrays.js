@@ -0,0 +1,70 @@
+var addon = require('../support').addon;
+var assert = require('../support').assert;
+
+describe('JsTypedArray', function() {
+  it('creates a typed array over an ArrayBuffer', function () {
+    var buffer = new ArrayBuffer(16);
+    var array = addon.create_float32_array(buffer, 4, 2);
+    assert.instanceOf(array, Float32Array);
+    assert.strictEqual(array.buffer, buffer);
+    assert.strictEqual(array.byteOffset, 4);
+    assert.strictEqual(array.length, 2);
+  });
+
+  it('rejects views that do not fit in the ArrayBuffer', function () {
+    var buffer = new ArrayBuffer(16);
+    assert.throws(function () { addon.create_float32_array(buffer, 2, 1); }, RangeError);
+    assert.throws(function () { addon.create_float32_array(buffer, 8, 3); }, RangeError);
+  });
+
+  it('borrows the elements of a typed array', function () {
+    assert.strictEqual(addon.sum_float32_array(new Float32Array([1.5, 2.5, 3])), 7);
+  });
+
+  it('mutably borrows the elements of a typed array', function () {
+    var buffer = new ArrayBuffer(32);
+    var whole = new Float64Array(buffer);
+    whole.set([1, 2, 3, 4]);
+    addon.double_float64_array(new Float64Array(buffer, 8, 2));
+    assert.deepEqual(Array.from(whole), [1, 4, 6, 4]);
+  });
+
+  it('reads the buffer, offset and length of a typed array', function () {
+    var buffer = new ArrayBuffer(8);
+    var info = addon.get_typed_array_info(new Uint16Array(buffer, 2, 3));
+    assert.strictEqual(info.buffer, buffer);
+    assert.strictEqual(info.byteOffset, 2);
+    assert.strictEqual(info.length, 3);
+  });
+
+  it('distinguishes typed arrays by element type', function () {
+    assert.isTrue(addon.is_uint8_array(new Uint8Array(4)));
+    assert.isFalse(addon.is_uint8_array(new Int8Array(4)));
+    assert.isFalse(addon.is_uint8_array(new Uint8ClampedArray(4)));
+    assert.isFalse(addon.is_uint8_array(new ArrayBuffer(4)));
+    assert.throws(function () { addon.sum_float32_array(new Float64Array(2)); }, TypeError);
+  });
+});
+
+describe('JsDataView', function() {
+  it('creates a DataView over an ArrayBuffer', function () {
+    var buffer = new ArrayBuffer(8);
+    var view = addon.create_data_view(buffer, 2, 4);
+    assert.instanceOf(view, DataView);
+    assert.strictEqual(view.buffer, buffer);
+    assert.strictEqual(view.byteOffset, 2);
+    assert.strictEqual(view.byteLength, 4);
+    assert.throws(function () { addon.create_data_view(buffer, 6, 4); }, RangeError);
+  });
+
+  it('borrows the bytes of a DataView', function () {
+    var buffer = new ArrayBuffer(4);
+    assert.strictEqual(addon.fill_data_view(new DataView(buffer, 1, 2), 7), 2);
+    assert.deepEqual(Array.from(new Uint8Array(buffer)), [0, 7, 7, 0]);
+  });
+
+  it('correctly identifies DataViews', function () {
+    assert.isTrue(addon.is_data_view(new DataView(new ArrayBuffer(1))));
+    assert.isFalse(addon.is_data_view(new Uint8Array(1)));
+  });
+});
@@ -0,0 +1,50 @@
+use neon::prelude::*;
+
+pub fn return_bigint_i64_min(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    JsBigInt::from_i64(&mut cx, i64::min_value())
+}
+
+pub fn return_bigint_u64_max(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    JsBigInt::from_u64(&mut cx, u64::max_value())
+}
+
+pub fn return_bigint_i128_min(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    JsBigInt::from_i128(&mut cx, i128::min_value())
+}
+
+pub fn return_bigint_u128_max(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    JsBigInt::from_u128(&mut cx, u128::max_value())
+}
+
+pub fn round_trip_bigint_i64(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    let value = cx.argument::<JsBigInt>(0)?.to_i64().or_else(|e| cx.throw_range_error(&e.to_string()))?;
+    JsBigInt::from_i64(&mut cx, value)
+}
+
+pub fn round_trip_bigint_u64(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    let value = cx.argument::<JsBigInt>(0)?.to_u64().or_else(|e| cx.throw_range_error(&e.to_string()))?;
+    JsBigInt::from_u64(&mut cx, value)
+}
+
+pub fn round_trip_bigint_i128(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    let value = cx.argument::<JsBigInt>(0)?.to_i128().or_else(|e| cx.throw_range_error(&e.to_string()))?;
+    JsBigInt::from_i128(&mut cx, value)
+}
+
+pub fn round_trip_bigint_u128(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    let value = cx.argument::<JsBigInt>(0)?.to_u128().or_else(|e| cx.throw_range_error(&e.to_string()))?;
+    JsBigInt::from_u128(&mut cx, value)
+}
+
+pub fn truncate_bigint_i64(mut cx: FunctionContext) -> JsResult<JsBigInt> {
+    let value = match cx.argument::<JsBigInt>(0)?.to_i64() {
+        Ok(v) => v,
+        Err(e) => e.truncated()
+    };
+    JsBigInt::from_i64(&mut cx, value)
+}
+
+pub fn is_bigint(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsValue>(0)?;
+    Ok(cx.boolean(value.is_a::<JsBigInt>()))
+}
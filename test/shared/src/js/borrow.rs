@@ -0,0 +1,52 @@
+use neon::prelude::*;
+
+pub fn borrow_mut_overlaps_borrow(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let a = cx.argument::<JsTypedArray<u8>>(0)?;
+    let mut b = cx.argument::<JsTypedArray<u8>>(1)?;
+    let conflict = {
+        let guard = cx.lock();
+        let _data = a.borrow(&guard);
+        let result = b.try_borrow_mut(&guard).is_err();
+        result
+    };
+    Ok(cx.boolean(conflict))
+}
+
+pub fn borrow_overlaps_borrow(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let a = cx.argument::<JsTypedArray<u8>>(0)?;
+    let b = cx.argument::<JsTypedArray<u8>>(1)?;
+    let conflict = {
+        let guard = cx.lock();
+        let _data = a.borrow(&guard);
+        let result = b.try_borrow(&guard).is_err();
+        result
+    };
+    Ok(cx.boolean(conflict))
+}
+
+pub fn buffer_borrow_mut_overlaps_array_buffer(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let mut buffer = cx.argument::<JsBuffer>(0)?;
+    let array_buffer = buffer.get(&mut cx, "buffer")?.downcast_or_throw::<JsArrayBuffer, _>(&mut cx)?;
+    let conflict = {
+        let guard = cx.lock();
+        let _data = array_buffer.borrow(&guard);
+        let result = buffer.try_borrow_mut(&guard).is_err();
+        result
+    };
+    Ok(cx.boolean(conflict))
+}
+
+pub fn borrow_again_after_release(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let a = cx.argument::<JsTypedArray<u8>>(0)?;
+    let mut b = cx.argument::<JsTypedArray<u8>>(1)?;
+    let ok = {
+        let guard = cx.lock();
+        {
+            let _first = a.borrow(&guard);
+            let _second = a.borrow(&guard);
+        }
+        let result = b.try_borrow_mut(&guard).is_ok();
+        result
+    };
+    Ok(cx.boolean(ok))
+}
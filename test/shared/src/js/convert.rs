@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use neon::prelude::*;
+
+pub fn add_numbers(mut cx: FunctionContext) -> NeonResult<f64> {
+    let (a, b) = cx.args::<(f64, f64)>()?;
+    Ok(a + b)
+}
+
+pub fn describe_args(mut cx: FunctionContext) -> NeonResult<String> {
+    let (n, s, flag) = cx.args::<(u8, String, Option<bool>)>()?;
+    Ok(format!("{} {} {:?}", n, s, flag))
+}
+
+pub fn sum_list(mut cx: FunctionContext) -> NeonResult<i32> {
+    let (list,) = cx.args::<(Vec<i32>,)>()?;
+    Ok(list.into_iter().sum())
+}
+
+pub fn swap_pair(mut cx: FunctionContext) -> NeonResult<(bool, String)> {
+    let ((s, b),) = cx.args::<((String, bool),)>()?;
+    Ok((b, s))
+}
+
+pub fn double_values(mut cx: FunctionContext) -> NeonResult<HashMap<String, f64>> {
+    let (map,) = cx.args::<(HashMap<String, f64>,)>()?;
+    Ok(map.into_iter().map(|(k, v)| (k, v * 2.0)).collect())
+}
+
+pub fn halve_float(mut cx: FunctionContext) -> NeonResult<f32> {
+    let (n,) = cx.args::<(f32,)>()?;
+    Ok(n / 2.0)
+}
+
+pub fn maybe_string(mut cx: FunctionContext) -> NeonResult<Option<String>> {
+    let (present,) = cx.args::<(bool,)>()?;
+    Ok(if present { Some("here".to_string()) } else { None })
+}
+
+pub fn large_integer(mut cx: FunctionContext) -> NeonResult<u64> {
+    let (exact,) = cx.args::<(bool,)>()?;
+    Ok(if exact { 9007199254740991 } else { 9007199254740993 })
+}
+
+pub fn convert_with_handles(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let list = match Vec::<String>::from_js(&mut cx, arg) {
+        Ok(list) => list,
+        Err(e) => return e.throw(&mut cx)
+    };
+    list.len().into_js(&mut cx)
+}
@@ -0,0 +1,32 @@
+use std::time::{Duration, UNIX_EPOCH};
+use neon::prelude::*;
+
+pub fn create_date(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let ms = cx.argument::<JsNumber>(0)?.value();
+    JsDate::new(&mut cx, ms)
+}
+
+pub fn create_date_from_system_time(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let time = UNIX_EPOCH + Duration::from_millis(1_000_000_000_123);
+    JsDate::from_system_time(&mut cx, time)
+}
+
+pub fn create_date_before_epoch(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let time = UNIX_EPOCH - Duration::from_millis(86_400_000);
+    JsDate::from_system_time(&mut cx, time)
+}
+
+pub fn get_date_value(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let date = cx.argument::<JsDate>(0)?;
+    Ok(cx.number(date.value()))
+}
+
+pub fn is_valid_date(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let date = cx.argument::<JsDate>(0)?;
+    Ok(cx.boolean(date.is_valid()))
+}
+
+pub fn is_date(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsValue>(0)?;
+    Ok(cx.boolean(value.is_a::<JsDate>()))
+}
@@ -0,0 +1,83 @@
+use neon::prelude::*;
+
+#[derive(FromJs, IntoJs)]
+#[neon(rename_all = "camelCase")]
+pub struct Profile {
+    user_name: String,
+    display_name: Option<String>,
+    #[neon(default)]
+    login_count: u32,
+    #[neon(rename = "isAdmin")]
+    admin: bool,
+    status: Status,
+    home: Location
+}
+
+#[derive(FromJs, IntoJs)]
+#[neon(rename_all = "kebab-case")]
+pub enum Status {
+    Active,
+    OnLeave
+}
+
+#[derive(FromJs, IntoJs)]
+pub struct Location(f64, f64);
+
+#[derive(FromJs, IntoJs)]
+pub struct Meters(f64);
+
+#[derive(FromJs, IntoJs)]
+#[neon(tag = "kind", rename_all = "lowercase")]
+pub enum Shape {
+    Circle { radius: Meters },
+    #[neon(rename_all = "camelCase")]
+    Rectangle { side_a: f64, side_b: f64 },
+    Point,
+    Polygon(Vec<(f64, f64)>)
+}
+
+pub fn make_profile(_: FunctionContext) -> NeonResult<Profile> {
+    Ok(Profile {
+        user_name: "ada".to_string(),
+        display_name: None,
+        login_count: 3,
+        admin: true,
+        status: Status::OnLeave,
+        home: Location(51.5, -0.1)
+    })
+}
+
+pub fn touch_profile(mut cx: FunctionContext) -> NeonResult<Profile> {
+    let (mut profile,) = cx.args::<(Profile,)>()?;
+    profile.login_count += 1;
+    profile.display_name = profile.display_name.map(|name| name.to_uppercase());
+    Ok(profile)
+}
+
+pub fn shape_area(mut cx: FunctionContext) -> NeonResult<f64> {
+    let (shape,) = cx.args::<(Shape,)>()?;
+    Ok(match shape {
+        Shape::Circle { radius: Meters(r) } => std::f64::consts::PI * r * r,
+        Shape::Rectangle { side_a, side_b } => side_a * side_b,
+        Shape::Point => 0.0,
+        Shape::Polygon(points) => {
+            let n = points.len();
+            let twice_area: f64 = (0..n).map(|i| {
+                let (x0, y0) = points[i];
+                let (x1, y1) = points[(i + 1) % n];
+                x0 * y1 - x1 * y0
+            }).sum();
+            twice_area.abs() / 2.0
+        }
+    })
+}
+
+pub fn scale_shape(mut cx: FunctionContext) -> NeonResult<Shape> {
+    let (shape, factor) = cx.args::<(Shape, f64)>()?;
+    Ok(match shape {
+        Shape::Circle { radius: Meters(r) } => Shape::Circle { radius: Meters(r * factor) },
+        Shape::Rectangle { side_a, side_b } => Shape::Rectangle { side_a: side_a * factor, side_b: side_b * factor },
+        Shape::Point => Shape::Point,
+        Shape::Polygon(points) => Shape::Polygon(points.into_iter().map(|(x, y)| (x * factor, y * factor)).collect())
+    })
+}
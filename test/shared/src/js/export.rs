@@ -0,0 +1,42 @@
+use neon::prelude::*;
+
+#[neon::export]
+fn exported_add(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+#[neon::export(name = "exportedGreeting")]
+fn exported_greeting(name: String, excited: Option<bool>) -> String {
+    let punctuation = if excited.unwrap_or(false) { "!" } else { "." };
+    format!("Hello, {}{}", name, punctuation)
+}
+
+#[neon::export]
+fn exported_divide(a: i32, b: i32) -> Result<i32, String> {
+    if b == 0 {
+        return Err("division by zero".to_string());
+    }
+    Ok(a / b)
+}
+
+#[neon::export]
+fn exported_nothing() { }
+
+#[neon::export]
+fn exported_with_context(cx: &mut FunctionContext, len: u32) -> NeonResult<Vec<u32>> {
+    if len > 100 {
+        return cx.throw_range_error("too long");
+    }
+    Ok((0..len).collect())
+}
+
+#[neon::export]
+fn exported_neon_function(mut cx: FunctionContext) -> JsResult<JsString> {
+    let n = cx.argument::<JsNumber>(0)?.value();
+    Ok(cx.string(format!("got {}", n)))
+}
+
+#[neon::export]
+fn exported_typed_neon_function(mut cx: FunctionContext) -> NeonResult<bool> {
+    Ok(cx.len() > 0)
+}
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use neon::prelude::*;
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+struct Tracked(Vec<u8>);
+
+impl AsMut<[u8]> for Tracked {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+pub fn external_buffer(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let data: Vec<u8> = (0..10).collect();
+    JsBuffer::external(&mut cx, data)
+}
+
+pub fn external_array_buffer(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    let data: Box<[u8]> = vec![1, 2, 3, 4].into_boxed_slice();
+    JsArrayBuffer::external(&mut cx, data)
+}
+
+pub fn tracked_external_buffer(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    JsBuffer::external(&mut cx, Tracked(vec![42; 16]))
+}
+
+pub fn tracked_external_array_buffer(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    JsArrayBuffer::external(&mut cx, Tracked(vec![42; 16]))
+}
+
+pub fn dropped_externals(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(DROPPED.load(Ordering::SeqCst) as f64))
+}
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use neon::prelude::*;
 use neon::object::This;
 
@@ -99,3 +101,33 @@ pub fn compute_scoped(mut cx: FunctionContext) -> JsResult<JsNumber> {
     }
     Ok(i)
 }
+
+pub fn make_counter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let count = Cell::new(cx.argument::<JsNumber>(0)?.value());
+    JsFunction::new(&mut cx, move |mut cx| {
+        count.set(count.get() + 1.0);
+        Ok(cx.number(count.get()))
+    })
+}
+
+static DROPPED_CLOSURES: AtomicUsize = AtomicUsize::new(0);
+
+struct DropCounter;
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        DROPPED_CLOSURES.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+pub fn make_tracked_closure(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let counter = DropCounter;
+    JsFunction::new(&mut cx, move |mut cx| {
+        let _ = &counter;
+        Ok(cx.undefined())
+    })
+}
+
+pub fn dropped_closures(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(DROPPED_CLOSURES.load(Ordering::SeqCst) as f64))
+}
@@ -0,0 +1,6 @@
+use neon::prelude::*;
+
+pub fn own_property_names(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let object = cx.argument::<JsObject>(0)?;
+    object.get_own_property_names(&mut cx)
+}
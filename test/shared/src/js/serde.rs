@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use neon::prelude::*;
+use serde::{Serialize, Deserialize};
+
+#[derive(Serialize, Deserialize)]
+struct User {
+    name: String,
+    age: u32,
+    email: Option<String>,
+    tags: Vec<String>,
+    role: Role
+}
+
+#[derive(Serialize, Deserialize)]
+enum Role {
+    Guest,
+    Member { since: u32 },
+    Admin(String)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Point {
+    x: f64,
+    y: f64
+}
+
+pub fn make_user(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let user = User {
+        name: "Ada".to_string(),
+        age: 36,
+        email: None,
+        tags: vec!["math".to_string(), "engines".to_string()],
+        role: Role::Member { since: 1843 }
+    };
+    neon::serde::to_value(&mut cx, &user)
+}
+
+pub fn roundtrip_user(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let mut user: User = neon::serde::from_value(&mut cx, arg)?;
+    user.age += 1;
+    neon::serde::to_value(&mut cx, &user)
+}
+
+pub fn describe_role(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let role: Role = neon::serde::from_value(&mut cx, arg)?;
+    let description = match role {
+        Role::Guest => "guest".to_string(),
+        Role::Member { since } => format!("member since {}", since),
+        Role::Admin(realm) => format!("admin of {}", realm)
+    };
+    Ok(cx.string(description))
+}
+
+pub fn point_length(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let point: Point = neon::serde::from_value(&mut cx, arg)?;
+    Ok(cx.number(point.x.hypot(point.y)))
+}
+
+pub fn count_words(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let words: Vec<String> = neon::serde::from_value(&mut cx, arg)?;
+    let mut counts = HashMap::new();
+    for word in words {
+        *counts.entry(word).or_insert(0u32) += 1;
+    }
+    neon::serde::to_value(&mut cx, &counts)
+}
+
+pub fn reverse_bytes(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let mut bytes: serde_bytes::ByteBuf = neon::serde::from_value(&mut cx, arg)?;
+    bytes.reverse();
+    neon::serde::to_value(&mut cx, &bytes)
+}
+
+pub fn ignore_value(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let _: serde::de::IgnoredAny = neon::serde::from_value(&mut cx, arg)?;
+    Ok(cx.undefined())
+}
@@ -0,0 +1,59 @@
+use neon::prelude::*;
+
+pub fn create_symbol(mut cx: FunctionContext) -> JsResult<JsSymbol> {
+    Ok(JsSymbol::new(&mut cx))
+}
+
+pub fn create_symbol_with_description(mut cx: FunctionContext) -> JsResult<JsSymbol> {
+    let description = cx.argument::<JsString>(0)?.value();
+    Ok(JsSymbol::with_description(&mut cx, description))
+}
+
+pub fn symbol_for(mut cx: FunctionContext) -> JsResult<JsSymbol> {
+    let key = cx.argument::<JsString>(0)?.value();
+    JsSymbol::for_key(&mut cx, key)
+}
+
+pub fn get_symbol_description(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let symbol = cx.argument::<JsSymbol>(0)?;
+    match symbol.description(&mut cx) {
+        Some(description) => Ok(description.upcast()),
+        None => Ok(cx.undefined().upcast())
+    }
+}
+
+pub fn get_well_known_symbols(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let symbols = [
+        JsSymbol::iterator(&mut cx)?,
+        JsSymbol::async_iterator(&mut cx)?,
+        JsSymbol::has_instance(&mut cx)?,
+        JsSymbol::to_primitive(&mut cx)?,
+        JsSymbol::to_string_tag(&mut cx)?,
+        JsSymbol::inspect_custom(&mut cx)?
+    ];
+    let array = JsArray::new(&mut cx, symbols.len() as u32);
+    for (i, symbol) in symbols.iter().enumerate() {
+        array.set(&mut cx, i as u32, *symbol)?;
+    }
+    Ok(array)
+}
+
+fn inspect(mut cx: FunctionContext) -> JsResult<JsString> {
+    Ok(cx.string("<rusty>"))
+}
+
+pub fn create_tagged_object(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let object = cx.empty_object();
+    let tag = JsSymbol::to_string_tag(&mut cx)?;
+    let name = cx.string("Rusty");
+    object.set(&mut cx, tag, name)?;
+    let custom = JsSymbol::inspect_custom(&mut cx)?;
+    let inspect = JsFunction::new(&mut cx, inspect)?;
+    object.set(&mut cx, custom, inspect)?;
+    Ok(object)
+}
+
+pub fn is_symbol(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsValue>(0)?;
+    Ok(cx.boolean(value.is_a::<JsSymbol>()))
+}
@@ -0,0 +1,64 @@
+use neon::prelude::*;
+
+pub fn create_float32_array(mut cx: FunctionContext) -> JsResult<JsTypedArray<f32>> {
+    let buffer = cx.argument::<JsArrayBuffer>(0)?;
+    let byte_offset = cx.argument::<JsNumber>(1)?.value() as usize;
+    let len = cx.argument::<JsNumber>(2)?.value() as usize;
+    JsTypedArray::new(&mut cx, buffer, byte_offset, len)
+}
+
+pub fn sum_float32_array(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsTypedArray<f32>>(0)?;
+    let sum: f32 = cx.borrow(&array, |data| data.iter().sum());
+    Ok(cx.number(sum))
+}
+
+pub fn double_float64_array(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let mut array = cx.argument::<JsTypedArray<f64>>(0)?;
+    cx.borrow_mut(&mut array, |mut data| {
+        for x in data.iter_mut() {
+            *x *= 2.0;
+        }
+    });
+    Ok(cx.undefined())
+}
+
+pub fn get_typed_array_info(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let array = cx.argument::<JsTypedArray<u16>>(0)?;
+    let info = cx.empty_object();
+    let buffer = array.buffer(&mut cx);
+    let byte_offset = cx.number(array.byte_offset() as f64);
+    let len = cx.number(array.len() as f64);
+    info.set(&mut cx, "buffer", buffer)?;
+    info.set(&mut cx, "byteOffset", byte_offset)?;
+    info.set(&mut cx, "length", len)?;
+    Ok(info)
+}
+
+pub fn is_uint8_array(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsValue>(0)?;
+    Ok(cx.boolean(value.is_a::<JsTypedArray<u8>>()))
+}
+
+pub fn create_data_view(mut cx: FunctionContext) -> JsResult<JsDataView> {
+    let buffer = cx.argument::<JsArrayBuffer>(0)?;
+    let byte_offset = cx.argument::<JsNumber>(1)?.value() as usize;
+    let byte_length = cx.argument::<JsNumber>(2)?.value() as usize;
+    JsDataView::new(&mut cx, buffer, byte_offset, byte_length)
+}
+
+pub fn fill_data_view(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let mut view = cx.argument::<JsDataView>(0)?;
+    let value = cx.argument::<JsNumber>(1)?.value() as u8;
+    cx.borrow_mut(&mut view, |data| {
+        for byte in data.as_mut_slice::<u8>() {
+            *byte = value;
+        }
+    });
+    Ok(cx.number(view.byte_length() as f64))
+}
+
+pub fn is_data_view(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsValue>(0)?;
+    Ok(cx.boolean(value.is_a::<JsDataView>()))
+}
@@ -0,0 +1,9 @@
+// Fixtures in this directory run against whichever addon is under test. Mocha is
+// started from that suite's package directory, so both the addon and chai resolve
+// from there.
+var path = require('path');
+
+var root = process.cwd();
+
+exports.addon = require(path.join(root, 'native'));
+exports.assert = require(require.resolve('chai', { paths: [root] })).assert;